@@ -0,0 +1,394 @@
+## Input errors
+
+input-invalid-format = Invalid format
+input-empty = Empty input
+input-invalid-position = Invalid position (e.g. h8)
+input-invalid-direction = Invalid direction (h for across, v for down)
+input-retry = Error: { $error }. Try again.
+
+## Move errors
+
+move-out-of-bounds = Out of bounds at { $pos }
+move-cell-occupied = Square already taken at { $pos }
+move-missing-letter = You don't have the letter '{ $letter }'
+move-letter-mismatch = Your word doesn't fit at { $pos }
+move-word-too-short = Words must have at least { $min } letters
+move-nothing-placed = A move must place at least one tile
+move-not-in-line = Tiles must form a single line with no gaps
+move-no-connection = The word must connect to the words on the board
+move-must-cover-start = The first word must cover { $pos }
+move-invalid-word = Invalid word (not in the dictionary)
+move-nothing-exchanged = Say which tiles to exchange (e.g. exchange KWZ)
+move-exchange-not-allowed = Cannot exchange: the bag needs at least { $count } tiles
+move-challenge-not-allowed = No challenges under these rules: words are checked when played
+move-nothing-to-challenge = No move to challenge
+move-no-dictionary = No dictionary to judge the challenge
+move-rejected = -> Illegal move: { $reason }. Try again.
+move-impossible = Illegal move: { $reason }
+
+## Chat errors
+
+chat-error-empty = Empty message
+chat-error-too-long = Message too long ({ $max } characters at most)
+chat-error-rate-limited = Too many messages, try again in { $secs } s
+chat-error-unknown-player = No player named { $name }
+chat-error-mute-self = You cannot mute yourself
+
+## Server errors
+
+server-error-version = Protocol version { $version } not supported (the server speaks { $server })
+server-error-hello-first = Say hello first
+server-error-empty-name = Empty name
+server-error-name-taken = Name already taken (wrong or missing token)
+server-error-bad-message = Unreadable message: { $message }
+server-error-seat-count = Between 1 and { $max } seats
+server-error-no-game = No game { $game }
+server-error-game-full = The game is full
+server-error-already-playing = You are playing in this game
+server-error-not-finished = Only finished games can be reviewed
+server-error-not-a-player = Only the players of the game can do that
+server-error-waiting = Waiting for players
+server-error-game-over = The game is over
+server-error-not-your-turn = Not your turn
+
+## Save errors
+
+save-error-malformed-header = Malformed save header
+save-error-checksum = Corrupted save (checksum { $found }, expected { $expected })
+save-error-unsupported-version = Unsupported save format version { $version }
+save-error-parse = Could not parse save: { $message }
+save-error-integrity = Inconsistent game: { $message }
+
+integrity-tile-count = { $found ->
+    [one] { $found } tile
+   *[other] { $found } tiles
+} { $letter } instead of { $expected } in the bag, racks and board
+integrity-start = Tiles on the board but none on the starting square
+integrity-detached = The tile on { $pos } isn't connected to the others
+integrity-move-score = Move of turn { $turn } recorded for { $found } points instead of { $expected }
+integrity-score = { $name } has { $found } points, moves and adjustments add up to { $expected }
+
+## Board and rack
+
+premium-double-letter = DL
+premium-triple-letter = TL
+premium-double-word = DW
+premium-triple-word = TW
+rack-title = Rack:
+rack-empty = (empty rack)
+bag-title = Bag contents
+bag-letter = Let
+bag-value = Val
+bag-count = Count
+bag-total = Total tiles: { $count }
+bag-remaining = Bag: { $count ->
+    [one] { $count } tile
+   *[other] { $count } tiles
+}
+rack-left = { $name } kept: { $letters }
+
+## Turns
+
+prompt-position = Square where the word starts (e.g. h8):
+prompt-direction = Direction (h/v):
+prompt-word = Your move:
+unseen-hint = Type "tiles" to see the tiles you can't see.
+move-accepted = -> Move accepted!
+move-played = { $name } plays { $word } for { $score } points
+move-passed = { $name } passes
+player-resigned = { $name } resigns
+move-scored = -> { $name } scores { $score } points
+bingo-note = (bingo!)
+player-draws = { $name } draws!
+tiles-exchanged = { $name } exchanges { $count ->
+    [one] { $count } tile
+   *[other] { $count } tiles
+}
+challenge-upheld = Challenge upheld: { $words } not valid, { $name }'s move is taken back.
+challenge-failed = Challenge failed: all of { $name }'s words are valid.
+challenge-penalty = { $name } loses { $points } points.
+challenge-turn-lost = { $name } loses their turn.
+commands-hint = Other commands: pass, exchange <tiles>, challenge, board, hooks [word], define <word>.
+time-up = Time is up for { $name }!
+game-over = Game over!
+player-fallback = Player { $id }
+overtime-penalty = -{ $points } overtime
+lost-on-time = lost on time
+resigned = resigned
+review-moves = Moves played:
+
+## Unseen tiles
+
+unseen-title = Unseen tiles ({ $count }):
+unseen-ratio = { $vowels } vowels / { $consonants } consonants · { $blanks } blank(s)
+
+## Chat
+
+chat-help =
+    Chat: /say <message>, /emote <{ $emotes }>,
+      /mute <player>, /unmute <player>, /chat to read it again
+chat-refused = -> Message refused: { $error }.
+chat-title = Chat
+chat-muted = { $name } will no longer see messages from { $target }.
+chat-already-muted = { $target } is already muted for { $name }.
+chat-unmuted = { $name } sees messages from { $target } again.
+chat-not-muted = { $target } was not muted for { $name }.
+emote-hello = Hello everyone!
+emote-nice-bingo = Nice bingo!
+emote-well-played = Well played!
+emote-thinking = Thinking...
+emote-oops = Oops!
+emote-good-game = Good game!
+
+## Profiles
+
+profile-rating = Rating: { $rating }
+profile-provisional = (provisional)
+profile-history = History: { $ratings }
+profile-games = Games played: { $played } (won: { $won })
+profile-average-score = Average score: { $score }
+profile-average-move = Average per move: { $score } ({ $moves } moves)
+profile-bingos = Bingos: { $count }
+profile-blanks = Blanks played: { $count }
+profile-best-word = Best word: { $word } ({ $score })
+profile-best-move = Best move: { $word } ({ $score })
+profile-top-words = Most played words: { $words }
+profile-none = No profiles yet.
+profile-unknown = No profile named '{ $name }'.
+leaderboard-empty = No rated games yet.
+leaderboard-player = Player
+leaderboard-games = Games
+
+## Save menu
+
+answer-yes = y
+prompt-time-control = Time control in minutes[+increment seconds], e.g. 25+0 (empty for none):
+time-control-invalid = Invalid time control.
+prompt-forfeit = Lose the game when the time runs out? (y/N):
+prompt-player-names = Enter player names, separated by commas:
+save-created = Created new game: { $path }
+save-status-finished = finished
+save-status-turn = turn { $turn }
+save-summary = { $title } — { $scores } — { $status } — last played { $date }
+saves-page = Saved games (page { $page }/{ $pages }):
+saves-page-filtered = Saved games matching '{ $query }' (page { $page }/{ $pages }):
+save-corrupted = corrupted
+save-too-new = made by a newer version
+save-inconsistent = inconsistent
+save-unreadable = could not load
+saves-help-new = n) Start a new game
+saves-help-manage = r <k> [title]) Rename   d <k>) Delete   a <k>) Archive (finished games)
+saves-help-analyze = x <k>) Analyze a finished game move by move
+saves-help-filter = f <name>) Filter by player (f alone clears)   <, >) Previous / next page
+saves-help-stats = s [name]) Player statistics   l) Leaderboard
+saves-none = No saved games found. Starting fresh!
+saves-no-match = No saved games match '{ $query }'.
+prompt-choose-save = Choose a save (1..), or a command:
+menu-invalid-choice = Invalid choice.
+save-no-number = No save numbered { $number }.
+save-loaded = Loaded game: { $path } (players: { $players })
+save-load-failed = Could not load { $path }: { $error }
+save-pick-another = Pick another save or choose 'n' for new.
+prompt-new-title = New title:
+prompt-analysis-export = Save the analysis to (a .json file gives JSON; Enter to skip):
+save-renamed = Renamed.
+save-rename-failed = Could not rename { $path }: { $error }
+prompt-delete = Delete { $path } for good? (y/N):
+save-deleted = Deleted.
+save-delete-failed = Could not delete { $path }: { $error }
+save-archive-unfinished = Only finished games can be archived.
+save-delete-unfinished = Only finished games can be deleted.
+save-archived = Archived to { $path }.
+save-archive-failed = Could not archive { $path }: { $error }
+current-players = Current players: { $players }
+
+## Network play
+
+lobby-help =
+    Commands: list, new <seats> [rules], join <number>, watch <number>,
+      review <number>, seek [min-max] [minutes+increment] [rules], cancel, quit
+      (rules: tournament, casual or family; the server's otherwise)
+lobby-no-games = No games in progress.
+lobby-waiting = waiting
+lobby-ongoing = in progress
+lobby-finished = finished
+lobby-spectators = { $count ->
+    [one] { $count } spectator
+   *[other] { $count } spectators
+}
+prompt-name = Your name:
+prompt-remote-move = Your move (e.g. h8 h WORD, pass, or tiles):
+remote-spectator = You are watching this game (quit to leave).
+remote-not-your-turn = It's not your turn.
+remote-connected = Connected as { $name }.
+remote-server-says = Server: { $message }
+remote-joined = Game { $game }: you are in seat { $seat }.
+remote-waiting-players = Waiting for the other players...
+remote-waiting-for = Waiting for { $name }...
+remote-seeking = Looking for an opponent (your rating: { $rating })...
+remote-seek-cancelled = Search cancelled.
+remote-watching = You are watching game { $game }.
+remote-muted = You will no longer see messages from { $name }.
+remote-unmuted = You see messages from { $name } again.
+remote-send-failed = Could not send: { $error }
+remote-reconnecting = Connection lost, reconnecting to { $addr }...
+remote-hosting = Game server on { $addr }
+remote-server-stopped = Server stopped: { $error }
+server-listening = krabbels server listening on { $addr }
+server-profiles = player profiles in { $path }
+
+## Full-screen interface
+
+tui-too-small = Window too small for the board
+tui-bag = Bag
+tui-bag-count = { $left } / { $total } tiles
+tui-unseen = Unseen ({ $count })
+tui-moves = Moves
+tui-turn = Turn { $turn }
+tui-preview = · { $score } points
+tui-keys = ←↑↓→ square · letters to place · ⌫ take back · Tab direction · Enter play · :pass · /say · Esc
+tui-keys-ascii = <- ^ v -> square - letters to place - Backspace take back - Tab direction - Enter play - :pass - /say - Esc
+
+## Linear mode
+
+linear-board-empty = The board is empty.
+linear-row = Row { $row }: { $runs }
+linear-run = { $letters } from { $from } to { $to }
+linear-single = { $letter } at { $pos }
+linear-blank-note = (blank: { $letters })
+linear-turn = Turn { $turn }, { $name } to play.
+linear-scores = Scores: { $scores }.
+linear-score-entry = { $name } { $score } points
+linear-rack = Rack: { $tiles }.
+linear-rack-empty = Empty rack.
+linear-blank = blank
+linear-move = { $name } plays { $word } at { $pos }, { $direction }, for { $score } points.
+linear-across = across
+linear-down = down
+linear-also = Other words: { $words }.
+
+## Word search
+
+query-invalid-pattern = Invalid pattern '{ $value }' (e.g. C?A*, *[AEI]RE)
+query-invalid-letters = Invalid letters '{ $value }'
+no-dictionary = This needs a dictionary (--dictionary or the dictionary key).
+words-none = No word found.
+words-found = { $count ->
+    [one] { $count } word
+   *[other] { $count } words
+}
+words-more = ... and { $count } more.
+words-points = pts
+words-odds = 1 in { $odds }
+words-odds-none = not in the bag
+
+hooks-title = Hooks of { $word }
+hooks-front = Front hooks: { $letters }
+hooks-back = Back hooks: { $letters }
+hooks-inner = Inner extensions: { $words }
+hooks-longer = Longer words ({ $count }): { $words }
+hooks-none = none
+hooks-board-title = Hook squares:
+hooks-board-none = No hook square on the board.
+hooks-square = { $pos }: { $letters }
+
+definition = { $word }: { $text }
+definition-missing = No definition for { $word }.
+no-definitions = No definitions file (--definitions or the definitions key).
+
+## Word judge
+
+judge-title = Word judge
+judge-help = Type every word of the play, separated by spaces; the verdict covers them all. quit to leave.
+judge-prompt = Words:
+judge-acceptable = ACCEPTABLE
+judge-not-acceptable = NOT ACCEPTABLE
+judge-next = Press Enter for the next play.
+judge-invalid = Letters only, words separated by spaces.
+judge-log-failed = Could not write the adjudication log: { $error }
+
+## Flashcards
+
+study-invalid-ranks = Invalid ranks '{ $value }' (e.g. 1-200)
+study-help = { $count ->
+    [one] { $count } card.
+   *[other] { $count } cards.
+} Type every word the letters make, separated by spaces; Enter alone if you don't know. quit to stop.
+study-question = { $letters } ({ $count ->
+    [one] { $count } word
+   *[other] { $count } words
+}):
+study-correct = Right!
+study-wrong = Answer: { $words }
+study-not-words = Not words: { $words }
+study-done = { $right } right out of { $asked }.
+study-nothing-due = Nothing to review and no new card in this quiz.
+study-summary = Cards: { $cards }, due now: { $due }, learnt: { $mastered }.
+study-accuracy = { $reviews } answers, { $percent }% right.
+study-no-reviews = No answer in the last { $days } days.
+study-day = Day
+study-answers = Answers
+study-right = Right
+study-time = Time
+
+## Game analysis
+
+analysis-running = Analyzing the game...
+analysis-error-unfinished = Only finished games can be analyzed.
+analysis-error-not-recorded = This game was saved before racks were recorded (turn { $turn }); it can't be analyzed.
+analysis-title = Move by move (score, equity: score plus the worth of the tiles kept)
+analysis-choice = { $play } { $score } ({ $equity })
+analysis-top = top play
+analysis-best-score = Best score: { $play }, { $lost ->
+    [one] { $lost } point lost
+   *[other] { $lost } points lost
+}
+analysis-best-equity = Best equity: { $play }, { $lost } equity lost
+analysis-missed-bingo = Missed bingo: { $play }
+analysis-phonies = Phonies: { $words }
+analysis-exchange = exchange { $letters }
+analysis-pass = pass
+analysis-withdrawn = withdrawn ({ $words })
+analysis-challenge-lost = turn lost to a challenge
+analysis-summary = Summary:
+analysis-player = { $name }: { $equity } equity and { $points } points lost, accuracy { $accuracy }% ({ $top } top plays out of { $turns } turns)
+analysis-player-bingos = Bingos: { $bingos }, missed: { $missed }, phonies: { $phonies }
+analysis-exported = Analysis written to { $path }.
+analysis-export-failed = Could not write { $path }: { $error }
+
+## Self-play
+
+selfplay-players = Between 2 and 4 bots at a table.
+selfplay-running = { $games } games between { $players }, on { $threads ->
+    [one] { $threads } thread
+   *[other] { $threads } threads
+}, from seed { $seed }
+selfplay-progress = { $done }/{ $games } games played
+selfplay-done = { $games } games in { $secs } s
+selfplay-aborted = Game with seed { $seed } stopped on turn { $turn }, a bot's move was refused: { $reason }
+selfplay-aborted-count = { $count ->
+    [one] { $count } game stopped on a refused move and is left out.
+   *[other] { $count } games stopped on a refused move and are left out.
+}
+selfplay-player = Bot
+selfplay-wins = Wins
+selfplay-win-rate = Win rate (95% CI)
+selfplay-spread = Spread
+selfplay-score = Score
+selfplay-bingos = Bingos
+selfplay-move = Move
+selfplay-archived = Games written to { $path }.
+selfplay-rated = Bot ratings updated (leaderboard: l in the save menu).
+
+## Configuration
+
+config-error-read = Could not read { $path }: { $message }
+config-error-parse = Invalid config file { $path }: { $message }
+config-error-language = Unknown language '{ $value }' (fr or en)
+config-error-tile-set = Unknown tile set '{ $value }' (french or english)
+config-error-time-control = Invalid time control '{ $value }' (e.g. 25+5)
+config-error-board-layout = Invalid board layout in { $path }: 15 lines of 15 squares among . d t D T
+config-error-rule = Invalid value for rules.{ $key }: { $value }
+config-error-dictionary = Could not read the dictionary { $path }: { $message }
+config-error-definitions = Could not read the definitions { $path }: { $message }
+prompt-player-names-default = Enter player names, separated by commas [{ $players }]:
@@ -0,0 +1,394 @@
+## Input errors
+
+input-invalid-format = Format invalide
+input-empty = Entrée vide
+input-invalid-position = Position invalide (ex: h8)
+input-invalid-direction = Direction invalide (h pour horizontal, v pour vertical)
+input-retry = Erreur : { $error }. Réessayez.
+
+## Move errors
+
+move-out-of-bounds = Hors limites à la position { $pos }
+move-cell-occupied = Case occupée à la position { $pos }
+move-missing-letter = Vous n'avez pas la lettre '{ $letter }'
+move-letter-mismatch = Votre mot ne s'insère pas à { $pos }
+move-word-too-short = Le mot doit contenir au moins { $min } lettres
+move-nothing-placed = Le coup doit poser au moins une lettre
+move-not-in-line = Les lettres doivent former une seule ligne, sans trou
+move-no-connection = Le mot doit être connecté aux mots existants
+move-must-cover-start = Le premier mot doit passer par { $pos }
+move-invalid-word = Mot invalide (pas dans le dictionnaire)
+move-nothing-exchanged = Indiquez les lettres à échanger (ex : echange KWZ)
+move-exchange-not-allowed = Échange impossible : il faut au moins { $count } lettres dans le sac
+move-challenge-not-allowed = Pas de contestation avec ces règles : les mots sont vérifiés à la pose
+move-nothing-to-challenge = Aucun coup à contester
+move-no-dictionary = Pas de dictionnaire pour juger la contestation
+move-rejected = -> Coup impossible : { $reason }. Recommencez.
+move-impossible = Coup impossible : { $reason }
+
+## Chat errors
+
+chat-error-empty = Message vide
+chat-error-too-long = Message trop long ({ $max } caractères au plus)
+chat-error-rate-limited = Trop de messages, réessayez dans { $secs } s
+chat-error-unknown-player = Aucun joueur nommé { $name }
+chat-error-mute-self = Vous ne pouvez pas vous rendre muet vous-même
+
+## Server errors
+
+server-error-version = Version de protocole { $version } non prise en charge (le serveur parle la { $server })
+server-error-hello-first = Commencez par vous présenter
+server-error-empty-name = Nom vide
+server-error-name-taken = Nom déjà pris (jeton absent ou faux)
+server-error-bad-message = Message illisible : { $message }
+server-error-seat-count = Entre 1 et { $max } places
+server-error-no-game = Pas de partie { $game }
+server-error-game-full = La partie est complète
+server-error-already-playing = Vous jouez dans cette partie
+server-error-not-finished = Seules les parties terminées peuvent être revues
+server-error-not-a-player = Réservé aux joueurs de la partie
+server-error-waiting = En attente de joueurs
+server-error-game-over = La partie est terminée
+server-error-not-your-turn = Ce n'est pas votre tour
+
+## Save errors
+
+save-error-malformed-header = En-tête de sauvegarde illisible
+save-error-checksum = Sauvegarde corrompue (somme { $found }, attendue { $expected })
+save-error-unsupported-version = Version de sauvegarde { $version } non prise en charge
+save-error-parse = Lecture de la sauvegarde impossible : { $message }
+save-error-integrity = Partie incohérente : { $message }
+
+integrity-tile-count = { $found ->
+    [one] { $found } jeton
+   *[other] { $found } jetons
+} { $letter } au lieu de { $expected } dans le sac, les chevalets et sur le plateau
+integrity-start = Des lettres sur le plateau mais aucune sur la case de départ
+integrity-detached = La lettre en { $pos } n'est pas reliée aux autres
+integrity-move-score = Coup du tour { $turn } noté { $found } points au lieu de { $expected }
+integrity-score = { $name } a { $found } points, les coups et ajustements donnent { $expected }
+
+## Board and rack
+
+premium-double-letter = LD
+premium-triple-letter = LT
+premium-double-word = MD
+premium-triple-word = MT
+rack-title = Chevalet :
+rack-empty = (chevalet vide)
+bag-title = Contenu du sac
+bag-letter = Let
+bag-value = Val
+bag-count = Nbre
+bag-total = Total : { $count } tuiles
+bag-remaining = Sac : { $count ->
+    [one] { $count } lettre
+   *[other] { $count } lettres
+}
+rack-left = Reste de { $name } : { $letters }
+
+## Turns
+
+prompt-position = Position où commence le mot (ex: h8) :
+prompt-direction = Direction (h/v) :
+prompt-word = Votre coup :
+unseen-hint = Tapez « tuiles » pour voir les tuiles que vous ne voyez pas.
+move-accepted = -> Coup accepté !
+move-played = { $name } joue { $word } pour { $score } points
+move-passed = { $name } passe
+player-resigned = { $name } abandonne
+move-scored = -> { $name } marque { $score } points
+bingo-note = (scrabble !)
+player-draws = { $name } pioche !
+tiles-exchanged = { $name } échange { $count ->
+    [one] { $count } lettre
+   *[other] { $count } lettres
+}
+challenge-upheld = Contestation acceptée : { $words } refusé, le coup de { $name } est retiré.
+challenge-failed = Contestation rejetée : tous les mots de { $name } sont valides.
+challenge-penalty = { $name } perd { $points } points.
+challenge-turn-lost = { $name } perd son tour.
+commands-hint = Autres commandes : passe, echange <lettres>, conteste, plateau, crochets [mot], definir <mot>.
+time-up = Temps écoulé pour { $name } !
+game-over = Partie terminée !
+player-fallback = Joueur { $id }
+overtime-penalty = -{ $points } dépassement de temps
+lost-on-time = perdu au temps
+resigned = a abandonné
+review-moves = Coups joués :
+
+## Unseen tiles
+
+unseen-title = Tuiles inconnues ({ $count }) :
+unseen-ratio = { $vowels } voyelles / { $consonants } consonnes · { $blanks } joker(s)
+
+## Chat
+
+chat-help =
+    Discussion : /dire <message>, /emote <{ $emotes }>,
+      /muet <joueur>, /parle <joueur>, /chat pour relire
+chat-refused = -> Message refusé : { $error }.
+chat-title = Discussion
+chat-muted = { $name } ne verra plus les messages de { $target }.
+chat-already-muted = { $target } est déjà muet pour { $name }.
+chat-unmuted = { $name } voit de nouveau les messages de { $target }.
+chat-not-muted = { $target } n'était pas muet pour { $name }.
+emote-hello = Bonjour tout le monde !
+emote-nice-bingo = Joli scrabble !
+emote-well-played = Bien joué !
+emote-thinking = Je réfléchis...
+emote-oops = Oups !
+emote-good-game = Bonne partie !
+
+## Profiles
+
+profile-rating = Classement : { $rating }
+profile-provisional = (provisoire)
+profile-history = Évolution : { $ratings }
+profile-games = Parties jouées : { $played } (gagnées : { $won })
+profile-average-score = Score moyen : { $score }
+profile-average-move = Score moyen par coup : { $score } ({ $moves } coups)
+profile-bingos = Scrabbles : { $count }
+profile-blanks = Jokers joués : { $count }
+profile-best-word = Meilleur mot : { $word } ({ $score })
+profile-best-move = Meilleur coup : { $word } ({ $score })
+profile-top-words = Mots les plus joués : { $words }
+profile-none = Aucun profil pour l'instant.
+profile-unknown = Aucun profil nommé '{ $name }'.
+leaderboard-empty = Aucune partie classée pour l'instant.
+leaderboard-player = Joueur
+leaderboard-games = Parties
+
+## Save menu
+
+answer-yes = o
+prompt-time-control = Cadence en minutes[+incrément en secondes], ex. 25+0 (vide pour aucune) :
+time-control-invalid = Cadence invalide.
+prompt-forfeit = Perdre la partie quand le temps est écoulé ? (o/N) :
+prompt-player-names = Noms des joueurs, séparés par des virgules :
+save-created = Nouvelle partie : { $path }
+save-status-finished = terminée
+save-status-turn = tour { $turn }
+save-summary = { $title } — { $scores } — { $status } — jouée le { $date }
+saves-page = Parties sauvegardées (page { $page }/{ $pages }) :
+saves-page-filtered = Parties avec '{ $query }' (page { $page }/{ $pages }) :
+save-corrupted = corrompue
+save-too-new = créée par une version plus récente
+save-inconsistent = incohérente
+save-unreadable = illisible
+saves-help-new = n) Nouvelle partie
+saves-help-manage = r <k> [titre]) Renommer   d <k>) Supprimer   a <k>) Archiver (parties terminées)
+saves-help-analyze = x <k>) Analyser une partie terminée coup par coup
+saves-help-filter = f <nom>) Filtrer par joueur (f seul pour tout voir)   <, >) Page précédente / suivante
+saves-help-stats = s [nom]) Statistiques des joueurs   l) Classement
+saves-none = Aucune partie sauvegardée. On commence !
+saves-no-match = Aucune partie avec '{ $query }'.
+prompt-choose-save = Choisissez une partie (1..) ou une commande :
+menu-invalid-choice = Choix invalide.
+save-no-number = Pas de partie numéro { $number }.
+save-loaded = Partie chargée : { $path } (joueurs : { $players })
+save-load-failed = Impossible de charger { $path } : { $error }
+save-pick-another = Choisissez une autre partie, ou 'n' pour en commencer une.
+prompt-new-title = Nouveau titre :
+prompt-analysis-export = Enregistrer l'analyse dans (un fichier .json pour du JSON ; Entrée pour passer) :
+save-renamed = Renommée.
+save-rename-failed = Impossible de renommer { $path } : { $error }
+prompt-delete = Supprimer { $path } pour de bon ? (o/N) :
+save-deleted = Supprimée.
+save-delete-failed = Impossible de supprimer { $path } : { $error }
+save-archive-unfinished = Seules les parties terminées peuvent être archivées.
+save-delete-unfinished = Seules les parties terminées peuvent être supprimées.
+save-archived = Archivée dans { $path }.
+save-archive-failed = Impossible d'archiver { $path } : { $error }
+current-players = Joueurs : { $players }
+
+## Network play
+
+lobby-help =
+    Commandes : liste, nouvelle <places> [règles], rejoindre <numéro>, regarder <numéro>,
+      revoir <numéro>, chercher [min-max] [minutes+incrément] [règles], annuler, quitter
+      (règles : tournament, casual ou family ; celles du serveur sinon)
+lobby-no-games = Aucune partie en cours.
+lobby-waiting = en attente
+lobby-ongoing = en cours
+lobby-finished = terminée
+lobby-spectators = { $count ->
+    [one] { $count } spectateur
+   *[other] { $count } spectateurs
+}
+prompt-name = Votre nom :
+prompt-remote-move = Votre coup (ex: h8 h MOT, passe, ou tuiles) :
+remote-spectator = Vous regardez cette partie (quitter pour sortir).
+remote-not-your-turn = Ce n'est pas votre tour.
+remote-connected = Connecté en tant que { $name }.
+remote-server-says = Serveur : { $message }
+remote-joined = Partie { $game } : vous êtes à la place { $seat }.
+remote-waiting-players = En attente des autres joueurs...
+remote-waiting-for = En attente de { $name }...
+remote-seeking = Recherche d'un adversaire (votre classement : { $rating })...
+remote-seek-cancelled = Recherche annulée.
+remote-watching = Vous regardez la partie { $game }.
+remote-muted = Vous ne verrez plus les messages de { $name }.
+remote-unmuted = Vous voyez de nouveau les messages de { $name }.
+remote-send-failed = Envoi impossible : { $error }
+remote-reconnecting = Connexion perdue, reconnexion à { $addr }...
+remote-hosting = Serveur de partie sur { $addr }
+remote-server-stopped = Serveur arrêté : { $error }
+server-listening = Serveur krabbels à l'écoute sur { $addr }
+server-profiles = Profils des joueurs dans { $path }
+
+## Full-screen interface
+
+tui-too-small = Fenêtre trop petite pour le plateau
+tui-bag = Sac
+tui-bag-count = { $left } / { $total } lettres
+tui-unseen = Inconnues ({ $count })
+tui-moves = Coups
+tui-turn = Tour { $turn }
+tui-preview = · { $score } points
+tui-keys = ←↑↓→ case · lettres pour poser · ⌫ reprendre · Tab sens · Entrée jouer · :passe · /dire · Échap
+tui-keys-ascii = <- ^ v -> case - lettres pour poser - Retour arrière reprendre - Tab sens - Entrée jouer - :passe - /dire - Échap
+
+## Linear mode
+
+linear-board-empty = Le plateau est vide.
+linear-row = Ligne { $row } : { $runs }
+linear-run = { $letters } de { $from } à { $to }
+linear-single = { $letter } en { $pos }
+linear-blank-note = (joker : { $letters })
+linear-turn = Tour { $turn }, au tour de { $name }.
+linear-scores = Scores : { $scores }.
+linear-score-entry = { $name } { $score } points
+linear-rack = Chevalet : { $tiles }.
+linear-rack-empty = Chevalet vide.
+linear-blank = joker
+linear-move = { $name } joue { $word } en { $pos }, { $direction }, pour { $score } points.
+linear-across = horizontalement
+linear-down = verticalement
+linear-also = Autres mots : { $words }.
+
+## Recherche de mots
+
+query-invalid-pattern = Motif invalide '{ $value }' (ex. C?A*, *[AEI]RE)
+query-invalid-letters = Lettres invalides '{ $value }'
+no-dictionary = Il faut un dictionnaire pour cela (--dictionary ou la clé dictionary).
+words-none = Aucun mot trouvé.
+words-found = { $count ->
+    [one] { $count } mot
+   *[other] { $count } mots
+}
+words-more = ... et { $count } de plus.
+words-points = pts
+words-odds = 1 sur { $odds }
+words-odds-none = absent du sac
+
+hooks-title = Rallonges de { $word }
+hooks-front = Raccords devant : { $letters }
+hooks-back = Raccords derrière : { $letters }
+hooks-inner = Insertions : { $words }
+hooks-longer = Mots plus longs ({ $count }) : { $words }
+hooks-none = aucun
+hooks-board-title = Cases de raccord :
+hooks-board-none = Aucune case de raccord sur le plateau.
+hooks-square = { $pos } : { $letters }
+
+definition = { $word } : { $text }
+definition-missing = Pas de définition pour { $word }.
+no-definitions = Pas de fichier de définitions (--definitions ou la clé definitions).
+
+## Arbitrage
+
+judge-title = Arbitrage
+judge-help = Tapez tous les mots du coup, séparés par des espaces ; le verdict porte sur l'ensemble. quitter pour sortir.
+judge-prompt = Mots :
+judge-acceptable = ACCEPTABLE
+judge-not-acceptable = NON ACCEPTABLE
+judge-next = Entrée pour le coup suivant.
+judge-invalid = Des lettres seulement, mots séparés par des espaces.
+judge-log-failed = Impossible d'écrire le journal d'arbitrage : { $error }
+
+## Fiches
+
+study-invalid-ranks = Rangs invalides '{ $value }' (ex. 1-200)
+study-help = { $count ->
+    [one] { $count } fiche.
+   *[other] { $count } fiches.
+} Tapez tous les mots que forment les lettres, séparés par des espaces ; Entrée seule si vous ne savez pas. quitter pour arrêter.
+study-question = { $letters } ({ $count ->
+    [one] { $count } mot
+   *[other] { $count } mots
+}) :
+study-correct = Juste !
+study-wrong = Réponse : { $words }
+study-not-words = Pas des mots : { $words }
+study-done = { $right } juste(s) sur { $asked }.
+study-nothing-due = Rien à revoir et aucune nouvelle fiche dans ce quiz.
+study-summary = Fiches : { $cards }, à revoir maintenant : { $due }, acquises : { $mastered }.
+study-accuracy = { $reviews } réponses, { $percent } % justes.
+study-no-reviews = Aucune réponse ces { $days } derniers jours.
+study-day = Jour
+study-answers = Réponses
+study-right = Justes
+study-time = Temps
+
+## Analyse de partie
+
+analysis-running = Analyse de la partie...
+analysis-error-unfinished = Seules les parties terminées peuvent être analysées.
+analysis-error-not-recorded = Cette partie a été enregistrée avant que les chevalets soient notés (tour { $turn }) ; elle ne peut pas être analysée.
+analysis-title = Coup par coup (score, équité : le score plus la valeur des lettres gardées)
+analysis-choice = { $play } { $score } ({ $equity })
+analysis-top = meilleur coup
+analysis-best-score = Meilleur score : { $play }, { $lost ->
+    [one] { $lost } point perdu
+   *[other] { $lost } points perdus
+}
+analysis-best-equity = Meilleure équité : { $play }, { $lost } d'équité perdue
+analysis-missed-bingo = Scrabble manqué : { $play }
+analysis-phonies = Mots invalides : { $words }
+analysis-exchange = échange { $letters }
+analysis-pass = passe
+analysis-withdrawn = retiré ({ $words })
+analysis-challenge-lost = tour perdu sur contestation
+analysis-summary = Bilan :
+analysis-player = { $name } : { $equity } d'équité et { $points } points perdus, précision { $accuracy } % ({ $top } meilleurs coups sur { $turns } tours)
+analysis-player-bingos = Scrabbles : { $bingos }, manqués : { $missed }, mots invalides : { $phonies }
+analysis-exported = Analyse écrite dans { $path }.
+analysis-export-failed = Impossible d'écrire { $path } : { $error }
+
+## Parties entre robots
+
+selfplay-players = Entre 2 et 4 robots à une table.
+selfplay-running = { $games } parties entre { $players }, sur { $threads ->
+    [one] { $threads } fil
+   *[other] { $threads } fils
+}, à partir de la graine { $seed }
+selfplay-progress = { $done }/{ $games } parties jouées
+selfplay-done = { $games } parties en { $secs } s
+selfplay-aborted = Partie de graine { $seed } arrêtée au tour { $turn }, coup d'un robot refusé : { $reason }
+selfplay-aborted-count = { $count ->
+    [one] { $count } partie arrêtée sur un coup refusé, laissée de côté.
+   *[other] { $count } parties arrêtées sur un coup refusé, laissées de côté.
+}
+selfplay-player = Robot
+selfplay-wins = Gains
+selfplay-win-rate = Victoires (IC 95 %)
+selfplay-spread = Écart
+selfplay-score = Score
+selfplay-bingos = Scrabbles
+selfplay-move = Coup
+selfplay-archived = Parties écrites dans { $path }.
+selfplay-rated = Classement des robots mis à jour (l dans le menu des parties).
+
+## Configuration
+
+config-error-read = Impossible de lire { $path } : { $message }
+config-error-parse = Fichier de configuration { $path } invalide : { $message }
+config-error-language = Langue inconnue '{ $value }' (fr ou en)
+config-error-tile-set = Jeu de lettres inconnu '{ $value }' (french ou english)
+config-error-time-control = Cadence invalide '{ $value }' (ex. 25+5)
+config-error-board-layout = Plateau invalide dans { $path } : 15 lignes de 15 cases parmi . d t D T
+config-error-rule = Valeur invalide pour rules.{ $key } : { $value }
+config-error-dictionary = Impossible de lire le dictionnaire { $path } : { $message }
+config-error-definitions = Impossible de lire les définitions { $path } : { $message }
+prompt-player-names-default = Noms des joueurs, séparés par des virgules [{ $players }] :
@@ -1,32 +1,316 @@
-use crate::game::Game;
-use crate::ui::{display, menu};
+use std::io::{self, IsTerminal};
 
-pub fn run() {
-    let mut game = Game::new();
-    game.add_player("Foufou".to_string());
+use crate::config::{Interface, Settings};
+use crate::game::definitions::Definitions;
+use crate::game::view::CHAT_BACKLOG;
+use crate::game::{Game, GameEvent};
+use crate::profile::ProfileStore;
+use crate::save::{Session, now_unix};
+use crate::study::hooks;
+use crate::t;
+use crate::ui::chat::{self, ChatCommand};
+use crate::ui::display;
+use crate::ui::linear;
+use crate::ui::menu::{self, TurnInput};
+use crate::ui::theme::appearance;
+use crate::ui::tui::{Status, Tui};
+use crate::ui::words;
 
-    display::show_game(&game);
+// Chat doesn't use up the turn. Returns a line for the player, if any;
+// a posted message shows up in the chat itself.
+fn run_chat_command(game: &mut Game, command: ChatCommand) -> Result<Option<String>, String> {
+    let name = game.current_player().name.clone();
+    match command {
+        ChatCommand::Say(body) => game
+            .chat
+            .post(&name, body, now_unix())
+            .map(|_| None)
+            .map_err(|e| e.to_string()),
+        ChatCommand::Mute(target) | ChatCommand::Unmute(target) if target == name => {
+            Err(t!("chat-error-mute-self"))
+        }
+        ChatCommand::Mute(target) => match game.set_muted(&name, &target, true) {
+            Ok(true) => Ok(Some(t!("chat-muted", name = &name, target = &target))),
+            Ok(false) => Ok(Some(t!(
+                "chat-already-muted",
+                name = &name,
+                target = &target
+            ))),
+            Err(e) => Err(e.to_string()),
+        },
+        ChatCommand::Unmute(target) => match game.set_muted(&name, &target, false) {
+            Ok(true) => Ok(Some(t!("chat-unmuted", name = &name, target = &target))),
+            Ok(false) => Ok(Some(t!("chat-not-muted", name = &name, target = &target))),
+            Err(e) => Err(e.to_string()),
+        },
+        ChatCommand::Show => Ok(None),
+    }
+}
+
+fn show_chat_pane(game: &Game) {
+    let name = &game.current_player().name;
+    chat::show_pane(&game.chat.recent(Some(name), chat::PANE_LINES));
+}
+
+fn player_name(game: &Game, player_id: usize) -> String {
+    game.players
+        .iter()
+        .find(|p| p.id == player_id)
+        .map(|p| p.name.clone())
+        .unwrap_or_default()
+}
+
+// What the status line says after a move went through.
+fn describe_move(game: &Game, events: &[GameEvent]) -> String {
+    let name = &game.current_player().name;
+    let notes: Vec<String> = events
+        .iter()
+        .filter_map(|event| display::describe_event(event, &|id| player_name(game, id)))
+        .collect();
+    if !notes.is_empty() {
+        return notes.join(" ");
+    }
+    let placed = events
+        .iter()
+        .any(|event| matches!(event, GameEvent::MoveScored { .. }));
+    match game.history.last() {
+        Some(record) if placed && appearance().linear => linear::describe_move(name, record),
+        Some(record) if placed => {
+            let word = record.words.first().map(|(w, _)| w.as_str()).unwrap_or("-");
+            let played = t!(
+                "move-played",
+                name = name,
+                word = word,
+                score = record.score
+            );
+            if record.bingo {
+                format!("{} {}", played, t!("bingo-note"))
+            } else {
+                played
+            }
+        }
+        _ => t!("move-passed", name = name),
+    }
+}
+
+// The meanings to show after a move, one per line.
+fn move_definitions(events: &[GameEvent], definitions: Option<&Definitions>) -> Vec<String> {
+    let Some(definitions) = definitions else {
+        return Vec::new();
+    };
+    events
+        .iter()
+        .flat_map(|event| display::event_definitions(event, definitions))
+        .collect()
+}
+
+fn define(word: &str, definitions: Option<&Definitions>) -> Result<String, String> {
+    definitions
+        .map(|definitions| display::definition_line(definitions, word))
+        .ok_or_else(|| t!("no-definitions"))
+}
+
+fn run_tui(session: &mut Session, definitions: Option<&Definitions>) -> io::Result<()> {
+    let mut tui = Tui::enter()?;
+
+    while !session.save.game.game_over {
+        let game = &mut session.save.game;
+        game.start_turn();
+        let Some(input) = tui.prompt_turn(game)? else {
+            // Quitting mid-turn doesn't give the time back.
+            session.store()?;
+            break;
+        };
+        let action = match input {
+            TurnInput::Play(action) => action,
+            // Always on screen already.
+            TurnInput::ShowUnseen | TurnInput::ShowBoard => continue,
+            TurnInput::ShowHooks(word) => {
+                let text =
+                    match (&game.lexicon, word) {
+                        (None, _) => Err(t!("no-dictionary")),
+                        (Some(lexicon), Some(word)) => Ok(words::hooks_summary(
+                            &hooks::find_hooks(&word, lexicon, game.tile_set),
+                        )),
+                        (Some(lexicon), None) => Ok(words::board_hooks_summary(
+                            &hooks::board_hooks(&game.board, lexicon, game.tile_set),
+                        )),
+                    };
+                tui.set_status(text.map_or_else(Status::Error, Status::Info));
+                continue;
+            }
+            TurnInput::Define(word) => {
+                let text = define(&word, definitions);
+                tui.set_status(text.map_or_else(Status::Error, Status::Info));
+                continue;
+            }
+            TurnInput::Chat(command) => {
+                match run_chat_command(game, command) {
+                    Ok(Some(text)) => tui.set_status(Status::Info(text)),
+                    Ok(None) => {}
+                    Err(e) => tui.set_status(Status::Error(e)),
+                }
+                session.store()?;
+                continue;
+            }
+        };
+
+        if game.check_timeout() {
+            let text = t!("time-up", name = &game.current_player().name);
+            tui.set_status(Status::Error(text));
+        } else {
+            let events = match game.apply_move(action) {
+                Ok(events) => events,
+                Err(e) => {
+                    tui.set_status(Status::Error(t!("move-impossible", reason = e.to_string())));
+                    continue;
+                }
+            };
+            let mut text = describe_move(game, &events);
+            for line in move_definitions(&events, definitions) {
+                text = format!("{} {}", text, line);
+            }
+            tui.set_status(Status::Info(text));
+            if !events.iter().any(GameEvent::keeps_turn) {
+                game.end_turn();
+            }
+        }
 
-    loop {
-        let action = menu::prompt_action(&game.board, &game.players[0].rack);
+        session.store()?;
+    }
+
+    Ok(())
+}
+
+fn run_lines(session: &mut Session, definitions: Option<&Definitions>) -> io::Result<()> {
+    display::show_game(&session.save.game);
+    println!("{}", t!("unseen-hint"));
+    println!("{}", t!("commands-hint"));
+    chat::show_help();
+    show_chat_pane(&session.save.game);
 
-        match game.apply_move(action) {
-            Ok(events) => {
-                display::show_events(&events);
+    while !session.save.game.game_over {
+        let game = &mut session.save.game;
+        game.start_turn();
+        let action = match menu::prompt_turn(&game.board, &game.current_player().rack) {
+            TurnInput::Play(action) => action,
+            TurnInput::ShowUnseen => {
+                display::show_unseen(&game.unseen_tiles(game.current_player_index));
+                continue;
+            }
+            TurnInput::ShowBoard => {
+                display::show_game(game);
+                continue;
             }
-            Err(e) => {
-                display::show_move_error(&e);
+            TurnInput::ShowHooks(word) => {
+                match (&game.lexicon, word) {
+                    (None, _) => println!("{}", t!("no-dictionary")),
+                    (Some(lexicon), Some(word)) => {
+                        words::run_hooks(&word, words::HOOKS_SHOWN, lexicon, game.tile_set)
+                    }
+                    (Some(lexicon), None) => words::show_board_hooks(
+                        &game.board,
+                        &hooks::board_hooks(&game.board, lexicon, game.tile_set),
+                    ),
+                }
                 continue;
             }
+            TurnInput::Define(word) => {
+                match define(&word, definitions) {
+                    Ok(text) | Err(text) => println!("{}", text),
+                }
+                continue;
+            }
+            TurnInput::Chat(ChatCommand::Show) => {
+                let name = &game.current_player().name;
+                chat::show_pane(&game.chat.recent(Some(name), CHAT_BACKLOG));
+                continue;
+            }
+            TurnInput::Chat(command) => {
+                match run_chat_command(game, command) {
+                    Ok(Some(text)) => println!("{}", text),
+                    Ok(None) => {
+                        if let Some(message) = game.chat.messages.last() {
+                            chat::show_message(message);
+                        }
+                    }
+                    Err(e) => chat::show_error(&e),
+                }
+                session.store()?;
+                continue;
+            }
+        };
+
+        if game.check_timeout() {
+            println!("{}", t!("time-up", name = &game.current_player().name));
+        } else {
+            let events = match game.apply_move(action) {
+                Ok(events) => events,
+                Err(e) => {
+                    display::show_move_error(&e);
+                    continue;
+                }
+            };
+            println!("{}", t!("move-accepted"));
+            println!("{}", describe_move(game, &events));
+            for line in move_definitions(&events, definitions) {
+                println!("{}", line);
+            }
+            if events.iter().any(GameEvent::keeps_turn) {
+                session.store()?;
+                continue;
+            }
+
+            println!("{}", t!("player-draws", name = &game.current_player().name));
+            game.end_turn();
         }
 
-        display::show_game(&game);
+        session.store()?;
+        display::show_game(&session.save.game);
+        if !session.save.game.game_over {
+            show_chat_pane(&session.save.game);
+        }
+    }
+
+    Ok(())
+}
+
+// The full-screen interface needs a real terminal; piped input and output
+// get the line-based one unless told otherwise. So does the linear mode,
+// which is meant for screen readers.
+pub fn run(session: &mut Session, settings: &Settings) -> io::Result<()> {
+    let already_over = session.save.game.game_over;
+    if !already_over {
+        let full = match settings.interface {
+            Interface::Auto => {
+                !settings.appearance.linear
+                    && io::stdin().is_terminal()
+                    && io::stdout().is_terminal()
+            }
+            Interface::Full => true,
+            Interface::Lines => false,
+        };
+        if full {
+            run_tui(session, settings.definitions.as_deref())?;
+        } else {
+            run_lines(session, settings.definitions.as_deref())?;
+        }
+    }
 
-        println!("{} pioche !", game.players[0].name);
-        game.players[0].rack.top_up(&mut game.bag);
-        println!("{}", game.players[0].rack);
+    // Leaving an unfinished game keeps it for later.
+    if !session.save.game.game_over {
+        return Ok(());
+    }
 
-        // later:
-        // save::io::save_to_path(&session.path, &session.save)?;
+    if !already_over {
+        let mut profiles = ProfileStore::load(&settings.data_dir)?;
+        profiles.record_game(&session.save.game, settings.rating_mode, now_unix());
+        profiles.save(&settings.data_dir)?;
     }
+
+    display::show_game(&session.save.game);
+    println!("{}", t!("game-over"));
+    display::show_scores(&session.save.game);
+
+    Ok(())
 }
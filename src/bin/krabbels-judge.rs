@@ -0,0 +1,71 @@
+use krabbels::config::{Options, Settings};
+use krabbels::game::judge::{self, JudgeLog, Verdict};
+use krabbels::i18n::Language;
+use krabbels::t;
+use krabbels::ui::judge as screen;
+
+use clap::Parser;
+use std::{io, path::PathBuf, process};
+
+// Meant for a second terminal next to the board: same config, same word
+// list as the game.
+#[derive(Parser)]
+#[command(
+    name = "krabbels-judge",
+    version,
+    about = "Krabbels word judge: says whether a play is acceptable"
+)]
+struct Cli {
+    /// Words of one play; without them the judge screen opens
+    words: Vec<String>,
+    /// Word list [default: the dictionary of the config file]
+    #[arg(long)]
+    dictionary: Option<PathBuf>,
+    /// Config file [default: <config dir>/krabbels/config.toml]
+    #[arg(long, env = "KRABBELS_CONFIG")]
+    config: Option<PathBuf>,
+    /// Where the adjudication log is kept [default: from the config, or ./data]
+    #[arg(long, env = "KRABBELS_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+    /// Language of the messages: fr or en
+    #[arg(long, env = "KRABBELS_LANG", value_parser = |tag: &str| Language::parse(tag).ok_or("fr or en"))]
+    lang: Option<Language>,
+    /// Don't log the adjudications
+    #[arg(long)]
+    no_log: bool,
+}
+
+fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let options = Options {
+        config: cli.config,
+        data_dir: cli.data_dir,
+        lang: cli.lang,
+        dictionary: cli.dictionary,
+        ..Options::default()
+    };
+    let settings = Settings::load(&options).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+    settings.apply();
+
+    let Some(lexicon) = settings.game.lexicon.as_deref() else {
+        eprintln!("{}", t!("no-dictionary"));
+        process::exit(2);
+    };
+    let log = (!cli.no_log).then(|| JudgeLog::new(&settings.data_dir));
+
+    if cli.words.is_empty() {
+        return screen::run(lexicon, log.as_ref());
+    }
+    let Some(words) = judge::parse_words(&cli.words.join(" ")) else {
+        eprintln!("{}", t!("judge-invalid"));
+        process::exit(2);
+    };
+    // The exit status tells scripts the verdict too.
+    match screen::adjudicate(words, lexicon, log.as_ref()) {
+        Verdict::Acceptable => Ok(()),
+        Verdict::NotAcceptable => process::exit(1),
+    }
+}
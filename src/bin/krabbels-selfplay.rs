@@ -0,0 +1,42 @@
+use krabbels::config::{Options, Settings};
+use krabbels::t;
+use krabbels::ui::selfplay::{self, SelfplayArgs};
+
+use clap::Parser;
+use std::{io, process};
+
+// Headless: every option of the game (rules, tiles, word list...) applies
+// to the bots' games too.
+#[derive(Parser)]
+#[command(
+    name = "krabbels-selfplay",
+    version,
+    about = "Krabbels bots against each other, for strength testing"
+)]
+struct Cli {
+    #[command(flatten)]
+    options: Options,
+    #[command(flatten)]
+    args: SelfplayArgs,
+}
+
+fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let settings = Settings::load(&cli.options).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+    settings.apply();
+
+    let Some(lexicon) = settings.game.lexicon.as_deref() else {
+        eprintln!("{}", t!("no-dictionary"));
+        process::exit(2);
+    };
+    selfplay::run(
+        &cli.args,
+        lexicon,
+        &settings.game,
+        settings.rating_mode,
+        &settings.data_dir,
+    )
+}
@@ -0,0 +1,51 @@
+use krabbels::config::{Options, Settings};
+use krabbels::net::protocol::DEFAULT_PORT;
+use krabbels::net::server::{self, ServerConfig};
+use krabbels::t;
+
+use clap::Parser;
+use std::{io, net::TcpListener, path::PathBuf, process};
+
+// Hosted games are set up from the same options as local ones: tiles,
+// board, rules, word list and clock.
+#[derive(Parser)]
+#[command(
+    name = "krabbels-server",
+    version,
+    about = "Krabbels multiplayer server"
+)]
+struct Cli {
+    #[arg(default_value_t = format!("0.0.0.0:{}", DEFAULT_PORT))]
+    addr: String,
+    /// Where player profiles are kept [default: from the config, or ./data]
+    #[arg(value_name = "DATA_DIR")]
+    profiles_dir: Option<PathBuf>,
+    #[command(flatten)]
+    options: Options,
+}
+
+fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let options = Options {
+        data_dir: cli.profiles_dir.or(cli.options.data_dir),
+        ..cli.options
+    };
+    let settings = Settings::load(&options).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+    settings.apply();
+
+    let listener = TcpListener::bind(&cli.addr)?;
+    let addr = listener.local_addr()?.to_string();
+    println!("{}", t!("server-listening", addr = addr));
+    println!(
+        "{}",
+        t!(
+            "server-profiles",
+            path = settings.data_dir.display().to_string()
+        )
+    );
+
+    server::serve(listener, ServerConfig::from_settings(&settings))
+}
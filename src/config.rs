@@ -0,0 +1,561 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ConfigError;
+use crate::game::GameOptions;
+use crate::game::bag::TileSet;
+use crate::game::board::{BOARD_SIZE, Board, Position};
+use crate::game::clock::TimeControl;
+use crate::game::definitions::Definitions;
+use crate::game::lexicon::Lexicon;
+use crate::game::rules::{ChallengeRule, EndRackScoring, RulePreset, RuleSet};
+use crate::i18n::{self, Language};
+use crate::profile::rating::RatingMode;
+use crate::save::DEFAULT_DATA_DIR;
+use crate::ui::theme::{self, Appearance, Theme};
+
+const APP_DIR: &str = "krabbels";
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum Interface {
+    // Full screen in a terminal, lines when piped.
+    #[default]
+    Auto,
+    Full,
+    Lines,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClockConfig {
+    // "25+5"; no clock when unset.
+    pub time_control: Option<String>,
+    pub forfeit_on_timeout: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayConfig {
+    pub color: bool,
+    pub interface: Interface,
+    pub theme: Theme,
+    pub ascii: bool,
+    pub linear: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            color: true,
+            interface: Interface::Auto,
+            theme: Theme::Classic,
+            ascii: false,
+            linear: false,
+        }
+    }
+}
+
+// The [rules] table: a preset, then single rules on top of it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RulesConfig {
+    pub preset: Option<RulePreset>,
+    pub rack_size: Option<usize>,
+    pub bingo_bonus: Option<u32>,
+    // "h8"
+    pub first_move: Option<String>,
+    pub min_word_length: Option<usize>,
+    pub challenge: Option<ChallengeRule>,
+    pub challenge_penalty: Option<u32>,
+    pub exchange_threshold: Option<usize>,
+    pub scoreless_turn_limit: Option<u32>,
+    pub end_rack: Option<EndRackScoring>,
+    pub overtime_penalty: Option<u32>,
+}
+
+impl RulesConfig {
+    fn apply(&self, rules: &mut RuleSet) -> Result<(), ConfigError> {
+        if let Some(input) = &self.first_move {
+            rules.first_move = Position::parse(input).ok_or_else(|| ConfigError::Rule {
+                key: "first_move",
+                value: input.clone(),
+            })?;
+        }
+        rules.rack_size = self.rack_size.unwrap_or(rules.rack_size);
+        rules.bingo_bonus = self.bingo_bonus.unwrap_or(rules.bingo_bonus);
+        rules.min_word_length = self.min_word_length.unwrap_or(rules.min_word_length);
+        rules.challenge = self.challenge.unwrap_or(rules.challenge);
+        rules.challenge_penalty = self.challenge_penalty.unwrap_or(rules.challenge_penalty);
+        rules.exchange_threshold = self.exchange_threshold.unwrap_or(rules.exchange_threshold);
+        rules.scoreless_turn_limit = self
+            .scoreless_turn_limit
+            .unwrap_or(rules.scoreless_turn_limit);
+        rules.end_rack = self.end_rack.unwrap_or(rules.end_rack);
+        rules.overtime_penalty = self.overtime_penalty.unwrap_or(rules.overtime_penalty);
+        Ok(())
+    }
+}
+
+// The config file as written by the user. Every key is optional; relative
+// paths are taken from the file's own directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub data_dir: Option<PathBuf>,
+    // Offered when a new game is created.
+    pub players: Vec<String>,
+    pub language: Option<String>,
+    pub tiles: TileSet,
+    // A text file as read by `Board::from_layout`.
+    pub board_layout: Option<PathBuf>,
+    // A word list as read by `Lexicon::load`.
+    pub dictionary: Option<PathBuf>,
+    // Tab-separated word and meaning, as read by `Definitions::load`.
+    pub definitions: Option<PathBuf>,
+    pub clock: ClockConfig,
+    pub rules: RulesConfig,
+    pub rating_mode: RatingMode,
+    pub display: DisplayConfig,
+}
+
+impl Config {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(APP_DIR).join(CONFIG_FILE))
+    }
+
+    // A missing file is not an error: everything has a default.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(ConfigError::Read {
+                    path: path.to_path_buf(),
+                    message: e.to_string(),
+                });
+            }
+        };
+        let mut config: Self = toml::from_str(&text).map_err(|e| ConfigError::Parse {
+            path: path.to_path_buf(),
+            message: e.message().to_string(),
+        })?;
+
+        if let Some(base) = path.parent() {
+            for path in [
+                &mut config.data_dir,
+                &mut config.board_layout,
+                &mut config.dictionary,
+                &mut config.definitions,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *path = base.join(&*path);
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn parse_language(tag: &str) -> Result<Language, String> {
+    Language::parse(tag).ok_or_else(|| ConfigError::Language(tag.to_string()).to_string())
+}
+
+fn parse_tile_set(name: &str) -> Result<TileSet, String> {
+    TileSet::parse(name).ok_or_else(|| ConfigError::TileSet(name.to_string()).to_string())
+}
+
+fn parse_time_control(input: &str) -> Result<TimeControl, String> {
+    TimeControl::parse(input).ok_or_else(|| ConfigError::TimeControl(input.to_string()).to_string())
+}
+
+// Command-line flags; each one overrides the matching config key.
+#[derive(Debug, Default, Args)]
+pub struct Options {
+    /// Config file [default: <config dir>/krabbels/config.toml]
+    #[arg(long, env = "KRABBELS_CONFIG", global = true)]
+    pub config: Option<PathBuf>,
+    /// Where saves and player profiles are kept [default: ./data]
+    #[arg(long, env = "KRABBELS_DATA_DIR", global = true)]
+    pub data_dir: Option<PathBuf>,
+    /// Interface language: fr or en [default: from the system locale]
+    #[arg(long, env = "KRABBELS_LANG", value_parser = parse_language, global = true)]
+    pub lang: Option<Language>,
+    /// Players offered for a new game, comma separated
+    #[arg(long, value_delimiter = ',')]
+    pub players: Vec<String>,
+    /// Tile set for new games and word values: french or english
+    #[arg(long, value_parser = parse_tile_set, global = true)]
+    pub tiles: Option<TileSet>,
+    /// Premium square layout for new games (15 lines of . d t D T)
+    #[arg(long)]
+    pub board_layout: Option<PathBuf>,
+    /// Word list; words not in it are refused
+    #[arg(long, global = true)]
+    pub dictionary: Option<PathBuf>,
+    /// Word meanings, one "WORD<tab>definition" per line
+    #[arg(long, global = true)]
+    pub definitions: Option<PathBuf>,
+    /// Clock for new games, minutes[+increment seconds], e.g. 25+5
+    #[arg(long, value_parser = parse_time_control)]
+    pub clock: Option<TimeControl>,
+    /// Lose on time instead of paying the overtime penalty
+    #[arg(long, overrides_with = "no_forfeit_on_timeout")]
+    pub forfeit_on_timeout: bool,
+    /// Pay the overtime penalty instead of losing on time
+    #[arg(long, overrides_with = "forfeit_on_timeout")]
+    pub no_forfeit_on_timeout: bool,
+    /// Rules for new games: tournament, casual or family [default: classic]
+    #[arg(long, value_enum)]
+    pub rules: Option<RulePreset>,
+    /// Tiles on a full rack
+    #[arg(long)]
+    pub rack_size: Option<usize>,
+    /// How words not in the dictionary are dealt with
+    #[arg(long, value_enum)]
+    pub challenge: Option<ChallengeRule>,
+    /// Bonus for playing the whole rack
+    #[arg(long)]
+    pub bingo_bonus: Option<u32>,
+    /// Points lost per started minute of overtime
+    #[arg(long)]
+    pub overtime_penalty: Option<u32>,
+    /// What moves ratings: the winner only, or the spread too
+    #[arg(long, value_enum, global = true)]
+    pub rating_mode: Option<RatingMode>,
+    /// Turn colors on
+    #[arg(long, overrides_with = "no_color")]
+    pub color: bool,
+    /// Turn colors off
+    #[arg(long, overrides_with = "color")]
+    pub no_color: bool,
+    /// auto, full or lines
+    #[arg(long, value_enum)]
+    pub interface: Option<Interface>,
+    /// Colors of the board and the clocks
+    #[arg(long, value_enum)]
+    pub theme: Option<Theme>,
+    /// Draw the board and rack with plain ASCII characters
+    #[arg(long, overrides_with = "no_ascii")]
+    pub ascii: bool,
+    /// Draw the board and rack with box and accented characters
+    #[arg(long, overrides_with = "ascii")]
+    pub no_ascii: bool,
+    /// Describe the board and moves in sentences, for screen readers
+    #[arg(long, overrides_with = "no_linear")]
+    pub linear: bool,
+    /// Draw the board instead of describing it
+    #[arg(long, overrides_with = "linear")]
+    pub no_linear: bool,
+}
+
+// An on/off switch given as `--x` or `--no-x`; None when neither is, so
+// the config file decides. The last one given wins.
+fn switch(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+impl Options {
+    pub fn forfeit_on_timeout(&self) -> Option<bool> {
+        switch(self.forfeit_on_timeout, self.no_forfeit_on_timeout)
+    }
+
+    pub fn color(&self) -> Option<bool> {
+        switch(self.color, self.no_color)
+    }
+
+    pub fn ascii(&self) -> Option<bool> {
+        switch(self.ascii, self.no_ascii)
+    }
+
+    pub fn linear(&self) -> Option<bool> {
+        switch(self.linear, self.no_linear)
+    }
+}
+
+// Everything resolved: flags over the config file over the defaults.
+pub struct Settings {
+    pub data_dir: PathBuf,
+    pub language: Language,
+    pub players: Vec<String>,
+    pub time_control: Option<TimeControl>,
+    pub game: GameOptions,
+    pub rating_mode: RatingMode,
+    pub definitions: Option<Arc<Definitions>>,
+    pub color: bool,
+    pub interface: Interface,
+    pub appearance: Appearance,
+}
+
+impl Settings {
+    pub fn load(options: &Options) -> Result<Self, ConfigError> {
+        let config = match options.config.clone().or_else(Config::default_path) {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+        Self::resolve(config, options)
+    }
+
+    pub fn resolve(config: Config, options: &Options) -> Result<Self, ConfigError> {
+        let language = match (options.lang, &config.language) {
+            (Some(language), _) => language,
+            (None, Some(tag)) => {
+                Language::parse(tag).ok_or_else(|| ConfigError::Language(tag.clone()))?
+            }
+            (None, None) => Language::from_env(),
+        };
+
+        let time_control = match (options.clock, &config.clock.time_control) {
+            (Some(control), _) => Some(control),
+            (None, Some(input)) => Some(
+                TimeControl::parse(input).ok_or_else(|| ConfigError::TimeControl(input.clone()))?,
+            ),
+            (None, None) => None,
+        }
+        .map(|mut control| {
+            control.forfeit_on_timeout = options
+                .forfeit_on_timeout()
+                .unwrap_or(config.clock.forfeit_on_timeout);
+            control
+        });
+
+        let board = match options
+            .board_layout
+            .as_ref()
+            .or(config.board_layout.as_ref())
+        {
+            Some(path) => load_layout(path)?,
+            None => Board::new(),
+        };
+        let lexicon = match options.dictionary.as_ref().or(config.dictionary.as_ref()) {
+            Some(path) => Some(Arc::new(Lexicon::load(path).map_err(|e| {
+                ConfigError::Dictionary {
+                    path: path.clone(),
+                    message: e.to_string(),
+                }
+            })?)),
+            None => None,
+        };
+        let definitions = match options.definitions.as_ref().or(config.definitions.as_ref()) {
+            Some(path) => Some(Arc::new(Definitions::load(path).map_err(|e| {
+                ConfigError::Definitions {
+                    path: path.clone(),
+                    message: e.to_string(),
+                }
+            })?)),
+            None => None,
+        };
+
+        // The preset first, then the rules set one by one: file, then flags.
+        let mut rules = options
+            .rules
+            .or(config.rules.preset)
+            .map_or_else(RuleSet::default, RuleSet::preset);
+        config.rules.apply(&mut rules)?;
+        rules.rack_size = options.rack_size.unwrap_or(rules.rack_size);
+        rules.challenge = options.challenge.unwrap_or(rules.challenge);
+        rules.bingo_bonus = options.bingo_bonus.unwrap_or(rules.bingo_bonus);
+        rules.overtime_penalty = options.overtime_penalty.unwrap_or(rules.overtime_penalty);
+        if !(1..=BOARD_SIZE).contains(&rules.rack_size) {
+            return Err(ConfigError::Rule {
+                key: "rack_size",
+                value: rules.rack_size.to_string(),
+            });
+        }
+        if !(1..=BOARD_SIZE).contains(&rules.min_word_length) {
+            return Err(ConfigError::Rule {
+                key: "min_word_length",
+                value: rules.min_word_length.to_string(),
+            });
+        }
+
+        let color = options.color().unwrap_or(config.display.color);
+        let appearance = Appearance {
+            // Without colors only the square labels are left.
+            theme: if color {
+                options.theme.unwrap_or(config.display.theme)
+            } else {
+                Theme::Mono
+            },
+            ascii: options.ascii().unwrap_or(config.display.ascii),
+            linear: options.linear().unwrap_or(config.display.linear),
+        };
+
+        Ok(Self {
+            data_dir: options
+                .data_dir
+                .clone()
+                .or(config.data_dir)
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_DIR)),
+            language,
+            players: if options.players.is_empty() {
+                config.players
+            } else {
+                options.players.clone()
+            },
+            time_control,
+            game: GameOptions {
+                tile_set: options.tiles.unwrap_or(config.tiles),
+                board,
+                rules,
+                lexicon,
+                seed: None,
+            },
+            rating_mode: options.rating_mode.unwrap_or(config.rating_mode),
+            definitions,
+            color,
+            interface: options.interface.unwrap_or(config.display.interface),
+            appearance,
+        })
+    }
+
+    // Process-wide switches: the message language, terminal colors and the
+    // way the board is drawn.
+    pub fn apply(&self) {
+        i18n::set_language(self.language);
+        theme::set_appearance(self.appearance);
+        if !self.color {
+            colored::control::set_override(false);
+        }
+    }
+}
+
+fn load_layout(path: &Path) -> Result<Board, ConfigError> {
+    let text = fs::read_to_string(path).map_err(|e| ConfigError::Read {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    Board::from_layout(&text).ok_or_else(|| ConfigError::BoardLayout {
+        path: path.to_path_buf(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        options: Options,
+    }
+
+    fn options(args: &[&str]) -> Options {
+        Cli::parse_from(["krabbels"].iter().chain(args)).options
+    }
+
+    // A file turning on every switch that is off by default, and off the
+    // one that is on.
+    fn config() -> Config {
+        toml::from_str(
+            r#"
+            rating_mode = "spread"
+
+            [clock]
+            time_control = "25+5"
+            forfeit_on_timeout = true
+
+            [rules]
+            preset = "casual"
+            rack_size = 8
+
+            [display]
+            color = false
+            ascii = true
+            linear = true
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn resolve(config: Config, args: &[&str]) -> Settings {
+        Settings::resolve(config, &options(args)).unwrap()
+    }
+
+    #[test]
+    fn the_file_applies_without_flags() {
+        let settings = resolve(config(), &[]);
+        let clock = settings.time_control.unwrap();
+        assert_eq!((clock.initial_secs, clock.increment_secs), (1500, 5));
+        assert!(clock.forfeit_on_timeout);
+        assert!(!settings.color);
+        assert!(settings.appearance.ascii);
+        assert!(settings.appearance.linear);
+        assert_eq!(settings.appearance.theme, Theme::Mono);
+        assert_eq!(settings.rating_mode, RatingMode::Spread);
+        assert_eq!(settings.game.rules.rack_size, 8);
+    }
+
+    #[test]
+    fn flags_override_the_file() {
+        let settings = resolve(
+            config(),
+            &[
+                "--no-forfeit-on-timeout",
+                "--color",
+                "--no-ascii",
+                "--no-linear",
+                "--clock",
+                "10",
+                "--rack-size",
+                "7",
+                "--rating-mode",
+                "win-loss",
+            ],
+        );
+        let clock = settings.time_control.unwrap();
+        assert_eq!((clock.initial_secs, clock.increment_secs), (600, 0));
+        assert!(!clock.forfeit_on_timeout);
+        assert!(settings.color);
+        assert!(!settings.appearance.ascii);
+        assert!(!settings.appearance.linear);
+        assert_eq!(settings.appearance.theme, Theme::Classic);
+        assert_eq!(settings.rating_mode, RatingMode::WinLoss);
+        assert_eq!(settings.game.rules.rack_size, 7);
+    }
+
+    #[test]
+    fn defaults_without_file_or_flags() {
+        let settings = resolve(Config::default(), &[]);
+        assert!(settings.time_control.is_none());
+        assert!(settings.color);
+        assert!(!settings.appearance.ascii);
+        assert!(!settings.appearance.linear);
+        assert_eq!(settings.rating_mode, RatingMode::WinLoss);
+        assert_eq!(settings.game.rules, RuleSet::default());
+
+        let settings = resolve(Config::default(), &["--clock", "5", "--forfeit-on-timeout"]);
+        assert!(settings.time_control.unwrap().forfeit_on_timeout);
+    }
+
+    #[test]
+    fn the_last_of_a_flag_pair_wins() {
+        assert_eq!(options(&[]).ascii(), None);
+        assert_eq!(options(&["--ascii"]).ascii(), Some(true));
+        assert_eq!(options(&["--ascii", "--no-ascii"]).ascii(), Some(false));
+        assert_eq!(options(&["--no-color", "--color"]).color(), Some(true));
+        assert_eq!(
+            options(&["--linear", "--no-linear", "--linear"]).linear(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_refused() {
+        assert!(toml::from_str::<Config>("[display]\nascci = true\n").is_err());
+        assert!(toml::from_str::<Config>("[rules]\nrating_mode = \"spread\"\n").is_err());
+    }
+}
@@ -1,3 +1,9 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::game::board::Position;
+use crate::t;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputError {
     InvalidFormat,
@@ -9,64 +15,349 @@ pub enum InputError {
 impl std::fmt::Display for InputError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            InputError::InvalidFormat => {
-                write!(f, "Format invalide")
-            }
-            InputError::EmptyInput => {
-                write!(f, "Entrée vide")
-            }
-            InputError::InvalidPosition => {
-                write!(f, "Position invalide (ex: h8)")
-            }
-            InputError::InvalidDirection => {
-                write!(f, "Direction invalide (h pour horizontal, v pour vertical)")
-            }
+            InputError::InvalidFormat => f.write_str(&t!("input-invalid-format")),
+            InputError::EmptyInput => f.write_str(&t!("input-empty")),
+            InputError::InvalidPosition => f.write_str(&t!("input-invalid-position")),
+            InputError::InvalidDirection => f.write_str(&t!("input-invalid-direction")),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+// "h8", as players type it.
+pub(crate) fn square_name(row: usize, col: usize) -> String {
+    Position { row, col }.to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MoveError {
     OutOfBounds { row: usize, col: usize },
     CellOccupied { row: usize, col: usize },
     MissingLetter { letter: char },
     LetterMismatch { row: usize, col: usize },
-    WordTooShort,
+    WordTooShort { min: usize },
+    NothingPlaced,
+    NotInLine,
     NoConnection,
-    MustCoverCenter,
+    MustCoverStart { row: usize, col: usize },
     InvalidWord,
+    NothingExchanged,
+    ExchangeNotAllowed { min_in_bag: usize },
+    ChallengeNotAllowed,
+    NothingToChallenge,
+    NoDictionary,
+    Integrity(IntegrityError),
 }
 
 impl std::fmt::Display for MoveError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MoveError::OutOfBounds { row, col } => {
-                let col_letter = (b'a' + *col as u8) as char;
-                write!(f, "Hors limites à la position {}{}", col_letter, row + 1)
+                f.write_str(&t!("move-out-of-bounds", pos = square_name(*row, *col)))
             }
             MoveError::CellOccupied { row, col } => {
-                let col_letter = (b'a' + *col as u8) as char;
-                write!(f, "Case occupée à la position {}{}", col_letter, row + 1)
+                f.write_str(&t!("move-cell-occupied", pos = square_name(*row, *col)))
             }
             MoveError::MissingLetter { letter } => {
-                write!(f, "Vous n'avez pas la lettre '{}'", letter)
+                f.write_str(&t!("move-missing-letter", letter = letter.to_string()))
             }
             MoveError::LetterMismatch { row, col } => {
-                let col_letter = (b'a' + *col as u8) as char;
-                write!(f, "Votre mot ne s'insère pas à {}{}", col_letter, row + 1)
+                f.write_str(&t!("move-letter-mismatch", pos = square_name(*row, *col)))
+            }
+            MoveError::WordTooShort { min } => f.write_str(&t!("move-word-too-short", min = *min)),
+            MoveError::NothingPlaced => f.write_str(&t!("move-nothing-placed")),
+            MoveError::NotInLine => f.write_str(&t!("move-not-in-line")),
+            MoveError::NoConnection => f.write_str(&t!("move-no-connection")),
+            MoveError::MustCoverStart { row, col } => {
+                f.write_str(&t!("move-must-cover-start", pos = square_name(*row, *col)))
+            }
+            MoveError::InvalidWord => f.write_str(&t!("move-invalid-word")),
+            MoveError::NothingExchanged => f.write_str(&t!("move-nothing-exchanged")),
+            MoveError::ExchangeNotAllowed { min_in_bag } => {
+                f.write_str(&t!("move-exchange-not-allowed", count = *min_in_bag))
+            }
+            MoveError::ChallengeNotAllowed => f.write_str(&t!("move-challenge-not-allowed")),
+            MoveError::NothingToChallenge => f.write_str(&t!("move-nothing-to-challenge")),
+            MoveError::NoDictionary => f.write_str(&t!("move-no-dictionary")),
+            MoveError::Integrity(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryError {
+    InvalidPattern(String),
+    InvalidLetters(String),
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::InvalidPattern(input) => {
+                f.write_str(&t!("query-invalid-pattern", value = input))
+            }
+            QueryError::InvalidLetters(input) => {
+                f.write_str(&t!("query-invalid-letters", value = input))
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatError {
+    Empty,
+    TooLong { max: usize },
+    RateLimited { retry_in_secs: i64 },
+    UnknownPlayer { name: String },
+}
+
+impl std::fmt::Display for ChatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChatError::Empty => f.write_str(&t!("chat-error-empty")),
+            ChatError::TooLong { max } => f.write_str(&t!("chat-error-too-long", max = *max)),
+            ChatError::RateLimited { retry_in_secs } => {
+                f.write_str(&t!("chat-error-rate-limited", secs = *retry_in_secs))
+            }
+            ChatError::UnknownPlayer { name } => {
+                f.write_str(&t!("chat-error-unknown-player", name = name))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveError {
+    MalformedHeader,
+    ChecksumMismatch { expected: u64, found: u64 },
+    UnsupportedVersion { version: u32 },
+    Parse(String),
+    Integrity(IntegrityError),
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::MalformedHeader => f.write_str(&t!("save-error-malformed-header")),
+            SaveError::ChecksumMismatch { expected, found } => f.write_str(&t!(
+                "save-error-checksum",
+                found = format!("{:016x}", found),
+                expected = format!("{:016x}", expected)
+            )),
+            SaveError::UnsupportedVersion { version } => {
+                f.write_str(&t!("save-error-unsupported-version", version = *version))
             }
-            MoveError::WordTooShort => {
-                write!(f, "Le mot doit contenir au moins 2 lettres")
+            SaveError::Parse(msg) => f.write_str(&t!("save-error-parse", message = msg)),
+            SaveError::Integrity(e) => {
+                f.write_str(&t!("save-error-integrity", message = e.to_string()))
             }
-            MoveError::NoConnection => {
-                write!(f, "Le mot doit être connecté aux mots existants")
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+// Something about a game that no sequence of moves can lead to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityError {
+    TileCount {
+        letter: char,
+        expected: usize,
+        found: usize,
+    },
+    StartNotCovered,
+    Detached {
+        pos: Position,
+    },
+    MoveScore {
+        turn: u32,
+        expected: u32,
+        found: u32,
+    },
+    Score {
+        name: String,
+        expected: i64,
+        found: u32,
+    },
+}
+
+impl std::fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityError::TileCount {
+                letter,
+                expected,
+                found,
+            } => f.write_str(&t!(
+                "integrity-tile-count",
+                letter = letter.to_string(),
+                expected = *expected,
+                found = *found
+            )),
+            IntegrityError::StartNotCovered => f.write_str(&t!("integrity-start")),
+            IntegrityError::Detached { pos } => {
+                f.write_str(&t!("integrity-detached", pos = pos.to_string()))
             }
-            MoveError::MustCoverCenter => {
-                write!(f, "Le premier mot doit être placé sur h8")
+            IntegrityError::MoveScore {
+                turn,
+                expected,
+                found,
+            } => f.write_str(&t!(
+                "integrity-move-score",
+                turn = *turn,
+                expected = *expected,
+                found = *found
+            )),
+            IntegrityError::Score {
+                name,
+                expected,
+                found,
+            } => f.write_str(&t!(
+                "integrity-score",
+                name = name,
+                expected = *expected,
+                found = *found
+            )),
+        }
+    }
+}
+
+impl std::error::Error for IntegrityError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnalysisError {
+    Unfinished,
+    // Saved before racks were kept in the history.
+    NotRecorded { turn: u32 },
+}
+
+impl std::fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalysisError::Unfinished => f.write_str(&t!("analysis-error-unfinished")),
+            AnalysisError::NotRecorded { turn } => {
+                f.write_str(&t!("analysis-error-not-recorded", turn = *turn))
             }
-            MoveError::InvalidWord => {
-                write!(f, "Mot invalide (pas dans le dictionnaire)")
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    Read { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    Language(String),
+    TileSet(String),
+    TimeControl(String),
+    BoardLayout { path: PathBuf },
+    Dictionary { path: PathBuf, message: String },
+    Definitions { path: PathBuf, message: String },
+    Rule { key: &'static str, value: String },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Read { path, message } => f.write_str(&t!(
+                "config-error-read",
+                path = path.display().to_string(),
+                message = message
+            )),
+            ConfigError::Parse { path, message } => f.write_str(&t!(
+                "config-error-parse",
+                path = path.display().to_string(),
+                message = message
+            )),
+            ConfigError::Language(tag) => f.write_str(&t!("config-error-language", value = tag)),
+            ConfigError::TileSet(name) => f.write_str(&t!("config-error-tile-set", value = name)),
+            ConfigError::TimeControl(input) => {
+                f.write_str(&t!("config-error-time-control", value = input))
+            }
+            ConfigError::BoardLayout { path } => f.write_str(&t!(
+                "config-error-board-layout",
+                path = path.display().to_string()
+            )),
+            ConfigError::Rule { key, value } => {
+                f.write_str(&t!("config-error-rule", key = *key, value = value))
             }
+            ConfigError::Dictionary { path, message } => f.write_str(&t!(
+                "config-error-dictionary",
+                path = path.display().to_string(),
+                message = message
+            )),
+            ConfigError::Definitions { path, message } => f.write_str(&t!(
+                "config-error-definitions",
+                path = path.display().to_string(),
+                message = message
+            )),
         }
     }
 }
+
+impl std::error::Error for ConfigError {}
+
+// What the server refuses. Sent as a code, so each client words it in its
+// own language.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "code", content = "detail", rename_all = "snake_case")]
+pub enum ServerError {
+    UnsupportedVersion { version: u32, server: u32 },
+    HelloFirst,
+    EmptyName,
+    NameTaken,
+    BadMessage { message: String },
+    SeatCount { max: usize },
+    NoGame { game_id: u32 },
+    GameFull,
+    AlreadyPlaying,
+    NotFinished,
+    NotAPlayer,
+    MuteSelf,
+    Chat(ChatError),
+    WaitingForPlayers,
+    GameOver,
+    NotYourTurn,
+    Move(MoveError),
+}
+
+impl std::fmt::Display for ServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerError::UnsupportedVersion { version, server } => f.write_str(&t!(
+                "server-error-version",
+                version = *version,
+                server = *server
+            )),
+            ServerError::HelloFirst => f.write_str(&t!("server-error-hello-first")),
+            ServerError::EmptyName => f.write_str(&t!("server-error-empty-name")),
+            ServerError::NameTaken => f.write_str(&t!("server-error-name-taken")),
+            ServerError::BadMessage { message } => {
+                f.write_str(&t!("server-error-bad-message", message = message))
+            }
+            ServerError::SeatCount { max } => {
+                f.write_str(&t!("server-error-seat-count", max = *max))
+            }
+            ServerError::NoGame { game_id } => {
+                f.write_str(&t!("server-error-no-game", game = *game_id))
+            }
+            ServerError::GameFull => f.write_str(&t!("server-error-game-full")),
+            ServerError::AlreadyPlaying => f.write_str(&t!("server-error-already-playing")),
+            ServerError::NotFinished => f.write_str(&t!("server-error-not-finished")),
+            ServerError::NotAPlayer => f.write_str(&t!("server-error-not-a-player")),
+            ServerError::MuteSelf => f.write_str(&t!("chat-error-mute-self")),
+            ServerError::Chat(e) => write!(f, "{e}"),
+            ServerError::WaitingForPlayers => f.write_str(&t!("server-error-waiting")),
+            ServerError::GameOver => f.write_str(&t!("server-error-game-over")),
+            ServerError::NotYourTurn => f.write_str(&t!("server-error-not-your-turn")),
+            ServerError::Move(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
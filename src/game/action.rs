@@ -6,7 +6,10 @@ pub enum Action {
         dir: board::Direction,
         word: board::Word,
     },
-    ExchangeTiles,
+    // Letters as on the rack, '_' for a blank.
+    ExchangeTiles {
+        letters: Vec<char>,
+    },
     Pass,
     Challenge,
     Resign,
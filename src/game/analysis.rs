@@ -0,0 +1,540 @@
+use serde::Serialize;
+
+use super::bag::TileSet;
+use super::board::{Board, Direction, Position};
+use super::lexicon::Lexicon;
+use super::movegen::{self, Candidate, Trie};
+use super::rules::{EndRackScoring, RuleSet};
+use super::unseen::{BLANK, VOWELS};
+use super::{Game, MoveRecord, ScorelessKind, ScorelessTurn};
+use crate::error::AnalysisError;
+
+// Within this much equity of the best, a move counts as the top play.
+pub const TOP_MARGIN: f64 = 1.0;
+
+// What a turn did, or could have done.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Play {
+    Place {
+        pos: Position,
+        dir: Direction,
+        // Blanks in lower case.
+        word: String,
+        bingo: bool,
+    },
+    Exchange {
+        letters: String,
+    },
+    Pass,
+    // Taken back after a challenge.
+    Withdrawn {
+        words: Vec<String>,
+    },
+    // Lost to a failed challenge.
+    ChallengeLost,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Choice {
+    pub play: Play,
+    pub score: u32,
+    // The score plus what the tiles kept are worth.
+    pub equity: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TurnAnalysis {
+    pub turn: u32,
+    pub player_id: usize,
+    // Blanks as '_'.
+    pub rack: String,
+    pub played: Choice,
+    // None when no word could be played at all.
+    pub best_score: Option<Choice>,
+    pub best_equity: Choice,
+    pub points_lost: u32,
+    pub equity_lost: f64,
+    // The best bingo there was, when the move wasn't one.
+    pub missed_bingo: Option<Choice>,
+    // Words of the move that aren't in the dictionary.
+    pub phonies: Vec<String>,
+}
+
+impl TurnAnalysis {
+    pub fn is_top(&self) -> bool {
+        self.equity_lost <= TOP_MARGIN
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PlayerSummary {
+    pub player_id: usize,
+    pub name: String,
+    pub turns: usize,
+    pub points_lost: u32,
+    pub equity_lost: f64,
+    pub top_plays: usize,
+    // Share of turns on the top play, in percent.
+    pub accuracy: f64,
+    pub bingos: usize,
+    pub missed_bingos: usize,
+    pub phonies: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct GameAnalysis {
+    pub turns: Vec<TurnAnalysis>,
+    pub players: Vec<PlayerSummary>,
+}
+
+// Rough worth of the tiles kept, in points: blanks and S make the next
+// move, duplicates, a lopsided vowel count and a Q without a U spoil it.
+// With the bag empty they only count against the player.
+pub fn leave_value(leave: &[char], bag: usize, rules: &RuleSet, tile_set: TileSet) -> f64 {
+    if bag == 0 {
+        let factor = match rules.end_rack {
+            EndRackScoring::Standard => 2.0,
+            EndRackScoring::Deduct => 1.0,
+            EndRackScoring::Ignore => 0.0,
+        };
+        let left: u32 = leave
+            .iter()
+            .map(|&letter| tile_set.letter_value(letter))
+            .sum();
+        return -factor * left as f64;
+    }
+
+    let mut value = 0.0;
+    let mut sorted = leave.to_vec();
+    sorted.sort_unstable();
+    for (i, &letter) in sorted.iter().enumerate() {
+        value += match letter {
+            BLANK => 25.0,
+            'S' => 8.0,
+            'Q' if !leave.contains(&'U') => -12.0,
+            letter => -(tile_set.letter_value(letter) as f64 - 1.0).max(0.0) / 2.0,
+        };
+        if i > 0 && sorted[i - 1] == letter && letter != BLANK {
+            value -= 3.0;
+        }
+    }
+    let vowels = leave.iter().filter(|&&l| VOWELS.contains(l)).count() as f64;
+    let consonants = leave
+        .iter()
+        .filter(|&&l| l != BLANK && !VOWELS.contains(l))
+        .count() as f64;
+    value -= 2.0 * ((vowels - consonants).abs() - 1.0).max(0.0);
+    value
+}
+
+// The rack minus the tiles used, blanks as '_'.
+fn without(rack: &[char], used: impl IntoIterator<Item = char>) -> Vec<char> {
+    let mut left = rack.to_vec();
+    for tile in used {
+        if let Some(i) = left.iter().position(|&l| l == tile) {
+            left.remove(i);
+        }
+    }
+    left
+}
+
+// One turn as the analysis replays it.
+enum Turn<'a> {
+    Move(&'a MoveRecord),
+    Scoreless(&'a ScorelessTurn),
+}
+
+impl Turn<'_> {
+    fn player_id(&self) -> usize {
+        match self {
+            Turn::Move(record) => record.player_id,
+            Turn::Scoreless(turn) => turn.player_id,
+        }
+    }
+
+    fn number(&self) -> u32 {
+        match self {
+            Turn::Move(record) => record.turn,
+            Turn::Scoreless(turn) => turn.turn,
+        }
+    }
+
+    fn rack(&self) -> &[char] {
+        match self {
+            Turn::Move(record) => &record.rack,
+            Turn::Scoreless(turn) => &turn.rack,
+        }
+    }
+}
+
+// Every turn in the order it was played: scoreless turns go before the
+// move they were followed by.
+fn turns(game: &Game) -> Vec<Turn<'_>> {
+    let mut turns = Vec::new();
+    for k in 0..=game.history.len() {
+        turns.extend(
+            game.scoreless_history
+                .iter()
+                .filter(|turn| turn.moves_before == k)
+                .map(Turn::Scoreless),
+        );
+        if let Some(record) = game.history.get(k) {
+            turns.push(Turn::Move(record));
+        }
+    }
+    turns
+}
+
+struct Context<'a> {
+    trie: Trie,
+    lexicon: &'a Lexicon,
+    rules: &'a RuleSet,
+    tile_set: TileSet,
+    players: usize,
+}
+
+impl Context<'_> {
+    // Tiles in the bag as the player saw it: all of them, less the board
+    // and full racks for everyone.
+    fn bag(&self, board: &Board, rack: &[char]) -> usize {
+        let on_board = board.cells.iter().flatten().filter(|c| c.letter.is_some());
+        self.tile_set
+            .tile_count()
+            .saturating_sub(on_board.count())
+            .saturating_sub(rack.len())
+            .saturating_sub(self.rules.rack_size * (self.players - 1))
+    }
+
+    fn place(&self, candidate: &Candidate, rack: &[char], bag: usize) -> Choice {
+        let leave = without(rack, candidate.tiles());
+        let bag = bag.saturating_sub(candidate.placed.len());
+        Choice {
+            play: Play::Place {
+                pos: candidate.pos,
+                dir: candidate.dir,
+                word: candidate.word.clone(),
+                bingo: candidate.bingo,
+            },
+            score: candidate.score,
+            equity: candidate.score as f64 + leave_value(&leave, bag, self.rules, self.tile_set),
+        }
+    }
+
+    fn keep(&self, play: Play, kept: &[char], bag: usize) -> Choice {
+        Choice {
+            play,
+            score: 0,
+            equity: leave_value(kept, bag, self.rules, self.tile_set),
+        }
+    }
+
+    // The exchange keeping the best tiles, when the bag allows one.
+    fn best_exchange(&self, rack: &[char], bag: usize) -> Option<Choice> {
+        let mut best: Option<Choice> = None;
+        for mask in 1..(1u32 << rack.len()) {
+            let (swapped, kept): (Vec<_>, Vec<_>) = rack
+                .iter()
+                .enumerate()
+                .partition(|(i, _)| mask & (1 << i) != 0);
+            if bag < self.rules.exchange_threshold.max(swapped.len()) {
+                continue;
+            }
+            let kept: Vec<char> = kept.into_iter().map(|(_, &l)| l).collect();
+            let letters: String = swapped.into_iter().map(|(_, &l)| l).collect();
+            let choice = self.keep(Play::Exchange { letters }, &kept, bag);
+            if best.as_ref().is_none_or(|b| choice.equity > b.equity) {
+                best = Some(choice);
+            }
+        }
+        best
+    }
+
+    fn turn(&self, board: &Board, turn: &Turn) -> TurnAnalysis {
+        let rack = turn.rack();
+        let bag = self.bag(board, rack);
+        let candidates = movegen::generate(board, rack, &self.trie, self.rules, self.tile_set);
+        let choices: Vec<Choice> = candidates
+            .iter()
+            .map(|candidate| self.place(candidate, rack, bag))
+            .collect();
+
+        let (played, phonies) = match turn {
+            Turn::Move(record) => {
+                let used = record.placed.iter().map(|&(_, l)| movegen::rack_tile(l));
+                let leave = without(rack, used);
+                let after = bag.saturating_sub(record.tiles_placed);
+                let word = main_word(board, record);
+                let phonies = record
+                    .words
+                    .iter()
+                    .map(|(word, _)| word.clone())
+                    .filter(|word| !self.lexicon.contains(word))
+                    .collect();
+                let played = Choice {
+                    play: Play::Place {
+                        pos: record.pos,
+                        dir: record.dir,
+                        word,
+                        bingo: record.bingo,
+                    },
+                    score: record.score,
+                    equity: record.score as f64
+                        + leave_value(&leave, after, self.rules, self.tile_set),
+                };
+                (played, phonies)
+            }
+            Turn::Scoreless(scoreless) => match &scoreless.kind {
+                ScorelessKind::Pass => (self.keep(Play::Pass, rack, bag), Vec::new()),
+                ScorelessKind::Exchange { letters } => {
+                    let kept = without(rack, letters.iter().copied());
+                    let play = Play::Exchange {
+                        letters: letters.iter().collect(),
+                    };
+                    (self.keep(play, &kept, bag), Vec::new())
+                }
+                ScorelessKind::Withdrawn { words } => {
+                    let play = Play::Withdrawn {
+                        words: words.clone(),
+                    };
+                    (self.keep(play, rack, bag), words.clone())
+                }
+                ScorelessKind::ChallengeLost => {
+                    (self.keep(Play::ChallengeLost, rack, bag), Vec::new())
+                }
+            },
+        };
+
+        let best_score = choices.iter().max_by_key(|c| c.score).cloned();
+        let best_equity = choices
+            .iter()
+            .cloned()
+            .chain(self.best_exchange(rack, bag))
+            .chain([self.keep(Play::Pass, rack, bag)])
+            .max_by(|a, b| a.equity.total_cmp(&b.equity))
+            .unwrap_or_else(|| self.keep(Play::Pass, rack, bag));
+        let bingo_played = matches!(played.play, Play::Place { bingo: true, .. });
+        let missed_bingo = if bingo_played {
+            None
+        } else {
+            choices
+                .iter()
+                .filter(|c| matches!(c.play, Play::Place { bingo: true, .. }))
+                .max_by_key(|c| c.score)
+                .cloned()
+        };
+
+        TurnAnalysis {
+            turn: turn.number(),
+            player_id: turn.player_id(),
+            rack: rack.iter().collect(),
+            points_lost: best_score
+                .as_ref()
+                .map_or(0, |best| best.score.saturating_sub(played.score)),
+            equity_lost: (best_equity.equity - played.equity).max(0.0),
+            played,
+            best_score,
+            best_equity,
+            missed_bingo,
+            phonies,
+        }
+    }
+}
+
+// The main word of a recorded move, read off the board before it with the
+// move's tiles added, blanks in lower case.
+fn main_word(board: &Board, record: &MoveRecord) -> String {
+    let mut word = String::new();
+    let (mut row, mut col) = (record.pos.row, record.pos.col);
+    while row < board.cells.len() && col < board.cells.len() {
+        let at = Position { row, col };
+        let letter = match record.placed.iter().find(|(pos, _)| *pos == at) {
+            Some(&(_, letter)) => letter,
+            None => {
+                let cell = &board.cells[row][col];
+                match cell.letter {
+                    Some(letter) if cell.is_blank => letter.to_ascii_lowercase(),
+                    Some(letter) => letter,
+                    None => break,
+                }
+            }
+        };
+        word.push(letter);
+        match record.dir {
+            Direction::Across => col += 1,
+            Direction::Down => row += 1,
+        }
+    }
+    word
+}
+
+fn put(board: &mut Board, record: &MoveRecord) {
+    for &(pos, letter) in &record.placed {
+        let cell = &mut board.cells[pos.row][pos.col];
+        cell.letter = Some(letter.to_ascii_uppercase());
+        cell.is_blank = letter.is_lowercase();
+    }
+}
+
+// Replays a finished game from an empty board and sets every turn
+// against the best moves the rack had.
+pub fn analyze(game: &Game, lexicon: &Lexicon) -> Result<GameAnalysis, AnalysisError> {
+    if !game.game_over {
+        return Err(AnalysisError::Unfinished);
+    }
+    if let Some(record) = game
+        .history
+        .iter()
+        .find(|r| r.rack.is_empty() || r.placed.len() != r.tiles_placed)
+    {
+        return Err(AnalysisError::NotRecorded { turn: record.turn });
+    }
+
+    let context = Context {
+        trie: Trie::new(lexicon),
+        lexicon,
+        rules: &game.rules,
+        tile_set: game.tile_set,
+        players: game.players.len().max(1),
+    };
+    let mut board = game.board.clone();
+    for cell in board.cells.iter_mut().flatten() {
+        cell.letter = None;
+        cell.is_blank = false;
+    }
+
+    let mut analyzed = Vec::new();
+    for turn in turns(game) {
+        analyzed.push(context.turn(&board, &turn));
+        if let Turn::Move(record) = turn {
+            put(&mut board, record);
+        }
+    }
+
+    let players = game
+        .players
+        .iter()
+        .map(|player| {
+            let own: Vec<&TurnAnalysis> = analyzed
+                .iter()
+                .filter(|t| t.player_id == player.id)
+                .collect();
+            let top_plays = own.iter().filter(|t| t.is_top()).count();
+            PlayerSummary {
+                player_id: player.id,
+                name: player.name.clone(),
+                turns: own.len(),
+                points_lost: own.iter().map(|t| t.points_lost).sum(),
+                equity_lost: own.iter().map(|t| t.equity_lost).sum(),
+                top_plays,
+                accuracy: if own.is_empty() {
+                    0.0
+                } else {
+                    100.0 * top_plays as f64 / own.len() as f64
+                },
+                bingos: own
+                    .iter()
+                    .filter(|t| matches!(t.played.play, Play::Place { bingo: true, .. }))
+                    .count(),
+                missed_bingos: own.iter().filter(|t| t.missed_bingo.is_some()).count(),
+                phonies: own.iter().filter(|t| !t.phonies.is_empty()).count(),
+            }
+        })
+        .collect();
+
+    Ok(GameAnalysis {
+        turns: analyzed,
+        players,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(leave: &str, bag: usize) -> f64 {
+        let leave: Vec<char> = leave.chars().collect();
+        leave_value(&leave, bag, &RuleSet::default(), TileSet::English)
+    }
+
+    #[test]
+    fn leave_values() {
+        // Blank and S, one consonant against no vowel.
+        assert_eq!(value("_S", 50), 33.0);
+        assert_eq!(value("Q", 50), -12.0);
+        assert_eq!(value("QU", 50), -4.5);
+        // A duplicate, and two vowels with no consonant.
+        assert_eq!(value("EE", 50), -5.0);
+        assert_eq!(value("AEIR", 50), -2.0);
+        // Bag empty: what is left counts twice against the player.
+        assert_eq!(value("QZ", 0), -40.0);
+    }
+
+    fn context<'a>(lexicon: &'a Lexicon, rules: &'a RuleSet) -> Context<'a> {
+        Context {
+            trie: Trie::new(lexicon),
+            lexicon,
+            rules,
+            tile_set: TileSet::English,
+            players: 2,
+        }
+    }
+
+    #[test]
+    fn a_pass_with_a_bingo_on_the_rack() {
+        let lexicon = Lexicon::from_words(["RETAINS", "RAT"]);
+        let rules = RuleSet::default();
+        let context = context(&lexicon, &rules);
+        let pass = ScorelessTurn {
+            player_id: 1,
+            turn: 1,
+            rack: "RETAINS".chars().collect(),
+            moves_before: 0,
+            kind: ScorelessKind::Pass,
+        };
+        let analysis = context.turn(&Board::new(), &Turn::Scoreless(&pass));
+
+        assert!(!analysis.is_top());
+        assert_eq!(analysis.played.score, 0);
+        // Over the double letter on d8 or l8, doubled by the centre, plus 50.
+        let best = analysis.best_score.unwrap();
+        assert_eq!(best.score, 66);
+        assert_eq!(analysis.points_lost, 66);
+        let missed = analysis.missed_bingo.unwrap();
+        assert_eq!(missed.score, 66);
+        assert!(matches!(missed.play, Play::Place { bingo: true, .. }));
+        assert!(analysis.best_equity.equity >= 66.0);
+    }
+
+    #[test]
+    fn a_small_word_instead_of_the_bingo() {
+        let lexicon = Lexicon::from_words(["RETAINS", "RAT"]);
+        let rules = RuleSet::default();
+        let context = context(&lexicon, &rules);
+        let placed = vec![
+            (Position { row: 7, col: 7 }, 'R'),
+            (Position { row: 7, col: 8 }, 'A'),
+            (Position { row: 7, col: 9 }, 'T'),
+        ];
+        let record = MoveRecord {
+            player_id: 1,
+            turn: 1,
+            pos: placed[0].0,
+            dir: Direction::Across,
+            words: vec![("RAT".to_string(), 6)],
+            score: 6,
+            tiles_placed: 3,
+            blanks_played: 0,
+            bingo: false,
+            rack: "RETAINS".chars().collect(),
+            placed,
+        };
+        let analysis = context.turn(&Board::new(), &Turn::Move(&record));
+
+        assert_eq!(analysis.played.score, 6);
+        assert_eq!(analysis.points_lost, 60);
+        assert_eq!(analysis.missed_bingo.map(|c| c.score), Some(66));
+        assert!(analysis.phonies.is_empty());
+        assert!(analysis.equity_lost > 50.0);
+    }
+}
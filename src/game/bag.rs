@@ -1,4 +1,6 @@
 use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
 
 pub const DISTRIBUTION: [(char, u8, u8); 27] = [
     ('_', 2, 0),
@@ -30,22 +32,113 @@ pub const DISTRIBUTION: [(char, u8, u8); 27] = [
     ('Z', 1, 10),
 ];
 
-#[derive(Copy, Clone)]
+pub const ENGLISH_DISTRIBUTION: [(char, u8, u8); 27] = [
+    ('_', 2, 0),
+    ('A', 9, 1),
+    ('B', 2, 3),
+    ('C', 2, 3),
+    ('D', 4, 2),
+    ('E', 12, 1),
+    ('F', 2, 4),
+    ('G', 3, 2),
+    ('H', 2, 4),
+    ('I', 9, 1),
+    ('J', 1, 8),
+    ('K', 1, 5),
+    ('L', 4, 1),
+    ('M', 2, 3),
+    ('N', 6, 1),
+    ('O', 8, 1),
+    ('P', 2, 3),
+    ('Q', 1, 10),
+    ('R', 6, 1),
+    ('S', 4, 1),
+    ('T', 6, 1),
+    ('U', 4, 1),
+    ('V', 2, 4),
+    ('W', 2, 4),
+    ('X', 1, 8),
+    ('Y', 2, 4),
+    ('Z', 1, 10),
+];
+
+// Which letters are in the bag and what they are worth. Kept in the save
+// so a game always scores with the set it started with.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TileSet {
+    #[default]
+    French,
+    English,
+}
+
+impl TileSet {
+    pub const ALL: [TileSet; 2] = [TileSet::French, TileSet::English];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TileSet::French => "french",
+            TileSet::English => "english",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|set| set.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    // (letter, count, value), blanks as '_'.
+    pub fn distribution(self) -> &'static [(char, u8, u8)] {
+        match self {
+            TileSet::French => &DISTRIBUTION,
+            TileSet::English => &ENGLISH_DISTRIBUTION,
+        }
+    }
+
+    pub fn letter_value(self, letter: char) -> u32 {
+        self.distribution()
+            .iter()
+            .find(|&&(ch, _, _)| ch == letter)
+            .map(|&(_, _, val)| val as u32)
+            .unwrap_or(0)
+    }
+
+    pub fn tile_count(self) -> usize {
+        self.distribution()
+            .iter()
+            .map(|&(_, count, _)| count as usize)
+            .sum()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Tile {
     pub letter: char,
     pub value: u8,
     pub is_blank: bool,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Bag {
     pub tiles: Vec<Tile>,
+    // Only for seeded games, which play out the same every time; a loaded
+    // game shuffles at random again.
+    #[serde(skip)]
+    rng: Option<StdRng>,
+}
+
+impl Default for Bag {
+    fn default() -> Self {
+        Self::new(TileSet::default())
+    }
 }
 
 impl Bag {
-    pub fn new() -> Self {
+    pub fn new(tile_set: TileSet) -> Self {
         let mut tiles = Vec::new();
 
-        for &(ch, count, val) in DISTRIBUTION.iter() {
+        for &(ch, count, val) in tile_set.distribution() {
             for _ in 0..count {
                 let is_blank = val == 0;
                 tiles.push(Tile {
@@ -56,12 +149,21 @@ impl Bag {
             }
         }
 
-        Self { tiles }
+        Self { tiles, rng: None }
+    }
+
+    pub fn seeded(tile_set: TileSet, seed: u64) -> Self {
+        Self {
+            rng: Some(StdRng::seed_from_u64(seed)),
+            ..Self::new(tile_set)
+        }
     }
 
     pub fn shuffle_bag(&mut self) {
-        let mut rng = rand::rng();
-        self.tiles.shuffle(&mut rng);
+        match &mut self.rng {
+            Some(rng) => self.tiles.shuffle(rng),
+            None => self.tiles.shuffle(&mut rand::rng()),
+        }
     }
 
     pub fn draw_n(&mut self, n: usize) -> Vec<Tile> {
@@ -1,15 +1,43 @@
-use crate::{error::MoveError, game::bag::Tile};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::MoveError,
+    game::bag::{Tile, TileSet},
+};
 
 use super::player::Rack;
 
 pub const BOARD_SIZE: usize = 15;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Position {
     pub row: usize,
     pub col: usize,
 }
 
+impl Position {
+    // "h8": column letter, then row number.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().to_lowercase();
+        let mut chars = input.chars();
+        let col = chars.next().filter(|c| ('a'..='o').contains(c))? as usize - 'a' as usize;
+        let row = chars.as_str().parse::<usize>().ok()?;
+        if !(1..=BOARD_SIZE).contains(&row) {
+            return None;
+        }
+        Some(Self { row: row - 1, col })
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", (b'a' + self.col as u8) as char, self.row + 1)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
     Across,
     Down,
@@ -19,7 +47,31 @@ pub struct Word {
     pub tiles: Vec<Tile>,
 }
 
-#[derive(Copy, Clone)]
+// A rack tile going down on an empty square.
+#[derive(Copy, Clone, Debug)]
+pub struct PlannedTile {
+    pub pos: Position,
+    pub letter: char,
+    pub is_blank: bool,
+    // Where it sits on the rack the move was planned against.
+    pub rack_index: usize,
+}
+
+// Everything a move changes, known before anything is changed.
+#[derive(Clone, Debug)]
+pub struct MovePlan {
+    pub pos: Position,
+    pub dir: Direction,
+    pub tiles: Vec<PlannedTile>,
+}
+
+impl MovePlan {
+    pub fn positions(&self) -> Vec<Position> {
+        self.tiles.iter().map(|t| t.pos).collect()
+    }
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum CellKind {
     Normal,
     DoubleLetter,
@@ -28,21 +80,31 @@ pub enum CellKind {
     TripleWord,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Cell {
     pub letter: Option<char>,
     pub kind: CellKind,
+    #[serde(default)]
+    pub is_blank: bool,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Board {
     pub cells: [[Cell; BOARD_SIZE]; BOARD_SIZE],
 }
 
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Board {
     pub fn new() -> Self {
         let mut cells = [[Cell {
             letter: None,
             kind: CellKind::Normal,
+            is_blank: false,
         }; BOARD_SIZE]; BOARD_SIZE];
 
         let mut set_kind = |positions: &[(usize, usize)], kind: CellKind| {
@@ -132,6 +194,37 @@ impl Board {
         Self { cells }
     }
 
+    // A custom premium layout: BOARD_SIZE lines of BOARD_SIZE squares,
+    // '.' plain, 'd'/'t' double/triple letter, 'D'/'T' double/triple word.
+    pub fn from_layout(text: &str) -> Option<Self> {
+        let rows: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if rows.len() != BOARD_SIZE {
+            return None;
+        }
+
+        let mut board = Self::new();
+        for (r, row) in rows.iter().enumerate() {
+            if row.chars().count() != BOARD_SIZE {
+                return None;
+            }
+            for (c, ch) in row.chars().enumerate() {
+                board.cells[r][c].kind = match ch {
+                    '.' => CellKind::Normal,
+                    'd' => CellKind::DoubleLetter,
+                    't' => CellKind::TripleLetter,
+                    'D' => CellKind::DoubleWord,
+                    'T' => CellKind::TripleWord,
+                    _ => return None,
+                };
+            }
+        }
+        Some(board)
+    }
+
     fn is_empty(&self) -> bool {
         self.cells
             .iter()
@@ -166,36 +259,25 @@ impl Board {
         Ok(())
     }
 
-    pub fn validate_player_has_tiles(&self, rack: &Rack, word: &Word) -> Result<(), MoveError> {
-        let mut available_tiles = rack.tiles.clone();
-
-        for tile in &word.tiles {
-            if let Some(idx) = available_tiles.iter().position(|t| t.letter == tile.letter) {
-                available_tiles.remove(idx);
-            } else {
-                return Err(MoveError::MissingLetter {
-                    letter: tile.letter,
-                });
-            }
-        }
-
-        Ok(())
-    }
-
+    // The first word has to cover `start`, the others touch what's there.
     pub fn validate_adjacent_tiles(
         &self,
         pos: &Position,
         dir: &Direction,
         word: &Word,
+        start: Position,
     ) -> Result<(), MoveError> {
         if self.is_empty() {
             for i in 0..word.tiles.len() {
                 let (row, col) = Self::step_towards_dir(pos, dir, i);
-                if row == 7 && col == 7 {
+                if row == start.row && col == start.col {
                     return Ok(());
                 }
             }
-            return Err(MoveError::MustCoverCenter);
+            return Err(MoveError::MustCoverStart {
+                row: start.row,
+                col: start.col,
+            });
         }
 
         for i in 0..word.tiles.len() {
@@ -226,9 +308,7 @@ impl Board {
 
     fn is_cell_available(
         &self,
-        word: &Word,
         target_letter: char,
-        i: usize,
         row: usize,
         col: usize,
     ) -> Result<bool, MoveError> {
@@ -243,32 +323,182 @@ impl Board {
         }
     }
 
-    pub fn place_word(
-        &mut self,
-        rack: &mut Rack,
+    // Works a move out against the board and rack as they are, touching
+    // neither: any reason to refuse it comes up here, before a single tile
+    // has moved.
+    pub fn plan_move(
+        &self,
+        rack: &Rack,
         pos: &Position,
         dir: &Direction,
         word: &Word,
-    ) -> Result<(), MoveError> {
-        for i in 0..word.tiles.len() {
-            let target_letter = word.tiles[i].letter;
-
+        start: Position,
+    ) -> Result<MovePlan, MoveError> {
+        self.validate_in_bounds(pos, dir, word)?;
+        self.validate_adjacent_tiles(pos, dir, word, start)?;
+
+        let mut used = vec![false; rack.tiles.len()];
+        let mut tiles = Vec::new();
+        for (i, tile) in word.tiles.iter().enumerate() {
+            let letter = tile.letter;
             let (row, col) = Self::step_towards_dir(pos, dir, i);
 
-            if self.is_cell_available(word, target_letter, i, row, col)? {
+            if self.is_cell_available(letter, row, col)? {
                 continue;
             }
 
-            let index = rack
-                .tiles
-                .iter()
-                .position(|tile| tile.letter == target_letter)
-                .ok_or(MoveError::MissingLetter {
-                    letter: target_letter,
-                })?;
-            let tile = rack.tiles.remove(index);
-            self.cells[row][col].letter = Some(tile.letter);
+            // A tile asked for as a blank takes one; otherwise fall back on
+            // a blank when the letter itself isn't on the rack.
+            let free = |pred: &dyn Fn(&Tile) -> bool| {
+                (0..rack.tiles.len()).find(|&j| !used[j] && pred(&rack.tiles[j]))
+            };
+            let own = |t: &Tile| !t.is_blank && t.letter == letter;
+            let rack_index = if tile.is_blank { None } else { free(&own) }
+                .or_else(|| free(&|t| t.is_blank))
+                .ok_or(MoveError::MissingLetter { letter })?;
+            used[rack_index] = true;
+            tiles.push(PlannedTile {
+                pos: Position { row, col },
+                letter,
+                is_blank: rack.tiles[rack_index].is_blank,
+                rack_index,
+            });
         }
-        Ok(())
+        if tiles.is_empty() {
+            return Err(MoveError::NothingPlaced);
+        }
+
+        Ok(MovePlan {
+            pos: *pos,
+            dir: *dir,
+            tiles,
+        })
+    }
+
+    fn lay(&mut self, plan: &MovePlan) {
+        for tile in &plan.tiles {
+            let cell = &mut self.cells[tile.pos.row][tile.pos.col];
+            cell.letter = Some(tile.letter);
+            cell.is_blank = tile.is_blank;
+        }
+    }
+
+    // The board as it would be after the move, to score it or look its
+    // words up first.
+    pub fn with_plan(&self, plan: &MovePlan) -> Board {
+        let mut board = self.clone();
+        board.lay(plan);
+        board
+    }
+
+    // Plays a planned move. The plan has to come from this board and rack
+    // as they are now: everything was checked then, so nothing can go
+    // wrong halfway.
+    pub fn commit(&mut self, rack: &mut Rack, plan: &MovePlan) {
+        self.lay(plan);
+        let mut indices: Vec<usize> = plan.tiles.iter().map(|t| t.rack_index).collect();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        for index in indices {
+            rack.tiles.remove(index);
+        }
+    }
+
+    fn occupied(&self, row: usize, col: usize) -> bool {
+        self.in_bounds(row, col) && self.cells[row][col].letter.is_some()
+    }
+
+    fn word_start(&self, pos: Position, dir: &Direction) -> Position {
+        let Position { mut row, mut col } = pos;
+        match dir {
+            Direction::Across => {
+                while col > 0 && self.occupied(row, col - 1) {
+                    col -= 1;
+                }
+            }
+            Direction::Down => {
+                while row > 0 && self.occupied(row - 1, col) {
+                    row -= 1;
+                }
+            }
+        }
+        Position { row, col }
+    }
+
+    fn score_word_at(
+        &self,
+        start: Position,
+        dir: &Direction,
+        placed: &[Position],
+        tile_set: TileSet,
+    ) -> (String, u32) {
+        let mut word = String::new();
+        let mut sum = 0;
+        let mut word_multiplier = 1;
+
+        let mut i = 0;
+        loop {
+            let (row, col) = Self::step_towards_dir(&start, dir, i);
+            if !self.occupied(row, col) {
+                break;
+            }
+
+            let cell = &self.cells[row][col];
+            let letter = cell.letter.unwrap_or(' ');
+            let mut value = if cell.is_blank {
+                0
+            } else {
+                tile_set.letter_value(letter)
+            };
+
+            // Premium squares only count on the turn they are covered.
+            if placed.contains(&Position { row, col }) {
+                match cell.kind {
+                    CellKind::DoubleLetter => value *= 2,
+                    CellKind::TripleLetter => value *= 3,
+                    CellKind::DoubleWord => word_multiplier *= 2,
+                    CellKind::TripleWord => word_multiplier *= 3,
+                    CellKind::Normal => {}
+                }
+            }
+
+            word.push(letter);
+            sum += value;
+            i += 1;
+        }
+
+        (word, sum * word_multiplier)
+    }
+
+    pub fn words_formed(
+        &self,
+        pos: &Position,
+        dir: &Direction,
+        placed: &[Position],
+        tile_set: TileSet,
+    ) -> Vec<(String, u32)> {
+        let mut words = Vec::new();
+
+        let main = self.score_word_at(self.word_start(*pos, dir), dir, placed, tile_set);
+        if main.0.chars().count() >= 2 {
+            words.push(main);
+        }
+
+        let cross_dir = match dir {
+            Direction::Across => Direction::Down,
+            Direction::Down => Direction::Across,
+        };
+        for &tile_pos in placed {
+            let cross = self.score_word_at(
+                self.word_start(tile_pos, &cross_dir),
+                &cross_dir,
+                placed,
+                tile_set,
+            );
+            if cross.0.chars().count() >= 2 {
+                words.push(cross);
+            }
+        }
+
+        words
     }
 }
@@ -0,0 +1,105 @@
+use clap::ValueEnum;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+
+use super::Game;
+use super::action::Action;
+use super::analysis::leave_value;
+use super::bag::Tile;
+use super::board::Word;
+use super::movegen::{self, Candidate, Trie};
+use super::unseen::BLANK;
+
+// How a computer player picks its move.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Strategy {
+    // The highest score.
+    Greedy,
+    // The highest score plus what the tiles kept are worth.
+    Equity,
+    // Any word it can play.
+    Random,
+}
+
+impl Strategy {
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::Greedy => "greedy",
+            Strategy::Equity => "equity",
+            Strategy::Random => "random",
+        }
+    }
+}
+
+fn place(candidate: &Candidate) -> Action {
+    Action::PlaceWord {
+        pos: candidate.pos,
+        dir: candidate.dir,
+        word: Word {
+            tiles: candidate
+                .word
+                .chars()
+                .map(|letter| Tile {
+                    letter: letter.to_ascii_uppercase(),
+                    value: 0,
+                    is_blank: letter.is_lowercase(),
+                })
+                .collect(),
+        },
+    }
+}
+
+// Nothing to play: swap what isn't worth keeping if the bag allows it,
+// pass otherwise.
+fn stuck(game: &Game, rack: &[char]) -> Action {
+    let mut letters: Vec<char> = rack
+        .iter()
+        .copied()
+        .filter(|&letter| letter != BLANK && letter != 'S')
+        .collect();
+    if letters.is_empty() {
+        letters = rack.to_vec();
+    }
+    if letters.is_empty() || game.bag.tiles.len() < game.rules.exchange_threshold.max(letters.len())
+    {
+        return Action::Pass;
+    }
+    Action::ExchangeTiles { letters }
+}
+
+// The current player's move.
+pub fn choose(game: &Game, trie: &Trie, strategy: Strategy, rng: &mut impl Rng) -> Action {
+    let rack: Vec<char> = game
+        .current_player()
+        .rack
+        .tiles
+        .iter()
+        .map(|tile| if tile.is_blank { BLANK } else { tile.letter })
+        .collect();
+    let candidates = movegen::generate(&game.board, &rack, trie, &game.rules, game.tile_set);
+
+    let bag = game.bag.tiles.len();
+    let equity = |candidate: &Candidate| {
+        let mut leave = rack.clone();
+        for tile in candidate.tiles() {
+            if let Some(i) = leave.iter().position(|&l| l == tile) {
+                leave.remove(i);
+            }
+        }
+        let bag = bag.saturating_sub(candidate.placed.len());
+        candidate.score as f64 + leave_value(&leave, bag, &game.rules, game.tile_set)
+    };
+    let chosen = match strategy {
+        Strategy::Greedy => candidates.iter().max_by_key(|c| c.score),
+        Strategy::Equity => candidates
+            .iter()
+            .max_by(|a, b| equity(a).total_cmp(&equity(b))),
+        Strategy::Random => candidates.choose(rng),
+    };
+    match chosen {
+        Some(candidate) => place(candidate),
+        None => stuck(game, &rack),
+    }
+}
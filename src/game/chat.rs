@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::Game;
+use crate::error::ChatError;
+
+pub const MAX_MESSAGE_LEN: usize = 200;
+// At most RATE_LIMIT messages per sender within RATE_WINDOW_SECS.
+pub const RATE_LIMIT: usize = 5;
+pub const RATE_WINDOW_SECS: i64 = 30;
+
+// Emotes travel as identifiers and are put into words by whoever shows them,
+// so every player reads them in their own language.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Emote {
+    Hello,
+    NiceBingo,
+    WellPlayed,
+    Thinking,
+    Oops,
+    GoodGame,
+}
+
+impl Emote {
+    pub const ALL: [Emote; 6] = [
+        Emote::Hello,
+        Emote::NiceBingo,
+        Emote::WellPlayed,
+        Emote::Thinking,
+        Emote::Oops,
+        Emote::GoodGame,
+    ];
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChatBody {
+    Text { text: String },
+    Emote { emote: Emote },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub from: String,
+    pub at_unix: i64,
+    pub body: ChatBody,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Chat {
+    pub messages: Vec<ChatMessage>,
+    // Player name -> names whose messages they no longer want to see.
+    #[serde(default)]
+    pub muted: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Chat {
+    pub fn post(
+        &mut self,
+        from: &str,
+        body: ChatBody,
+        at_unix: i64,
+    ) -> Result<&ChatMessage, ChatError> {
+        let body = match body {
+            ChatBody::Text { text } => {
+                let text = text.trim().to_string();
+                if text.is_empty() {
+                    return Err(ChatError::Empty);
+                }
+                if text.chars().count() > MAX_MESSAGE_LEN {
+                    return Err(ChatError::TooLong {
+                        max: MAX_MESSAGE_LEN,
+                    });
+                }
+                ChatBody::Text { text }
+            }
+            emote => emote,
+        };
+
+        // The history doubles as the rate limiter's memory, so the limit
+        // holds across reloads and reconnections.
+        let window_start = at_unix - RATE_WINDOW_SECS;
+        let recent: Vec<i64> = self
+            .messages
+            .iter()
+            .filter(|m| m.from == from && m.at_unix > window_start)
+            .map(|m| m.at_unix)
+            .collect();
+        if recent.len() >= RATE_LIMIT {
+            let oldest = recent[recent.len() - RATE_LIMIT];
+            return Err(ChatError::RateLimited {
+                retry_in_secs: (oldest + RATE_WINDOW_SECS + 1 - at_unix).max(1),
+            });
+        }
+
+        self.messages.push(ChatMessage {
+            from: from.to_string(),
+            at_unix,
+            body,
+        });
+        Ok(self.messages.last().unwrap())
+    }
+
+    // Returns false if `target` was already muted.
+    pub fn mute(&mut self, by: &str, target: &str) -> bool {
+        self.muted
+            .entry(by.to_string())
+            .or_default()
+            .insert(target.to_string())
+    }
+
+    // Returns false if `target` was not muted.
+    pub fn unmute(&mut self, by: &str, target: &str) -> bool {
+        let Some(muted) = self.muted.get_mut(by) else {
+            return false;
+        };
+        let removed = muted.remove(target);
+        if muted.is_empty() {
+            self.muted.remove(by);
+        }
+        removed
+    }
+
+    pub fn is_muted(&self, by: &str, from: &str) -> bool {
+        self.muted.get(by).is_some_and(|muted| muted.contains(from))
+    }
+
+    // The last `count` messages `reader` wants to see, oldest first.
+    // Without a reader (spectators) nothing is filtered.
+    pub fn recent(&self, reader: Option<&str>, count: usize) -> Vec<ChatMessage> {
+        let mut visible: Vec<ChatMessage> = self
+            .messages
+            .iter()
+            .rev()
+            .filter(|m| reader.is_none_or(|r| !self.is_muted(r, &m.from)))
+            .take(count)
+            .cloned()
+            .collect();
+        visible.reverse();
+        visible
+    }
+}
+
+impl Game {
+    // Only seated players can be muted; returns false if nothing changed.
+    pub fn set_muted(&mut self, by: &str, target: &str, muted: bool) -> Result<bool, ChatError> {
+        if !self.players.iter().any(|p| p.name == target) {
+            return Err(ChatError::UnknownPlayer {
+                name: target.to_string(),
+            });
+        }
+        Ok(if muted {
+            self.chat.mute(by, target)
+        } else {
+            self.chat.unmute(by, target)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> ChatBody {
+        ChatBody::Text {
+            text: text.to_string(),
+        }
+    }
+
+    fn senders(chat: &Chat, reader: Option<&str>) -> Vec<String> {
+        chat.recent(reader, 10)
+            .into_iter()
+            .map(|m| m.from)
+            .collect()
+    }
+
+    #[test]
+    fn messages_over_the_rate_are_held_back() {
+        let mut chat = Chat::default();
+        for at in 100..100 + RATE_LIMIT as i64 {
+            chat.post("Ann", text("hi"), at).unwrap();
+        }
+        // The first went out at 100, so one more is allowed from 131 on.
+        assert_eq!(
+            chat.post("Ann", text("hi"), 110).err(),
+            Some(ChatError::RateLimited { retry_in_secs: 21 })
+        );
+        // Others aren't held back by Ann's messages.
+        chat.post("Bob", text("hi"), 110).unwrap();
+        chat.post("Ann", text("hi"), 131).unwrap();
+        assert_eq!(chat.messages.len(), RATE_LIMIT + 2);
+    }
+
+    #[test]
+    fn long_and_empty_messages_are_refused() {
+        let mut chat = Chat::default();
+        let longest = "a".repeat(MAX_MESSAGE_LEN);
+        chat.post("Ann", text(&longest), 0).unwrap();
+        assert_eq!(
+            chat.post("Ann", text(&format!("{longest}a")), 1).err(),
+            Some(ChatError::TooLong {
+                max: MAX_MESSAGE_LEN
+            })
+        );
+        assert_eq!(
+            chat.post("Ann", text("   "), 2).err(),
+            Some(ChatError::Empty)
+        );
+        assert_eq!(chat.messages.len(), 1);
+    }
+
+    #[test]
+    fn muted_players_are_hidden_from_whoever_muted_them() {
+        let mut game = Game::new();
+        game.add_player("Ann".to_string());
+        game.add_player("Bob".to_string());
+        game.chat.post("Ann", text("hello"), 0).unwrap();
+        game.chat.post("Bob", text("hi"), 1).unwrap();
+
+        assert_eq!(game.set_muted("Ann", "Bob", true), Ok(true));
+        assert_eq!(game.set_muted("Ann", "Bob", true), Ok(false));
+        assert_eq!(
+            game.set_muted("Ann", "Cid", true),
+            Err(ChatError::UnknownPlayer {
+                name: "Cid".to_string()
+            })
+        );
+
+        assert_eq!(senders(&game.chat, Some("Ann")), ["Ann"]);
+        assert_eq!(senders(&game.chat, Some("Bob")), ["Ann", "Bob"]);
+        assert_eq!(senders(&game.chat, None), ["Ann", "Bob"]);
+
+        assert_eq!(game.set_muted("Ann", "Bob", false), Ok(true));
+        assert_eq!(senders(&game.chat, Some("Ann")), ["Ann", "Bob"]);
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+const MINUTE_MS: i64 = 60_000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeControl {
+    pub initial_secs: u64,
+    pub increment_secs: u64,
+    pub forfeit_on_timeout: bool,
+}
+
+impl TimeControl {
+    // "25+5" is 25 minutes per player plus 5 seconds added after each move.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (minutes, increment) = match input.split_once('+') {
+            Some((minutes, increment)) => (minutes.trim(), increment.trim()),
+            None => (input.trim(), "0"),
+        };
+        let minutes = minutes.parse::<u64>().ok().filter(|&m| m > 0)?;
+        let increment = increment.parse::<u64>().ok()?;
+
+        Some(Self {
+            initial_secs: minutes * 60,
+            increment_secs: increment,
+            forfeit_on_timeout: false,
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Clock {
+    pub control: TimeControl,
+    // Milliseconds left per player, negative once in overtime.
+    pub remaining_ms: Vec<i64>,
+    #[serde(skip)]
+    running: Option<(usize, Instant)>,
+}
+
+impl Clock {
+    pub fn new(control: TimeControl, player_count: usize) -> Self {
+        Self {
+            control,
+            remaining_ms: vec![control.initial_secs as i64 * 1000; player_count],
+            running: None,
+        }
+    }
+
+    pub fn add_player(&mut self) {
+        self.remaining_ms
+            .push(self.control.initial_secs as i64 * 1000);
+    }
+
+    pub fn start(&mut self, player_index: usize) {
+        if self.running.is_none() {
+            self.running = Some((player_index, Instant::now()));
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some((idx, since)) = self.running.take() {
+            self.remaining_ms[idx] -= since.elapsed().as_millis() as i64;
+            self.remaining_ms[idx] += self.control.increment_secs as i64 * 1000;
+        }
+    }
+
+    // Charges the time spent so far without ending the turn, so a save
+    // taken mid-turn doesn't hand the thinking time back: the timing part
+    // of the clock isn't saved.
+    pub fn charge(&mut self) {
+        if let Some((idx, since)) = &mut self.running {
+            self.remaining_ms[*idx] -= since.elapsed().as_millis() as i64;
+            *since = Instant::now();
+        }
+    }
+
+    pub fn remaining(&self, player_index: usize) -> i64 {
+        let mut ms = self.remaining_ms[player_index];
+        if let Some((idx, since)) = self.running
+            && idx == player_index
+        {
+            ms -= since.elapsed().as_millis() as i64;
+        }
+        ms
+    }
+
+    pub fn is_flagged(&self, player_index: usize) -> bool {
+        self.remaining(player_index) < 0
+    }
+
+    // Started minutes spent past the flag; the rules say what each costs.
+    pub fn overtime_minutes(&self, player_index: usize) -> u32 {
+        let over = -self.remaining_ms[player_index];
+        if over <= 0 {
+            return 0;
+        }
+        // Every started minute counts.
+        let minutes = (over + MINUTE_MS - 1) / MINUTE_MS;
+        minutes as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    fn control(initial_secs: u64, increment_secs: u64) -> TimeControl {
+        TimeControl {
+            initial_secs,
+            increment_secs,
+            forfeit_on_timeout: false,
+        }
+    }
+
+    #[test]
+    fn parse_minutes_and_increment() {
+        assert_eq!(TimeControl::parse("25+5"), Some(control(1500, 5)));
+        assert_eq!(TimeControl::parse(" 3 + 2 "), Some(control(180, 2)));
+        assert_eq!(TimeControl::parse("10"), Some(control(600, 0)));
+        for bad in ["", "0+5", "+5", "5+", "5+x", "ten", "-5"] {
+            assert_eq!(TimeControl::parse(bad), None, "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn the_increment_comes_after_the_move() {
+        let mut clock = Clock::new(control(60, 5), 2);
+        clock.start(1);
+        thread::sleep(Duration::from_millis(20));
+        assert!(clock.remaining(1) < 60_000);
+        assert_eq!(clock.remaining(0), 60_000);
+
+        clock.stop();
+        let left = clock.remaining_ms[1];
+        assert!((64_000..65_000).contains(&left), "{left}");
+        assert_eq!(clock.remaining_ms[0], 60_000);
+        // Stopped twice, added once.
+        clock.stop();
+        assert_eq!(clock.remaining_ms[1], left);
+    }
+
+    #[test]
+    fn charging_keeps_the_clock_running() {
+        let mut clock = Clock::new(control(60, 5), 1);
+        clock.start(0);
+        thread::sleep(Duration::from_millis(20));
+        clock.charge();
+        let charged = clock.remaining_ms[0];
+        assert!(charged <= 59_980, "{charged}");
+        assert!(clock.running.is_some());
+
+        // What was charged isn't charged again, and no increment yet.
+        clock.stop();
+        assert!(clock.remaining_ms[0] <= charged + 5_000);
+        assert!(clock.remaining_ms[0] > charged + 4_000);
+    }
+
+    #[test]
+    fn every_started_minute_of_overtime_counts() {
+        let mut clock = Clock::new(control(60, 0), 1);
+        for (ms, minutes) in [(0, 0), (1, 0), (-1, 1), (-60_000, 1), (-60_001, 2)] {
+            clock.remaining_ms[0] = ms;
+            assert_eq!(clock.overtime_minutes(0), minutes, "{ms}");
+            assert_eq!(clock.is_flagged(0), ms < 0);
+        }
+    }
+}
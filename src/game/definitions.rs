@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// What the words mean, for players to look up. Independent of the lexicon:
+// a word can be valid without a definition here, and the other way round.
+pub struct Definitions {
+    entries: HashMap<String, String>,
+}
+
+impl Definitions {
+    // One "WORD<tab>definition" per line. Lines starting with '#' or without
+    // a tab are ignored; a word found twice gets both meanings.
+    pub fn parse(text: &str) -> Self {
+        let mut entries: HashMap<String, String> = HashMap::new();
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let Some((word, meaning)) = line.split_once('\t') else {
+                continue;
+            };
+            let (word, meaning) = (word.trim().to_uppercase(), meaning.trim());
+            if word.is_empty() || meaning.is_empty() {
+                continue;
+            }
+            entries
+                .entry(word)
+                .and_modify(|known| {
+                    known.push_str(" / ");
+                    known.push_str(meaning);
+                })
+                .or_insert_with(|| meaning.to_string());
+        }
+        Self { entries }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.entries
+            .get(&word.trim().to_uppercase())
+            .map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_found_twice_get_both_meanings() {
+        let definitions =
+            Definitions::parse("chat\tanimal domestique\nCHAT\tdiscussion en ligne\n");
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(
+            definitions.get("CHAT"),
+            Some("animal domestique / discussion en ligne")
+        );
+    }
+
+    #[test]
+    fn comments_and_lines_without_a_tab_are_skipped() {
+        let text = "# CHIEN\tcommentaire\nCHIEN animal\n\nKA\t\n\tsans mot\nZO\tboeuf du Tibet\n";
+        let definitions = Definitions::parse(text);
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions.get("CHIEN"), None);
+        assert_eq!(definitions.get("KA"), None);
+        assert_eq!(definitions.get("ZO"), Some("boeuf du Tibet"));
+        assert!(Definitions::parse("# rien\n").is_empty());
+    }
+
+    #[test]
+    fn lookups_ignore_case_and_spaces() {
+        let definitions = Definitions::parse("  Kiwi \t fruit \n");
+        assert_eq!(definitions.get("kiwi"), Some("fruit"));
+        assert_eq!(definitions.get(" KiWi "), Some("fruit"));
+        assert_eq!(definitions.get("KIWIS"), None);
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use super::Game;
+use super::board::{BOARD_SIZE, Position};
+use super::move_score;
+use super::unseen::BLANK;
+use crate::error::IntegrityError;
+
+impl Game {
+    // Checks what must hold whatever was played: every tile of the set is
+    // in the bag, on a rack or on the board, once; the board holds one
+    // group of tiles through the starting square; and the scores are what
+    // the moves and adjustments add up to.
+    pub fn verify_integrity(&self) -> Result<(), IntegrityError> {
+        self.verify_tiles()?;
+        self.verify_board()?;
+        self.verify_scores()
+    }
+
+    fn verify_tiles(&self) -> Result<(), IntegrityError> {
+        let mut found: BTreeMap<char, usize> = BTreeMap::new();
+        let bag = self.bag.tiles.iter();
+        let racks = self.players.iter().flat_map(|p| p.rack.tiles.iter());
+        for tile in bag.chain(racks) {
+            let letter = if tile.is_blank { BLANK } else { tile.letter };
+            *found.entry(letter).or_default() += 1;
+        }
+        for cell in self.board.cells.iter().flatten() {
+            if let Some(letter) = cell.letter {
+                let letter = if cell.is_blank { BLANK } else { letter };
+                *found.entry(letter).or_default() += 1;
+            }
+        }
+
+        for &(letter, count, _) in self.tile_set.distribution() {
+            let expected = count as usize;
+            let count = found.remove(&letter).unwrap_or(0);
+            if count != expected {
+                return Err(IntegrityError::TileCount {
+                    letter,
+                    expected,
+                    found: count,
+                });
+            }
+        }
+        // Letters the set doesn't have at all.
+        match found.into_iter().next() {
+            Some((letter, count)) => Err(IntegrityError::TileCount {
+                letter,
+                expected: 0,
+                found: count,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn verify_board(&self) -> Result<(), IntegrityError> {
+        let occupied = |row: usize, col: usize| self.board.cells[row][col].letter.is_some();
+        let tiles = (0..BOARD_SIZE)
+            .flat_map(|row| (0..BOARD_SIZE).map(move |col| (row, col)))
+            .filter(|&(row, col)| occupied(row, col))
+            .count();
+        if tiles == 0 {
+            return Ok(());
+        }
+        let start = self.rules.first_move;
+        if !occupied(start.row, start.col) {
+            return Err(IntegrityError::StartNotCovered);
+        }
+
+        // Everything has to be reachable from the starting square.
+        let mut reached = [[false; BOARD_SIZE]; BOARD_SIZE];
+        let mut queue = VecDeque::from([(start.row, start.col)]);
+        reached[start.row][start.col] = true;
+        while let Some((row, col)) = queue.pop_front() {
+            let neighbours = [
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ];
+            for (r, c) in neighbours {
+                if r < BOARD_SIZE && c < BOARD_SIZE && occupied(r, c) && !reached[r][c] {
+                    reached[r][c] = true;
+                    queue.push_back((r, c));
+                }
+            }
+        }
+        let detached = (0..BOARD_SIZE)
+            .flat_map(|row| (0..BOARD_SIZE).map(move |col| Position { row, col }))
+            .find(|pos| occupied(pos.row, pos.col) && !reached[pos.row][pos.col]);
+        if let Some(pos) = detached {
+            return Err(IntegrityError::Detached { pos });
+        }
+        Ok(())
+    }
+
+    fn verify_scores(&self) -> Result<(), IntegrityError> {
+        for record in &self.history {
+            let (expected, _) = move_score(&record.words, record.tiles_placed, &self.rules);
+            if record.score != expected {
+                return Err(IntegrityError::MoveScore {
+                    turn: record.turn,
+                    expected,
+                    found: record.score,
+                });
+            }
+        }
+
+        let Some(adjustments) = &self.adjustments else {
+            return Ok(());
+        };
+        for player in &self.players {
+            let moves: i64 = self
+                .history
+                .iter()
+                .filter(|r| r.player_id == player.id)
+                .map(|r| r.score as i64)
+                .sum();
+            let adjusted: i64 = adjustments
+                .iter()
+                .filter(|a| a.player_id == player.id)
+                .map(|a| a.points)
+                .sum();
+            let expected = moves + adjusted;
+            if player.score as i64 != expected {
+                return Err(IntegrityError::Score {
+                    name: player.name.clone(),
+                    expected,
+                    found: player.score,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MoveError;
+    use crate::game::GameOptions;
+    use crate::game::action::Action;
+    use crate::game::bag::Tile;
+    use crate::game::board::{Direction, Word};
+
+    // Two players, one word on the board.
+    fn played_game() -> Game {
+        let mut game = Game::with_options(&GameOptions {
+            seed: Some(7),
+            ..GameOptions::default()
+        });
+        game.add_player("Ann".to_string());
+        game.add_player("Bob".to_string());
+
+        // Without a word list any letters make a word.
+        let tiles = game.players[0]
+            .rack
+            .tiles
+            .iter()
+            .filter(|t| !t.is_blank)
+            .take(3)
+            .cloned()
+            .collect();
+        game.apply_move(Action::PlaceWord {
+            pos: game.rules.first_move,
+            dir: Direction::Across,
+            word: Word { tiles },
+        })
+        .unwrap();
+        game.end_turn();
+        game
+    }
+
+    #[test]
+    fn a_played_game_checks_out() {
+        let game = played_game();
+        assert!(game.players[0].score > 0);
+        assert_eq!(game.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn a_tile_missing_from_the_bag() {
+        let mut game = played_game();
+        let tile = game.bag.tiles.pop().unwrap();
+        let letter = if tile.is_blank { BLANK } else { tile.letter };
+
+        match game.verify_integrity() {
+            Err(IntegrityError::TileCount {
+                letter: short,
+                expected,
+                found,
+            }) => {
+                assert_eq!(short, letter);
+                assert_eq!(found + 1, expected);
+            }
+            other => panic!("missing tile not reported: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn a_letter_floating_on_the_board() {
+        let mut game = played_game();
+        // Straight from the bag, so the tile count still adds up.
+        let tile = game.bag.tiles.pop().unwrap();
+        let cell = &mut game.board.cells[0][0];
+        cell.letter = Some(tile.letter);
+        cell.is_blank = tile.is_blank;
+
+        assert_eq!(
+            game.verify_integrity(),
+            Err(IntegrityError::Detached {
+                pos: Position { row: 0, col: 0 }
+            })
+        );
+    }
+
+    #[test]
+    fn a_score_that_does_not_match_the_history() {
+        let mut game = played_game();
+        let played = game.players[0].score;
+        game.players[0].score += 5;
+        assert_eq!(
+            game.verify_integrity(),
+            Err(IntegrityError::Score {
+                name: "Ann".to_string(),
+                expected: played as i64,
+                found: played + 5,
+            })
+        );
+
+        // A move recorded for the wrong points is caught on its own.
+        game.players[0].score = played + 5;
+        game.history[0].score += 5;
+        assert_eq!(
+            game.verify_integrity(),
+            Err(IntegrityError::MoveScore {
+                turn: 1,
+                expected: played,
+                found: played + 5,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn debug_builds_undo_a_move_that_fails_the_check() {
+        let mut game = played_game();
+        game.bag.tiles.pop();
+        let letters = |game: &Game| -> Vec<Option<char>> {
+            game.board
+                .cells
+                .iter()
+                .flatten()
+                .map(|c| c.letter)
+                .collect()
+        };
+        let board = letters(&game);
+        let rack = game.players[1].rack.tiles.clone();
+        let bag = game.bag.tiles.clone();
+
+        // Bob's word would go down through Ann's first letter.
+        let first = game.board.cells[7][7].letter.unwrap();
+        let mut tiles: Vec<_> = game.players[1]
+            .rack
+            .tiles
+            .iter()
+            .filter(|t| !t.is_blank)
+            .take(2)
+            .cloned()
+            .collect();
+        tiles.push(Tile {
+            letter: first,
+            value: 0,
+            is_blank: false,
+        });
+        let result = game.apply_move(Action::PlaceWord {
+            pos: Position { row: 5, col: 7 },
+            dir: Direction::Down,
+            word: Word { tiles },
+        });
+
+        assert!(matches!(
+            result,
+            Err(MoveError::Integrity(IntegrityError::TileCount { .. }))
+        ));
+        assert_eq!(letters(&game), board);
+        assert_eq!(game.players[1].rack.tiles, rack);
+        assert_eq!(game.bag.tiles, bag);
+        assert_eq!(game.players[1].score, 0);
+        assert_eq!(game.history.len(), 1);
+        assert_eq!(game.current_player_index, 1);
+    }
+}
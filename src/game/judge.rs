@@ -0,0 +1,150 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::lexicon::Lexicon;
+
+// Kept apart from the game saves, like the profiles.
+const JUDGE_DIR: &str = "judge";
+const LOG_FILE: &str = "adjudications.jsonl";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Acceptable,
+    NotAcceptable,
+}
+
+// A play stands or falls as a whole: the verdict never says which word
+// was wrong. Nothing to judge is not acceptable.
+pub fn judge<S: AsRef<str>>(lexicon: &Lexicon, words: &[S]) -> Verdict {
+    if !words.is_empty() && words.iter().all(|word| lexicon.contains(word.as_ref())) {
+        Verdict::Acceptable
+    } else {
+        Verdict::NotAcceptable
+    }
+}
+
+// The words of a play as typed to the judge: letters only, separated by
+// blanks, commas or '+'. None when anything else shows up.
+pub fn parse_words(input: &str) -> Option<Vec<String>> {
+    let words: Vec<String> = input
+        .split(|ch: char| ch.is_whitespace() || ch == ',' || ch == '+')
+        .filter(|word| !word.is_empty())
+        .map(str::to_uppercase)
+        .collect();
+    let letters_only = words.iter().all(|w| w.chars().all(char::is_alphabetic));
+    (!words.is_empty() && letters_only).then_some(words)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Adjudication {
+    pub at: i64,
+    pub words: Vec<String>,
+    pub verdict: Verdict,
+}
+
+// Every adjudication, one JSON object per line, so a disputed ruling can be
+// checked after the round.
+pub struct JudgeLog {
+    path: PathBuf,
+}
+
+impl JudgeLog {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(JUDGE_DIR).join(LOG_FILE),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, adjudication: &Adjudication) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(adjudication)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon() -> Lexicon {
+        Lexicon::from_words(["CHAT", "CHATS", "AS"])
+    }
+
+    #[test]
+    fn a_play_stands_or_falls_as_a_whole() {
+        let lexicon = lexicon();
+        assert_eq!(judge(&lexicon, &["CHAT", "AS"]), Verdict::Acceptable);
+        assert_eq!(judge(&lexicon, &["chats"]), Verdict::Acceptable);
+        assert_eq!(
+            judge(&lexicon, &["CHATS", "ZZ", "AS"]),
+            Verdict::NotAcceptable
+        );
+        assert_eq!(judge::<&str>(&lexicon, &[]), Verdict::NotAcceptable);
+    }
+
+    #[test]
+    fn words_are_split_on_blanks_commas_and_plus() {
+        assert_eq!(
+            parse_words(" chat,as + chats\tzo "),
+            Some(vec![
+                "CHAT".to_string(),
+                "AS".to_string(),
+                "CHATS".to_string(),
+                "ZO".to_string()
+            ])
+        );
+        assert_eq!(parse_words(""), None);
+        assert_eq!(parse_words(" , + "), None);
+        assert_eq!(parse_words("CHAT AS2"), None);
+        assert_eq!(parse_words("CH-AT"), None);
+    }
+
+    #[test]
+    fn the_log_gets_one_json_object_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = JudgeLog::new(dir.path());
+        let rulings = [
+            Adjudication {
+                at: 1,
+                words: vec!["CHAT".to_string(), "AS".to_string()],
+                verdict: Verdict::Acceptable,
+            },
+            Adjudication {
+                at: 2,
+                words: vec!["ZZ".to_string()],
+                verdict: Verdict::NotAcceptable,
+            },
+        ];
+        for ruling in &rulings {
+            log.append(ruling).unwrap();
+        }
+
+        let text = fs::read_to_string(log.path()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(text.ends_with('\n'));
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(
+            first,
+            serde_json::json!({"at": 1, "words": ["CHAT", "AS"], "verdict": "acceptable"})
+        );
+        let second: Adjudication = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second.words, ["ZZ"]);
+        assert_eq!(second.verdict, Verdict::NotAcceptable);
+    }
+}
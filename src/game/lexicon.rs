@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The accepted words. Without one, any word goes and players check each
+// other's words themselves.
+pub struct Lexicon {
+    words: HashSet<String>,
+}
+
+impl Lexicon {
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            words: words
+                .into_iter()
+                .map(|w| w.as_ref().trim().to_uppercase())
+                .filter(|w| !w.is_empty())
+                .collect(),
+        }
+    }
+
+    // One word per line; anything after the first blank (a definition, a
+    // count...) and lines starting with '#' are ignored.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Self::from_words(
+            text.lines()
+                .filter(|line| !line.starts_with('#'))
+                .filter_map(|line| line.split_whitespace().next()),
+        ))
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_uppercase())
+    }
+
+    // In no particular order.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
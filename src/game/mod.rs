@@ -1,15 +1,36 @@
 pub mod action;
+pub mod analysis;
 pub mod bag;
 pub mod board;
+pub mod bot;
+pub mod chat;
+pub mod clock;
+pub mod definitions;
+pub mod integrity;
+pub mod judge;
+pub mod lexicon;
+pub mod movegen;
 pub mod player;
+pub mod rules;
+pub mod selfplay;
+pub mod unseen;
+pub mod view;
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 use self::action::Action;
-use self::bag::Bag;
-use self::board::Board;
+use self::bag::{Bag, Tile, TileSet};
+use self::board::{Board, Direction, MovePlan, Position, Word};
+use self::chat::Chat;
+use self::clock::{Clock, TimeControl};
+use self::lexicon::Lexicon;
 use self::player::Player;
+use self::rules::{ChallengeRule, EndRackScoring, RuleSet};
 use crate::error::MoveError;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum GameEvent {
     TilePlaced {
         row: usize,
@@ -21,6 +42,11 @@ pub enum GameEvent {
         score: u32,
         player_id: usize,
     },
+    MoveScored {
+        player_id: usize,
+        score: u32,
+        bingo: bool,
+    },
     RackUpdated {
         player_id: usize,
         rack: Vec<Option<char>>,
@@ -28,58 +54,878 @@ pub enum GameEvent {
     TurnEnded {
         next_player_id: usize,
     },
+    TilesExchanged {
+        player_id: usize,
+        count: usize,
+    },
+    ChallengeResolved {
+        challenger_id: usize,
+        challenged_id: usize,
+        // The move was taken back; `words` are then the ones not in the
+        // dictionary, otherwise all the words the move formed.
+        upheld: bool,
+        words: Vec<String>,
+        // Lost by the challenger when the challenge failed.
+        penalty: u32,
+        turn_lost: bool,
+    },
+    Resigned {
+        player_id: usize,
+    },
+}
+
+impl GameEvent {
+    // A challenge is settled before the challenger moves: unless it cost
+    // them their turn, they play next as if nothing happened.
+    pub fn keeps_turn(&self) -> bool {
+        matches!(
+            self,
+            GameEvent::ChallengeResolved {
+                turn_lost: false,
+                ..
+            }
+        )
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MoveRecord {
+    pub player_id: usize,
+    pub turn: u32,
+    pub pos: Position,
+    pub dir: Direction,
+    pub words: Vec<(String, u32)>,
+    pub score: u32,
+    pub tiles_placed: usize,
+    pub blanks_played: usize,
+    pub bingo: bool,
+    // The rack before the move, blanks as '_', and the letters put down,
+    // blanks in lower case. Older saves have neither.
+    #[serde(default)]
+    pub rack: Vec<char>,
+    #[serde(default)]
+    pub placed: Vec<(Position, char)>,
+}
+
+// A turn that put nothing on the board, kept so the whole game can be
+// replayed and analysed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScorelessTurn {
+    pub player_id: usize,
+    pub turn: u32,
+    pub rack: Vec<char>,
+    // Moves in the history before this turn: the board it was played on.
+    pub moves_before: usize,
+    pub kind: ScorelessKind,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScorelessKind {
+    Pass,
+    Exchange { letters: Vec<char> },
+    // Taken back after a challenge; `words` are the phonies.
+    Withdrawn { words: Vec<String> },
+    // A failed challenge that cost the turn.
+    ChallengeLost,
+}
+
+// Points won or lost outside a move. With the moves, they add up to the
+// scores.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreAdjustment {
+    pub player_id: usize,
+    pub points: i64,
+    pub reason: AdjustmentReason,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentReason {
+    ChallengePenalty,
+    EndRack,
+    Overtime,
+}
+
+// The last move, kept until the next player moves so it can be challenged
+// and taken back.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LastPlay {
+    pub player_index: usize,
+    pub tiles: Vec<(Position, Tile)>,
+    pub words: Vec<String>,
+    pub score: u32,
+    // Tiles drawn after the move, returned to the bag if it's withdrawn.
+    pub drawn: usize,
+    pub scoreless_before: u32,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Game {
     pub board: Board,
     pub players: Vec<Player>,
     pub bag: Bag,
-    // pub current_player_index: usize,
-    // pub game_over: bool,
+    pub current_player_index: usize,
+    pub turn: u32,
+    pub game_over: bool,
+    #[serde(default)]
+    pub history: Vec<MoveRecord>,
+    #[serde(default)]
+    pub scoreless_history: Vec<ScorelessTurn>,
+    #[serde(default)]
+    pub clock: Option<Clock>,
+    #[serde(default)]
+    pub forfeited_by: Option<usize>,
+    #[serde(default)]
+    pub resigned_by: Option<usize>,
+    #[serde(default)]
+    pub chat: Chat,
+    #[serde(default)]
+    pub tile_set: TileSet,
+    #[serde(default)]
+    pub rules: RuleSet,
+    #[serde(default)]
+    pub scoreless_turns: u32,
+    #[serde(default)]
+    pub last_play: Option<LastPlay>,
+    // None in saves from before adjustments were kept: their scores can't
+    // be checked against the history.
+    #[serde(default)]
+    pub adjustments: Option<Vec<ScoreAdjustment>>,
+    // Not saved: whoever loads the game supplies the word list.
+    #[serde(skip)]
+    pub lexicon: Option<Arc<Lexicon>>,
+}
+
+// How a new game is set up; the default is the classic French game.
+#[derive(Clone, Default)]
+pub struct GameOptions {
+    pub tile_set: TileSet,
+    pub board: Board,
+    pub rules: RuleSet,
+    pub lexicon: Option<Arc<Lexicon>>,
+    // Draws the same tiles every time, for bot games.
+    pub seed: Option<u64>,
+}
+
+// Total for the words formed, plus the bingo bonus when the whole rack went down.
+fn move_score(words: &[(String, u32)], tiles_placed: usize, rules: &RuleSet) -> (u32, bool) {
+    let bingo = tiles_placed == rules.rack_size;
+    let bonus = if bingo { rules.bingo_bonus } else { 0 };
+    (words.iter().map(|(_, s)| s).sum::<u32>() + bonus, bingo)
+}
+
+// Blanks as '_', as racks are written everywhere else.
+fn rack_letters(tiles: &[Tile]) -> Vec<char> {
+    tiles
+        .iter()
+        .map(|tile| if tile.is_blank { '_' } else { tile.letter })
+        .collect()
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Game {
     pub fn new() -> Self {
-        let mut bag = Bag::new();
+        Self::with_options(&GameOptions::default())
+    }
+
+    pub fn with_options(options: &GameOptions) -> Self {
+        let mut bag = match options.seed {
+            Some(seed) => Bag::seeded(options.tile_set, seed),
+            None => Bag::new(options.tile_set),
+        };
         bag.shuffle_bag();
         Self {
-            board: Board::new(),
+            board: options.board.clone(),
             bag,
             players: Vec::new(),
+            current_player_index: 0,
+            turn: 1,
+            game_over: false,
+            history: Vec::new(),
+            scoreless_history: Vec::new(),
+            clock: None,
+            forfeited_by: None,
+            resigned_by: None,
+            chat: Chat::default(),
+            tile_set: options.tile_set,
+            rules: options.rules,
+            scoreless_turns: 0,
+            last_play: None,
+            adjustments: Some(Vec::new()),
+            lexicon: options.lexicon.clone(),
         }
     }
 
+    pub fn set_time_control(&mut self, control: TimeControl) {
+        self.clock = Some(Clock::new(control, self.players.len()));
+    }
+
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.current_player_index]
+    }
+
+    // Debug builds check the whole game after every move, which also
+    // catches the previous turn's draw. A move that leaves it inconsistent
+    // is undone before the error goes back, so nobody plays on from a
+    // state the other side never saw.
     pub fn apply_move(&mut self, action: Action) -> Result<Vec<GameEvent>, MoveError> {
+        if !cfg!(debug_assertions) {
+            return self.apply(action);
+        }
+        let before = self.clone();
+        let result = self.apply(action);
+        if let Err(e) = self.verify_integrity() {
+            *self = before;
+            return Err(MoveError::Integrity(e));
+        }
+        result
+    }
+
+    fn apply(&mut self, action: Action) -> Result<Vec<GameEvent>, MoveError> {
         let mut events = Vec::new();
 
         match action {
-            Action::PlaceWord { pos, dir, word } => {
-                self.board.validate_in_bounds(&pos, &dir, &word)?;
-                self.board.validate_adjacent_tiles(&pos, &dir, &word)?;
-                // self.board
-                //     .validate_player_has_tiles(&self.players[0].rack, &word)?;
-                self.board
-                    .place_word(&mut self.players[0].rack, &pos, &dir, &word)?;
-
-                //events.push(GameEvent::WordPlaced { count });
+            Action::PlaceWord { pos, dir, word } => self.place(pos, dir, word, &mut events)?,
+            Action::ExchangeTiles { letters } => {
+                self.exchange(&letters)?;
+                events.push(GameEvent::TilesExchanged {
+                    player_id: self.current_player().id,
+                    count: letters.len(),
+                });
+            }
+            Action::Challenge => events.push(self.challenge()?),
+            Action::Pass => {
+                self.record_scoreless(ScorelessKind::Pass);
+                self.scoreless_turns += 1;
+                self.last_play = None;
+            }
+            Action::Resign => {
+                let player_id = self.current_player().id;
+                if let Some(clock) = &mut self.clock {
+                    clock.stop();
+                }
+                self.resigned_by = Some(player_id);
+                self.finish();
+                events.push(GameEvent::Resigned { player_id });
             }
-            _ => {}
         }
 
         Ok(events)
     }
 
+    fn place(
+        &mut self,
+        pos: Position,
+        dir: Direction,
+        word: Word,
+        events: &mut Vec<GameEvent>,
+    ) -> Result<(), MoveError> {
+        // Everything is checked before anything changes, so a refused
+        // move leaves the board and the rack as they were.
+        let plan = self.plan_move(&pos, &dir, &word)?;
+        let words = self.planned_words(&plan);
+        self.check_words(&words)?;
+
+        let player = &mut self.players[self.current_player_index];
+        let rack = rack_letters(&player.rack.tiles);
+        self.board.commit(&mut player.rack, &plan);
+        let placed = plan.positions();
+        let (score, bingo) = move_score(&words, placed.len(), &self.rules);
+        player.score += score;
+
+        for p in &placed {
+            events.push(GameEvent::TilePlaced {
+                row: p.row,
+                col: p.col,
+                ch: self.board.cells[p.row][p.col].letter.unwrap_or(' '),
+            });
+        }
+        for (word, word_score) in &words {
+            events.push(GameEvent::WordPlaced {
+                word: word.clone(),
+                score: *word_score,
+                player_id: player.id,
+            });
+        }
+        events.push(GameEvent::MoveScored {
+            player_id: player.id,
+            score,
+            bingo,
+        });
+
+        let played = words.iter().map(|(word, _)| word.clone()).collect();
+        self.history.push(MoveRecord {
+            player_id: player.id,
+            turn: self.turn,
+            pos,
+            dir,
+            words,
+            score,
+            tiles_placed: placed.len(),
+            blanks_played: plan.tiles.iter().filter(|t| t.is_blank).count(),
+            bingo,
+            rack,
+            placed: plan
+                .tiles
+                .iter()
+                .map(|t| {
+                    let letter = if t.is_blank {
+                        t.letter.to_ascii_lowercase()
+                    } else {
+                        t.letter
+                    };
+                    (t.pos, letter)
+                })
+                .collect(),
+        });
+
+        self.last_play = Some(LastPlay {
+            player_index: self.current_player_index,
+            tiles: placed.iter().map(|&p| (p, self.tile_on(p))).collect(),
+            words: played,
+            score,
+            drawn: 0,
+            scoreless_before: self.scoreless_turns,
+        });
+        self.scoreless_turns = 0;
+
+        Ok(())
+    }
+
+    // The rack tile that went on a square: blanks go back as blanks.
+    fn tile_on(&self, pos: Position) -> Tile {
+        let cell = &self.board.cells[pos.row][pos.col];
+        let letter = cell.letter.unwrap_or(' ');
+        if cell.is_blank {
+            Tile {
+                letter: '_',
+                value: 0,
+                is_blank: true,
+            }
+        } else {
+            Tile {
+                letter,
+                value: self.tile_set.letter_value(letter) as u8,
+                is_blank: false,
+            }
+        }
+    }
+
+    // Swaps the given rack tiles for as many from the bag. The new ones are
+    // drawn before the old ones go in, so nobody gets their tiles back.
+    fn exchange(&mut self, letters: &[char]) -> Result<(), MoveError> {
+        if letters.is_empty() {
+            return Err(MoveError::NothingExchanged);
+        }
+        let threshold = self.rules.exchange_threshold.max(letters.len());
+        if self.bag.tiles.len() < threshold {
+            return Err(MoveError::ExchangeNotAllowed {
+                min_in_bag: threshold,
+            });
+        }
+
+        let player = &mut self.players[self.current_player_index];
+        let mut kept = player.rack.tiles.clone();
+        let mut returned = Vec::new();
+        for &letter in letters {
+            let letter = letter.to_ascii_uppercase();
+            let index = kept
+                .iter()
+                .position(|tile| tile.letter == letter)
+                .ok_or(MoveError::MissingLetter { letter })?;
+            returned.push(kept.remove(index));
+        }
+        self.record_scoreless(ScorelessKind::Exchange {
+            letters: rack_letters(&returned),
+        });
+
+        let player = &mut self.players[self.current_player_index];
+
+        kept.extend(self.bag.draw_n(returned.len()));
+        player.rack.tiles = kept;
+        self.bag.tiles.extend(returned);
+        self.bag.shuffle_bag();
+
+        self.scoreless_turns += 1;
+        self.last_play = None;
+        Ok(())
+    }
+
+    // The current player challenges the previous move. Under single and
+    // double challenge words aren't checked when played, only here.
+    fn challenge(&mut self) -> Result<GameEvent, MoveError> {
+        if self.rules.challenge == ChallengeRule::Void {
+            return Err(MoveError::ChallengeNotAllowed);
+        }
+        let lexicon = self.lexicon.clone().ok_or(MoveError::NoDictionary)?;
+        let play = self
+            .last_play
+            .take_if(|play| play.player_index != self.current_player_index)
+            .ok_or(MoveError::NothingToChallenge)?;
+
+        let challenger_id = self.current_player().id;
+        let challenged_id = self.players[play.player_index].id;
+        let phonies: Vec<String> = play
+            .words
+            .iter()
+            .filter(|word| !lexicon.contains(word))
+            .cloned()
+            .collect();
+
+        if !phonies.is_empty() {
+            self.withdraw(play, phonies.clone());
+            return Ok(GameEvent::ChallengeResolved {
+                challenger_id,
+                challenged_id,
+                upheld: true,
+                words: phonies,
+                penalty: 0,
+                turn_lost: false,
+            });
+        }
+
+        let penalty = self.rules.challenge_penalty;
+        self.adjust_score(
+            self.current_player_index,
+            -(penalty as i64),
+            AdjustmentReason::ChallengePenalty,
+        );
+        let turn_lost = self.rules.challenge == ChallengeRule::Double;
+        if turn_lost {
+            self.record_scoreless(ScorelessKind::ChallengeLost);
+            self.scoreless_turns += 1;
+        }
+        Ok(GameEvent::ChallengeResolved {
+            challenger_id,
+            challenged_id,
+            upheld: false,
+            words: play.words,
+            penalty,
+            turn_lost,
+        })
+    }
+
+    // Takes a challenged move off the board: tiles back on the rack, the
+    // replacements back in the bag, the score and the history entry gone.
+    fn withdraw(&mut self, play: LastPlay, phonies: Vec<String>) {
+        let player = &mut self.players[play.player_index];
+        let keep = player.rack.tiles.len().saturating_sub(play.drawn);
+        let drawn = player.rack.tiles.split_off(keep);
+        self.bag.tiles.extend(drawn);
+        self.bag.shuffle_bag();
+
+        for (pos, tile) in play.tiles {
+            let cell = &mut self.board.cells[pos.row][pos.col];
+            cell.letter = None;
+            cell.is_blank = false;
+            player.rack.tiles.push(tile);
+        }
+        player.score = player.score.saturating_sub(play.score);
+        if let Some(record) = self.history.pop() {
+            self.scoreless_history.push(ScorelessTurn {
+                player_id: record.player_id,
+                turn: record.turn,
+                rack: record.rack,
+                moves_before: self.history.len(),
+                kind: ScorelessKind::Withdrawn { words: phonies },
+            });
+        }
+        // The withdrawn move counts as a scoreless turn.
+        self.scoreless_turns = play.scoreless_before + 1;
+    }
+
+    fn record_scoreless(&mut self, kind: ScorelessKind) {
+        let player = self.current_player();
+        self.scoreless_history.push(ScorelessTurn {
+            player_id: player.id,
+            turn: self.turn,
+            rack: rack_letters(&player.rack.tiles),
+            moves_before: self.history.len(),
+            kind,
+        });
+    }
+
+    // The current player's move worked out against the board and rack as
+    // they are, without playing it.
+    pub fn plan_move(
+        &self,
+        pos: &Position,
+        dir: &Direction,
+        word: &Word,
+    ) -> Result<MovePlan, MoveError> {
+        if word.tiles.len() < self.rules.min_word_length {
+            return Err(MoveError::WordTooShort {
+                min: self.rules.min_word_length,
+            });
+        }
+        self.board.plan_move(
+            &self.current_player().rack,
+            pos,
+            dir,
+            word,
+            self.rules.first_move,
+        )
+    }
+
+    // The words a planned move makes, scored.
+    fn planned_words(&self, plan: &MovePlan) -> Vec<(String, u32)> {
+        self.board.with_plan(plan).words_formed(
+            &plan.pos,
+            &plan.dir,
+            &plan.positions(),
+            self.tile_set,
+        )
+    }
+
+    // Scores a word for the current player without playing it, e.g. while
+    // the move is still being laid out on the board.
+    pub fn preview_move(
+        &self,
+        pos: &Position,
+        dir: &Direction,
+        word: &Word,
+    ) -> Result<u32, MoveError> {
+        let plan = self.plan_move(pos, dir, word)?;
+        let words = self.planned_words(&plan);
+        self.check_words(&words)?;
+        Ok(move_score(&words, plan.tiles.len(), &self.rules).0)
+    }
+
+    // Every word the move forms must be long enough. Phonies are refused
+    // only under void challenge: otherwise they stand until challenged.
+    fn check_words(&self, words: &[(String, u32)]) -> Result<(), MoveError> {
+        let min = self.rules.min_word_length;
+        if words.iter().any(|(w, _)| w.chars().count() < min) {
+            return Err(MoveError::WordTooShort { min });
+        }
+        match &self.lexicon {
+            Some(lexicon)
+                if self.rules.challenge == ChallengeRule::Void
+                    && words.iter().any(|(w, _)| !lexicon.contains(w)) =>
+            {
+                Err(MoveError::InvalidWord)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn overtime_penalty(&self, player_index: usize) -> u32 {
+        self.clock
+            .as_ref()
+            .map_or(0, |clock| clock.overtime_minutes(player_index))
+            * self.rules.overtime_penalty
+    }
+
     pub fn add_player(&mut self, name: String) {
         let id = self.players.len() + 1;
         let mut player = Player::new(id, name);
-        player.rack.top_up(&mut self.bag);
+        player.rack.top_up(&mut self.bag, self.rules.rack_size);
         self.players.push(player);
+        if let Some(clock) = &mut self.clock {
+            clock.add_player();
+        }
+    }
+
+    // See `Clock::charge`.
+    pub fn charge_clock(&mut self) {
+        if let Some(clock) = &mut self.clock {
+            clock.charge();
+        }
+    }
+
+    pub fn start_turn(&mut self) {
+        if let Some(clock) = &mut self.clock {
+            clock.start(self.current_player_index);
+        }
+    }
+
+    // With forfeit enabled, a move submitted after the flag fell is not
+    // played: the game ends on the spot.
+    pub fn check_timeout(&mut self) -> bool {
+        let Some(clock) = &mut self.clock else {
+            return false;
+        };
+        if !clock.control.forfeit_on_timeout || !clock.is_flagged(self.current_player_index) {
+            return false;
+        }
+
+        clock.stop();
+        self.forfeited_by = Some(self.players[self.current_player_index].id);
+        self.finish();
+        true
+    }
+
+    pub fn end_turn(&mut self) {
+        if let Some(clock) = &mut self.clock {
+            clock.stop();
+        }
+        // A resignation has already ended the game.
+        if self.game_over {
+            return;
+        }
+
+        let player = &mut self.players[self.current_player_index];
+        let drawn = player.rack.top_up(&mut self.bag, self.rules.rack_size);
+        if let Some(play) = &mut self.last_play
+            && play.player_index == self.current_player_index
+        {
+            play.drawn = drawn;
+        }
+
+        let limit = self.rules.scoreless_turn_limit;
+        if player.rack.tiles.is_empty() || (limit > 0 && self.scoreless_turns >= limit) {
+            self.finish();
+        } else {
+            self.current_player_index = (self.current_player_index + 1) % self.players.len();
+            self.turn += 1;
+        }
+    }
+
+    fn finish(&mut self) {
+        self.game_over = true;
+        self.last_play = None;
+
+        if self.rules.end_rack != EndRackScoring::Ignore {
+            let left: u32 = self.players.iter().map(|p| p.rack.value()).sum();
+            for i in 0..self.players.len() {
+                let rack = &self.players[i].rack;
+                let points = if !rack.tiles.is_empty() {
+                    -(rack.value() as i64)
+                } else if self.rules.end_rack == EndRackScoring::Standard {
+                    left as i64
+                } else {
+                    0
+                };
+                self.adjust_score(i, points, AdjustmentReason::EndRack);
+            }
+        }
+
+        for i in 0..self.players.len() {
+            let penalty = self.overtime_penalty(i);
+            self.adjust_score(i, -(penalty as i64), AdjustmentReason::Overtime);
+        }
+    }
+
+    // Scores never go below zero; what was actually added or taken off
+    // is written down.
+    fn adjust_score(&mut self, index: usize, points: i64, reason: AdjustmentReason) {
+        let player = &mut self.players[index];
+        let before = player.score as i64;
+        player.score = (before + points).max(0) as u32;
+        let points = player.score as i64 - before;
+        if points != 0
+            && let Some(adjustments) = &mut self.adjustments
+        {
+            adjustments.push(ScoreAdjustment {
+                player_id: player.id,
+                points,
+                reason,
+            });
+        }
+    }
+
+    // Scores used to decide the result: a forfeit or a resignation counts
+    // as scoring nothing.
+    pub fn result_scores(&self) -> Vec<u32> {
+        self.players
+            .iter()
+            .map(|p| {
+                if self.forfeited_by == Some(p.id) || self.resigned_by == Some(p.id) {
+                    0
+                } else {
+                    p.score
+                }
+            })
+            .collect()
     }
 }
 
-// on_play_button_click(word_input) {
-//     let action = Action::PlaceWord { row, col, dir, word_input };
-//     game.apply(action);           // Mutate model
-//     let view = game.view();       // Snapshot view
-//     ui.render(view);              // Draw on screen
-//}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Everything a refused move must leave as it was.
+    type Snapshot = (Vec<(Option<char>, bool)>, Vec<Tile>, Vec<Tile>, u32);
+
+    fn snapshot(game: &Game) -> Snapshot {
+        let cells = game
+            .board
+            .cells
+            .iter()
+            .flatten()
+            .map(|cell| (cell.letter, cell.is_blank))
+            .collect();
+        let player = game.current_player();
+        (
+            cells,
+            player.rack.tiles.clone(),
+            game.bag.tiles.clone(),
+            player.score,
+        )
+    }
+
+    fn word(letters: &[char]) -> Word {
+        Word {
+            tiles: letters
+                .iter()
+                .map(|&letter| Tile {
+                    letter,
+                    value: 0,
+                    is_blank: false,
+                })
+                .collect(),
+        }
+    }
+
+    fn rack_letters(game: &Game) -> Vec<char> {
+        let rack = &game.current_player().rack;
+        rack.tiles
+            .iter()
+            .filter(|t| !t.is_blank)
+            .map(|t| t.letter)
+            .collect()
+    }
+
+    // Ann's three letters across from h8, Bob to play. Without a word
+    // list any letters make a word.
+    fn second_move() -> Game {
+        let mut game = Game::with_options(&GameOptions {
+            seed: Some(3),
+            ..GameOptions::default()
+        });
+        game.add_player("Ann".to_string());
+        game.add_player("Bob".to_string());
+        let letters = rack_letters(&game);
+        game.apply_move(Action::PlaceWord {
+            pos: game.rules.first_move,
+            dir: Direction::Across,
+            word: word(&letters[..3]),
+        })
+        .unwrap();
+        game.end_turn();
+        game
+    }
+
+    #[test]
+    fn a_mismatch_past_the_first_letters_changes_nothing() {
+        let mut game = second_move();
+        let on_board = game.board.cells[7][8].letter.unwrap();
+        let mine = rack_letters(&game);
+        let wrong = if on_board == 'Z' { 'Y' } else { 'Z' };
+        let before = snapshot(&game);
+
+        // Down through i8: two rack tiles, then a letter that isn't there.
+        let result = game.apply_move(Action::PlaceWord {
+            pos: Position { row: 5, col: 8 },
+            dir: Direction::Down,
+            word: word(&[mine[0], mine[1], wrong, mine[2]]),
+        });
+        assert_eq!(
+            result.err(),
+            Some(MoveError::LetterMismatch { row: 7, col: 8 })
+        );
+        assert_eq!(snapshot(&game), before);
+        assert_eq!(game.current_player().name, "Bob");
+    }
+
+    #[test]
+    fn a_missing_letter_past_the_first_letters_changes_nothing() {
+        let mut game = second_move();
+        let on_board = game.board.cells[7][8].letter.unwrap();
+        let mine = rack_letters(&game);
+        let absent = ('A'..='Z').find(|c| !mine.contains(c)).unwrap();
+        // Any blank on the rack stands in for the first few.
+        let blanks = game.current_player().rack.tiles.len() - mine.len();
+        let mut letters = vec![mine[0], mine[1], on_board];
+        letters.extend(std::iter::repeat_n(absent, blanks + 1));
+        let before = snapshot(&game);
+
+        let result = game.apply_move(Action::PlaceWord {
+            pos: Position { row: 5, col: 8 },
+            dir: Direction::Down,
+            word: word(&letters),
+        });
+        assert_eq!(
+            result.err(),
+            Some(MoveError::MissingLetter { letter: absent })
+        );
+        assert_eq!(snapshot(&game), before);
+
+        // The same tiles still make a move.
+        letters.truncate(3);
+        game.apply_move(Action::PlaceWord {
+            pos: Position { row: 5, col: 8 },
+            dir: Direction::Down,
+            word: word(&letters),
+        })
+        .unwrap();
+        assert_eq!(game.board.cells[5][8].letter, Some(mine[0]));
+    }
+
+    #[test]
+    fn overtime_costs_points_per_started_minute() {
+        let mut game = second_move();
+        game.set_time_control(TimeControl::parse("1").unwrap());
+        let clock = game.clock.as_mut().unwrap();
+        clock.remaining_ms = vec![-61_000, 5_000];
+
+        assert_eq!(game.rules.overtime_penalty, 10);
+        assert_eq!(game.overtime_penalty(0), 20);
+        assert_eq!(game.overtime_penalty(1), 0);
+    }
+
+    #[test]
+    fn every_word_formed_must_be_long_enough() {
+        let mut game = second_move();
+        game.rules.min_word_length = 3;
+        let letters = rack_letters(&game);
+        let before = snapshot(&game);
+
+        // Three letters under Ann's word: each makes a two-letter word down.
+        let result = game.apply_move(Action::PlaceWord {
+            pos: Position { row: 8, col: 7 },
+            dir: Direction::Across,
+            word: word(&letters[..3]),
+        });
+        assert_eq!(result.err(), Some(MoveError::WordTooShort { min: 3 }));
+        assert_eq!(snapshot(&game), before);
+
+        // Down from the end of it, the word across grows to four letters.
+        game.apply_move(Action::PlaceWord {
+            pos: Position { row: 7, col: 10 },
+            dir: Direction::Down,
+            word: word(&letters[..3]),
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn resigning_ends_the_game_as_a_loss() {
+        let mut game = second_move();
+        let bob = game.current_player().id;
+        let rack = game.current_player().rack.tiles.clone();
+
+        let events = game.apply_move(Action::Resign).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [GameEvent::Resigned { player_id }] if *player_id == bob
+        ));
+        assert!(game.game_over);
+        assert_eq!(game.resigned_by, Some(bob));
+        assert_eq!(game.result_scores()[1], 0);
+        assert_eq!(game.result_scores()[0], game.players[0].score);
+
+        // The turn doesn't move on and nothing is drawn.
+        let turn = game.turn;
+        game.end_turn();
+        assert_eq!(game.turn, turn);
+        assert_eq!(game.current_player().id, bob);
+        assert_eq!(game.current_player().rack.tiles, rack);
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::HashSet;
+
+use super::bag::TileSet;
+use super::board::{BOARD_SIZE, Board, Direction, Position};
+use super::lexicon::Lexicon;
+use super::move_score;
+use super::rules::RuleSet;
+use super::unseen::BLANK;
+
+const NONE: u32 = u32::MAX;
+const LETTERS: usize = 26;
+
+// The word list as a letter tree, so words can be grown a letter at a
+// time. Nodes sit in flat arrays, children chained as siblings: a big
+// list stays a few tens of megabytes.
+pub struct Trie {
+    letters: Vec<u8>,
+    first_child: Vec<u32>,
+    next_sibling: Vec<u32>,
+    terminal: Vec<bool>,
+}
+
+impl Trie {
+    // Words with anything but A to Z are left out: no tile plays them.
+    pub fn new(lexicon: &Lexicon) -> Self {
+        let mut trie = Self {
+            letters: vec![0],
+            first_child: vec![NONE],
+            next_sibling: vec![NONE],
+            terminal: vec![false],
+        };
+        for word in lexicon.words() {
+            if word.bytes().all(|b| b.is_ascii_uppercase()) {
+                trie.insert(word.as_bytes());
+            }
+        }
+        trie
+    }
+
+    fn insert(&mut self, word: &[u8]) {
+        let mut node = 0;
+        for &letter in word {
+            node = match self.child(node, letter) {
+                Some(child) => child,
+                None => {
+                    let child = self.letters.len() as u32;
+                    self.letters.push(letter);
+                    self.first_child.push(NONE);
+                    self.next_sibling.push(self.first_child[node as usize]);
+                    self.terminal.push(false);
+                    self.first_child[node as usize] = child;
+                    child
+                }
+            };
+        }
+        self.terminal[node as usize] = true;
+    }
+
+    fn child(&self, node: u32, letter: u8) -> Option<u32> {
+        let mut child = self.first_child[node as usize];
+        while child != NONE {
+            if self.letters[child as usize] == letter {
+                return Some(child);
+            }
+            child = self.next_sibling[child as usize];
+        }
+        None
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        let mut node = 0;
+        for letter in word.bytes() {
+            match self.child(node, letter.to_ascii_uppercase()) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        self.terminal[node as usize]
+    }
+}
+
+// A legal move: the tiles it puts down and what it scores.
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    // Where the main word starts, as typed: `h8 h CHAT`.
+    pub pos: Position,
+    pub dir: Direction,
+    // The main word, blanks in lower case.
+    pub word: String,
+    // Blanks in lower case.
+    pub placed: Vec<(Position, char)>,
+    pub words: Vec<(String, u32)>,
+    pub score: u32,
+    pub bingo: bool,
+}
+
+impl Candidate {
+    // The rack tiles it uses, blanks as '_'.
+    pub fn tiles(&self) -> impl Iterator<Item = char> + '_ {
+        self.placed.iter().map(|&(_, letter)| rack_tile(letter))
+    }
+}
+
+// The rack tile behind a letter on the board: blanks are in lower case.
+pub fn rack_tile(letter: char) -> char {
+    if letter.is_lowercase() { BLANK } else { letter }
+}
+
+fn letter_index(letter: char) -> Option<usize> {
+    letter
+        .is_ascii_uppercase()
+        .then(|| letter as usize - 'A' as usize)
+}
+
+fn index_letter(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+// The square at `i` along line `line`.
+fn square(dir: Direction, line: usize, i: usize) -> Position {
+    match dir {
+        Direction::Across => Position { row: line, col: i },
+        Direction::Down => Position { row: i, col: line },
+    }
+}
+
+fn letter_at(board: &Board, row: isize, col: isize) -> Option<char> {
+    if row < 0 || col < 0 || row >= BOARD_SIZE as isize || col >= BOARD_SIZE as isize {
+        return None;
+    }
+    board.cells[row as usize][col as usize].letter
+}
+
+// One line of the board, as the generator walks it.
+struct Line {
+    letters: [Option<char>; BOARD_SIZE],
+    // Letters allowed by the word across the line, as a bit set; None
+    // when nothing touches the square that way.
+    cross: [Option<u32>; BOARD_SIZE],
+    // Empty squares a move has to cover one of to be connected.
+    anchor: [bool; BOARD_SIZE],
+}
+
+struct Search<'a> {
+    trie: &'a Trie,
+    line: Line,
+    dir: Direction,
+    index: usize,
+    min_length: usize,
+    rack: [u8; LETTERS + 1],
+    placed: Vec<(usize, char)>,
+    found: Vec<(usize, Vec<(usize, char)>)>,
+}
+
+impl Search<'_> {
+    fn walk(&mut self, start: usize, i: usize, node: u32, connected: bool) {
+        if i > start
+            && !self.placed.is_empty()
+            && connected
+            && self.trie.terminal[node as usize]
+            && i - start >= self.min_length
+            && (i == BOARD_SIZE || self.line.letters[i].is_none())
+        {
+            self.found.push((start, self.placed.clone()));
+        }
+        if i == BOARD_SIZE {
+            return;
+        }
+
+        if let Some(letter) = self.line.letters[i] {
+            if let Some(child) = self.trie.child(node, letter as u8) {
+                self.walk(start, i + 1, child, connected);
+            }
+            return;
+        }
+
+        let allowed = self.line.cross[i].unwrap_or(u32::MAX);
+        let connected = connected || self.line.anchor[i];
+        for index in 0..LETTERS {
+            if allowed & (1 << index) == 0 {
+                continue;
+            }
+            let letter = index_letter(index);
+            let Some(child) = self.trie.child(node, letter as u8) else {
+                continue;
+            };
+            if self.rack[index] > 0 {
+                self.rack[index] -= 1;
+                self.placed.push((i, letter));
+                self.walk(start, i + 1, child, connected);
+                self.placed.pop();
+                self.rack[index] += 1;
+            }
+            if self.rack[LETTERS] > 0 {
+                self.rack[LETTERS] -= 1;
+                self.placed.push((i, letter.to_ascii_lowercase()));
+                self.walk(start, i + 1, child, connected);
+                self.placed.pop();
+                self.rack[LETTERS] += 1;
+            }
+        }
+    }
+}
+
+fn read_line(
+    board: &Board,
+    trie: &Trie,
+    dir: Direction,
+    index: usize,
+    first_move: Option<Position>,
+    min_length: usize,
+) -> Line {
+    let mut line = Line {
+        letters: [None; BOARD_SIZE],
+        cross: [None; BOARD_SIZE],
+        anchor: [false; BOARD_SIZE],
+    };
+    // Steps along the line and across it.
+    let (along, across): ((isize, isize), (isize, isize)) = match dir {
+        Direction::Across => ((0, 1), (1, 0)),
+        Direction::Down => ((1, 0), (0, 1)),
+    };
+
+    for i in 0..BOARD_SIZE {
+        let pos = square(dir, index, i);
+        let (row, col) = (pos.row as isize, pos.col as isize);
+        line.letters[i] = board.cells[pos.row][pos.col].letter;
+        if line.letters[i].is_some() {
+            continue;
+        }
+
+        line.anchor[i] = match first_move {
+            Some(start) => start == pos,
+            None => [along, across].iter().any(|&(dr, dc)| {
+                letter_at(board, row - dr, col - dc).is_some()
+                    || letter_at(board, row + dr, col + dc).is_some()
+            }),
+        };
+
+        let mut before = String::new();
+        let mut r = row - across.0;
+        let mut c = col - across.1;
+        while let Some(letter) = letter_at(board, r, c) {
+            before.insert(0, letter);
+            r -= across.0;
+            c -= across.1;
+        }
+        let mut after = String::new();
+        let mut r = row + across.0;
+        let mut c = col + across.1;
+        while let Some(letter) = letter_at(board, r, c) {
+            after.push(letter);
+            r += across.0;
+            c += across.1;
+        }
+        if before.is_empty() && after.is_empty() {
+            continue;
+        }
+        // A word across that would be too short rules the square out.
+        let mut allowed = 0;
+        if before.len() + 1 + after.len() >= min_length {
+            for index in 0..LETTERS {
+                let word = format!("{}{}{}", before, index_letter(index), after);
+                if trie.contains(&word) {
+                    allowed |= 1 << index;
+                }
+            }
+        }
+        line.cross[i] = Some(allowed);
+    }
+    line
+}
+
+// Every move the rack can play on the board, scored. `rack` has blanks
+// as '_'.
+pub fn generate(
+    board: &Board,
+    rack: &[char],
+    trie: &Trie,
+    rules: &RuleSet,
+    tile_set: TileSet,
+) -> Vec<Candidate> {
+    let mut counts = [0u8; LETTERS + 1];
+    for &letter in rack {
+        if letter == BLANK {
+            counts[LETTERS] += 1;
+        } else if let Some(index) = letter_index(letter.to_ascii_uppercase()) {
+            counts[index] += 1;
+        }
+    }
+    let tiles = counts.iter().map(|&c| c as usize).sum::<usize>();
+    let empty = board.cells.iter().flatten().all(|c| c.letter.is_none());
+    let first_move = empty.then_some(rules.first_move);
+
+    let mut candidates = Vec::new();
+    let mut seen = HashSet::new();
+    let min_length = rules.min_word_length.max(2);
+    for dir in [Direction::Across, Direction::Down] {
+        for index in 0..BOARD_SIZE {
+            let line = read_line(board, trie, dir, index, first_move, min_length);
+            if !line.anchor.contains(&true) {
+                continue;
+            }
+            let mut search = Search {
+                trie,
+                line,
+                dir,
+                index,
+                min_length,
+                rack: counts,
+                placed: Vec::new(),
+                found: Vec::new(),
+            };
+            for start in 0..BOARD_SIZE {
+                if start > 0 && search.line.letters[start - 1].is_some() {
+                    continue;
+                }
+                // Too far from any anchor for the tiles to reach it.
+                let Some(anchor) = (start..BOARD_SIZE).find(|&i| search.line.anchor[i]) else {
+                    break;
+                };
+                let gaps = (start..=anchor)
+                    .filter(|&i| search.line.letters[i].is_none())
+                    .count();
+                if gaps > tiles {
+                    continue;
+                }
+                search.walk(start, start, 0, false);
+            }
+
+            for (start, placed) in search.found {
+                let mut key: Vec<(Position, char)> = placed
+                    .iter()
+                    .map(|&(i, letter)| (square(dir, index, i), letter))
+                    .collect();
+                key.sort();
+                // A single tile makes the same move both ways.
+                if !seen.insert(key) {
+                    continue;
+                }
+                candidates.push(score(
+                    board,
+                    square(search.dir, search.index, start),
+                    dir,
+                    &placed,
+                    rules,
+                    tile_set,
+                ));
+            }
+        }
+    }
+    candidates
+}
+
+fn score(
+    board: &Board,
+    pos: Position,
+    dir: Direction,
+    placed: &[(usize, char)],
+    rules: &RuleSet,
+    tile_set: TileSet,
+) -> Candidate {
+    let mut board = board.clone();
+    let line = match dir {
+        Direction::Across => pos.row,
+        Direction::Down => pos.col,
+    };
+    let placed: Vec<(Position, char)> = placed
+        .iter()
+        .map(|&(i, letter)| (square(dir, line, i), letter))
+        .collect();
+    for &(at, letter) in &placed {
+        let cell = &mut board.cells[at.row][at.col];
+        cell.letter = Some(letter.to_ascii_uppercase());
+        cell.is_blank = letter.is_lowercase();
+    }
+
+    let positions: Vec<Position> = placed.iter().map(|&(at, _)| at).collect();
+    let words = board.words_formed(&pos, &dir, &positions, tile_set);
+    let (score, bingo) = move_score(&words, placed.len(), rules);
+
+    let mut word = String::new();
+    let mut i = 0;
+    loop {
+        let at = match dir {
+            Direction::Across => Position {
+                row: pos.row,
+                col: pos.col + i,
+            },
+            Direction::Down => Position {
+                row: pos.row + i,
+                col: pos.col,
+            },
+        };
+        if at.row >= BOARD_SIZE || at.col >= BOARD_SIZE {
+            break;
+        }
+        let cell = &board.cells[at.row][at.col];
+        let Some(letter) = cell.letter else { break };
+        word.push(if cell.is_blank {
+            letter.to_ascii_lowercase()
+        } else {
+            letter
+        });
+        i += 1;
+    }
+
+    Candidate {
+        pos,
+        dir,
+        word,
+        placed,
+        words,
+        score,
+        bingo,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie(words: &[&str]) -> Trie {
+        Trie::new(&Lexicon::from_words(words))
+    }
+
+    fn moves(board: &Board, rack: &str, words: &[&str]) -> Vec<Candidate> {
+        let rack: Vec<char> = rack.chars().collect();
+        generate(
+            board,
+            &rack,
+            &trie(words),
+            &RuleSet::default(),
+            TileSet::English,
+        )
+    }
+
+    fn lay(board: &mut Board, row: usize, col: usize, word: &str) {
+        for (i, letter) in word.chars().enumerate() {
+            board.cells[row][col + i].letter = Some(letter);
+        }
+    }
+
+    fn at(row: usize, col: usize) -> Position {
+        Position { row, col }
+    }
+
+    #[test]
+    fn trie_holds_whole_words_only() {
+        let trie = trie(&["CHAT", "CHATS", "L'EAU"]);
+        assert!(trie.contains("CHAT"));
+        assert!(trie.contains("chats"));
+        assert!(!trie.contains("CHA"));
+        assert!(!trie.contains("CHATTE"));
+        assert!(!trie.contains("L'EAU"));
+    }
+
+    #[test]
+    fn first_move_goes_through_the_centre() {
+        let found = moves(&Board::new(), "CAT", &["CAT", "AT"]);
+        let centre = RuleSet::default().first_move;
+
+        assert!(!found.is_empty());
+        assert!(
+            found
+                .iter()
+                .all(|c| c.placed.iter().any(|&(pos, _)| pos == centre))
+        );
+        // Across and down, at every start that reaches h8.
+        let cat: Vec<_> = found.iter().filter(|c| c.word == "CAT").collect();
+        assert_eq!(cat.len(), 6);
+        assert!(found.iter().any(|c| c.word == "AT"));
+    }
+
+    #[test]
+    fn cross_words_must_be_words() {
+        let mut board = Board::new();
+        lay(&mut board, 7, 7, "AT");
+        let found = moves(&board, "CS", &["AT", "CAT", "AS"]);
+
+        // S under the A makes AS; under the T it would make TS.
+        assert!(found.iter().any(|c| c.placed == [(at(8, 7), 'S')]));
+        assert!(
+            found
+                .iter()
+                .all(|c| c.placed.iter().all(|&(pos, _)| pos != at(8, 8)))
+        );
+        let cat = found.iter().find(|c| c.word == "CAT").unwrap();
+        assert_eq!(cat.placed, [(at(7, 6), 'C')]);
+        assert_eq!(cat.words, [("CAT".to_string(), 5)]);
+    }
+
+    #[test]
+    fn a_blank_plays_any_letter_for_nothing() {
+        let found = moves(&Board::new(), "CA_", &["CAT"]);
+        let cat = found
+            .iter()
+            .find(|c| c.pos == at(7, 7) && c.dir == Direction::Across)
+            .unwrap();
+
+        assert_eq!(cat.word, "CAt");
+        assert_eq!(cat.placed[2], (at(7, 9), 't'));
+        assert_eq!(cat.tiles().collect::<String>(), "CA_");
+        // C and A on the double word square, the blank worth nothing.
+        assert_eq!(cat.score, 8);
+    }
+}
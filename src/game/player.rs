@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use super::bag::{Bag, Tile};
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Player {
     pub id: usize,
     pub name: String,
@@ -10,31 +13,42 @@ pub struct Player {
 impl Player {
     pub fn new(id: usize, name: String) -> Self {
         Self {
-            id: id,
-            name: name,
+            id,
+            name,
             rack: Rack::new(),
             score: 0,
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Rack {
     pub tiles: Vec<Tile>,
 }
 
-impl Rack {
-    pub const CAPACITY: usize = 7;
+impl Default for Rack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+impl Rack {
     pub fn new() -> Self {
-        Self {
-            tiles: Vec::with_capacity(Self::CAPACITY),
-        }
+        Self { tiles: Vec::new() }
     }
 
-    pub fn top_up(&mut self, bag: &mut Bag) {
-        let need = Self::CAPACITY.saturating_sub(self.tiles.len());
+    // Draws until the rack holds `size` tiles or the bag is empty; returns
+    // how many were drawn.
+    pub fn top_up(&mut self, bag: &mut Bag, size: usize) -> usize {
+        let need = size.saturating_sub(self.tiles.len());
         let drawn = bag.draw_n(need);
+        let count = drawn.len();
         self.tiles.extend(drawn);
+        count
+    }
+
+    // What the tiles left count for at the end of the game.
+    pub fn value(&self) -> u32 {
+        self.tiles.iter().map(|tile| tile.value as u32).sum()
     }
 }
@@ -0,0 +1,159 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::board::{BOARD_SIZE, Position};
+
+pub const RACK_SIZE: usize = 7;
+pub const BINGO_BONUS: u32 = 50;
+pub const MIN_WORD_LENGTH: usize = 2;
+// Fewer tiles than this in the bag and exchanges are refused.
+pub const EXCHANGE_THRESHOLD: usize = 7;
+// Passes, exchanges and withdrawn moves in a row before the game stops.
+pub const SCORELESS_TURN_LIMIT: u32 = 6;
+pub const OVERTIME_PENALTY: u32 = 10;
+pub const CENTRE: Position = Position {
+    row: BOARD_SIZE / 2,
+    col: BOARD_SIZE / 2,
+};
+
+// What happens to words that aren't in the dictionary.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChallengeRule {
+    // Refused as they are played; nothing to challenge.
+    #[default]
+    Void,
+    // Played as is; a challenged phony is taken back, a failed challenge
+    // costs only the challenge penalty.
+    Single,
+    // As single, but a failed challenge also loses the challenger's turn.
+    Double,
+}
+
+// How the tiles left on the racks count at the end.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum EndRackScoring {
+    // Everyone loses what they hold; whoever went out also gains it all.
+    #[default]
+    Standard,
+    // Everyone loses what they hold, nobody gains it.
+    Deduct,
+    // Racks don't count.
+    Ignore,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum RulePreset {
+    // Double challenge, racks counted, game stopped after six scoreless turns.
+    Tournament,
+    // Single challenge without penalty.
+    Casual,
+    // No challenges, exchanges any time, no end-of-game arithmetic.
+    Family,
+}
+
+impl RulePreset {
+    pub const ALL: [RulePreset; 3] = [
+        RulePreset::Tournament,
+        RulePreset::Casual,
+        RulePreset::Family,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RulePreset::Tournament => "tournament",
+            RulePreset::Casual => "casual",
+            RulePreset::Family => "family",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+// Rules that vary between clubs and variants. Stored with the game so a
+// save keeps the rules it was started with.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSet {
+    // Tiles on a full rack, 8 in Lexulous-style games.
+    pub rack_size: usize,
+    // Added when a move uses the whole rack.
+    pub bingo_bonus: u32,
+    // The square the first word has to cover.
+    #[serde(with = "square")]
+    pub first_move: Position,
+    pub min_word_length: usize,
+    pub challenge: ChallengeRule,
+    // Lost by a player whose challenge fails.
+    pub challenge_penalty: u32,
+    // Tiles needed in the bag to exchange.
+    pub exchange_threshold: usize,
+    // 0 for no limit.
+    pub scoreless_turn_limit: u32,
+    pub end_rack: EndRackScoring,
+    // Lost per started minute past the clock.
+    pub overtime_penalty: u32,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            rack_size: RACK_SIZE,
+            bingo_bonus: BINGO_BONUS,
+            first_move: CENTRE,
+            min_word_length: MIN_WORD_LENGTH,
+            challenge: ChallengeRule::Void,
+            challenge_penalty: 0,
+            exchange_threshold: EXCHANGE_THRESHOLD,
+            scoreless_turn_limit: SCORELESS_TURN_LIMIT,
+            end_rack: EndRackScoring::Standard,
+            overtime_penalty: OVERTIME_PENALTY,
+        }
+    }
+}
+
+impl RuleSet {
+    pub fn preset(preset: RulePreset) -> Self {
+        match preset {
+            RulePreset::Tournament => Self {
+                challenge: ChallengeRule::Double,
+                ..Self::default()
+            },
+            RulePreset::Casual => Self {
+                challenge: ChallengeRule::Single,
+                overtime_penalty: 0,
+                ..Self::default()
+            },
+            RulePreset::Family => Self {
+                exchange_threshold: 1,
+                scoreless_turn_limit: 0,
+                end_rack: EndRackScoring::Ignore,
+                overtime_penalty: 0,
+                ..Self::default()
+            },
+        }
+    }
+}
+
+// The first-move square as players write it, "h8".
+mod square {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    use crate::game::board::Position;
+
+    pub fn serialize<S: Serializer>(pos: &Position, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&pos.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Position, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Position::parse(&text)
+            .ok_or_else(|| de::Error::custom(format!("invalid square '{}' (e.g. h8)", text)))
+    }
+}
@@ -0,0 +1,279 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use super::bot::{self, Strategy};
+use super::movegen::Trie;
+use super::{Game, GameOptions, MoveRecord, ScorelessTurn};
+use crate::error::MoveError;
+
+// A game between bots stops here even if nobody went out.
+pub const MAX_TURNS: u32 = 500;
+// z for a 95% confidence interval.
+const Z95: f64 = 1.96;
+
+// One of the players in a self-play run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entrant {
+    pub name: String,
+    pub strategy: Strategy,
+}
+
+// Named after their strategies, numbered when one plays more than once.
+pub fn entrants(strategies: &[Strategy]) -> Vec<Entrant> {
+    strategies
+        .iter()
+        .enumerate()
+        .map(|(i, &strategy)| {
+            let same = strategies[..i].iter().filter(|&&s| s == strategy).count();
+            let name = if same == 0 {
+                strategy.name().to_string()
+            } else {
+                format!("{}#{}", strategy.name(), same + 1)
+            };
+            Entrant { name, strategy }
+        })
+        .collect()
+}
+
+// A finished bot game, as kept in the archive.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub seed: u64,
+    // The entrant in every seat, first to play first.
+    pub seats: Vec<usize>,
+    pub scores: Vec<u32>,
+    pub turns: u32,
+    pub finished: bool,
+    pub history: Vec<MoveRecord>,
+    pub scoreless_history: Vec<ScorelessTurn>,
+}
+
+impl GameRecord {
+    pub fn new(seed: u64, seats: &[usize], game: &Game) -> Self {
+        Self {
+            seed,
+            seats: seats.to_vec(),
+            scores: game.result_scores(),
+            turns: game.turn,
+            finished: game.game_over,
+            history: game.history.clone(),
+            scoreless_history: game.scoreless_history.clone(),
+        }
+    }
+}
+
+// Who sits where in game `index`: the seats turn round from one game to
+// the next, so nobody always plays first.
+pub fn seating(index: u64, entrants: usize) -> Vec<usize> {
+    (0..entrants)
+        .map(|seat| (seat + index as usize) % entrants)
+        .collect()
+}
+
+// A game that stopped on a move the bot offered but the game refused: a
+// bug in the generator, not a result.
+#[derive(Debug)]
+pub struct Aborted {
+    pub seed: u64,
+    pub turn: u32,
+    pub error: MoveError,
+}
+
+// Plays one game out. The same seed deals the same tiles and makes the
+// same random choices.
+pub fn play(
+    seed: u64,
+    seats: &[usize],
+    entrants: &[Entrant],
+    options: &GameOptions,
+    trie: &Trie,
+) -> Result<Game, Aborted> {
+    let mut game = Game::with_options(&GameOptions {
+        seed: Some(seed),
+        ..options.clone()
+    });
+    for &entrant in seats {
+        game.add_player(entrants[entrant].name.clone());
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    while !game.game_over && game.turn <= MAX_TURNS {
+        let strategy = entrants[seats[game.current_player_index]].strategy;
+        let action = bot::choose(&game, trie, strategy, &mut rng);
+        if let Err(error) = game.apply_move(action) {
+            return Err(Aborted {
+                seed,
+                turn: game.turn,
+                error,
+            });
+        }
+        game.end_turn();
+    }
+    Ok(game)
+}
+
+// Results of one entrant over a run.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EntrantStats {
+    pub games: usize,
+    // Shared wins count as a fraction.
+    pub wins: f64,
+    // Own score minus the best other score, summed.
+    pub spread: i64,
+    pub score: u64,
+    pub bingos: usize,
+    pub moves: usize,
+    pub move_points: u64,
+}
+
+impl EntrantStats {
+    pub fn add(&mut self, record: &GameRecord, seat: usize) {
+        let own = record.scores[seat];
+        let best_other = record
+            .scores
+            .iter()
+            .enumerate()
+            .filter(|&(s, _)| s != seat)
+            .map(|(_, &score)| score)
+            .max()
+            .unwrap_or(0);
+        let top = record.scores.iter().copied().max().unwrap_or(0);
+        let sharing = record.scores.iter().filter(|&&s| s == top).count();
+
+        self.games += 1;
+        if own == top {
+            self.wins += 1.0 / sharing as f64;
+        }
+        self.spread += own as i64 - best_other as i64;
+        self.score += own as u64;
+
+        let player_id = seat + 1;
+        for record in record.history.iter().filter(|r| r.player_id == player_id) {
+            self.moves += 1;
+            self.move_points += record.score as u64;
+            if record.bingo {
+                self.bingos += 1;
+            }
+        }
+    }
+
+    fn per_game(&self, total: f64) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            total / self.games as f64
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.per_game(self.wins)
+    }
+
+    pub fn average_spread(&self) -> f64 {
+        self.per_game(self.spread as f64)
+    }
+
+    pub fn average_score(&self) -> f64 {
+        self.per_game(self.score as f64)
+    }
+
+    pub fn bingo_rate(&self) -> f64 {
+        self.per_game(self.bingos as f64)
+    }
+
+    pub fn average_move(&self) -> f64 {
+        if self.moves == 0 {
+            0.0
+        } else {
+            self.move_points as f64 / self.moves as f64
+        }
+    }
+
+    // Wilson score interval for the win rate, at 95%.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        if self.games == 0 {
+            return (0.0, 1.0);
+        }
+        let n = self.games as f64;
+        let p = self.win_rate();
+        let z2 = Z95 * Z95;
+        let denominator = 1.0 + z2 / n;
+        let centre = (p + z2 / (2.0 * n)) / denominator;
+        let half = Z95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+        ((centre - half).max(0.0), (centre + half).min(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(scores: &[u32]) -> GameRecord {
+        GameRecord {
+            seed: 0,
+            seats: (0..scores.len()).collect(),
+            scores: scores.to_vec(),
+            turns: 20,
+            finished: true,
+            history: Vec::new(),
+            scoreless_history: Vec::new(),
+        }
+    }
+
+    fn stats(wins: f64, games: usize) -> EntrantStats {
+        EntrantStats {
+            games,
+            wins,
+            ..EntrantStats::default()
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn wilson_interval_at_half() {
+        let (low, high) = stats(5.0, 10).confidence_interval();
+        assert!(close(low, 0.237), "{low}");
+        assert!(close(high, 0.763), "{high}");
+    }
+
+    #[test]
+    fn wilson_interval_stays_within_bounds() {
+        let (low, high) = stats(10.0, 10).confidence_interval();
+        assert!(close(low, 0.722), "{low}");
+        assert_eq!(high, 1.0);
+        assert_eq!(stats(0.0, 0).confidence_interval(), (0.0, 1.0));
+    }
+
+    #[test]
+    fn a_tie_is_half_a_win_each() {
+        let tie = record(&[350, 350]);
+        let mut first = EntrantStats::default();
+        let mut second = EntrantStats::default();
+        first.add(&tie, 0);
+        second.add(&tie, 1);
+        assert_eq!(first.wins, 0.5);
+        assert_eq!(second.wins, 0.5);
+        assert_eq!(first.spread, 0);
+
+        first.add(&record(&[400, 300]), 0);
+        second.add(&record(&[400, 300]), 1);
+        assert_eq!(first.wins, 1.5);
+        assert_eq!(second.wins, 0.5);
+        assert_eq!(first.average_spread(), 50.0);
+        assert_eq!(second.win_rate(), 0.25);
+    }
+
+    #[test]
+    fn a_three_way_tie_is_a_third_each() {
+        let mut stats = EntrantStats::default();
+        stats.add(&record(&[300, 300, 300]), 2);
+        assert!(close(stats.wins, 1.0 / 3.0));
+        stats.add(&record(&[300, 300, 200]), 2);
+        assert!(close(stats.wins, 1.0 / 3.0));
+        assert_eq!(stats.spread, -100);
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use super::Game;
+use super::bag::TileSet;
+use super::view::GameView;
+
+pub const VOWELS: &str = "AEIOUY";
+pub const BLANK: char = '_';
+
+// Tiles a player can't see: the bag plus every other rack. Worked out from
+// the full distribution minus the board and the player's own rack, so it
+// never peeks at the bag itself.
+pub struct Unseen {
+    // Letter -> count, '_' for blanks. Letters all gone are kept at 0.
+    pub counts: BTreeMap<char, u8>,
+}
+
+impl Unseen {
+    // `seen` lists the board and the rack, blanks as '_'.
+    fn from_seen(tile_set: TileSet, seen: impl Iterator<Item = char>) -> Self {
+        let mut counts: BTreeMap<char, u8> = tile_set
+            .distribution()
+            .iter()
+            .map(|&(letter, count, _)| (letter, count))
+            .collect();
+        for letter in seen {
+            if let Some(count) = counts.get_mut(&letter) {
+                *count = count.saturating_sub(1);
+            }
+        }
+        Self { counts }
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().map(|&c| c as usize).sum()
+    }
+
+    pub fn blanks(&self) -> usize {
+        self.counts.get(&BLANK).copied().unwrap_or(0) as usize
+    }
+
+    pub fn vowels(&self) -> usize {
+        self.counts
+            .iter()
+            .filter(|(letter, _)| VOWELS.contains(**letter))
+            .map(|(_, &c)| c as usize)
+            .sum()
+    }
+
+    pub fn consonants(&self) -> usize {
+        self.total() - self.vowels() - self.blanks()
+    }
+}
+
+impl Game {
+    pub fn unseen_tiles(&self, seat: usize) -> Unseen {
+        let board = self.board.cells.iter().flatten().filter_map(|cell| {
+            cell.letter
+                .map(|letter| if cell.is_blank { BLANK } else { letter })
+        });
+        let rack = self
+            .players
+            .get(seat)
+            .into_iter()
+            .flat_map(|p| p.rack.tiles.iter().map(|t| t.letter));
+        Unseen::from_seen(self.tile_set, board.chain(rack))
+    }
+}
+
+impl GameView {
+    // Same as `Game::unseen_tiles`, from what a seat was sent.
+    pub fn unseen_tiles(&self) -> Unseen {
+        let board = self
+            .board
+            .iter()
+            .flat_map(|row| row.chars())
+            .filter_map(|ch| match ch {
+                '.' => None,
+                ch if ch.is_lowercase() => Some(BLANK),
+                ch => Some(ch),
+            });
+        Unseen::from_seen(self.tile_set, board.chain(self.rack.iter().copied()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::bag::Tile;
+
+    fn tile(letter: char) -> Tile {
+        Tile {
+            letter,
+            value: 0,
+            is_blank: letter == BLANK,
+        }
+    }
+
+    #[test]
+    fn the_board_and_own_rack_are_left_out() {
+        let mut game = Game::new();
+        game.add_player("Ann".to_string());
+        game.add_player("Bob".to_string());
+        game.players[0].rack.tiles = "AE_".chars().map(tile).collect();
+        game.players[1].rack.tiles = "KWX".chars().map(tile).collect();
+        game.board.cells[7][7].letter = Some('A');
+        // A blank played as S is a blank gone, not an S.
+        let cell = &mut game.board.cells[7][8];
+        cell.letter = Some('S');
+        cell.is_blank = true;
+
+        let full: BTreeMap<char, u8> = game
+            .tile_set
+            .distribution()
+            .iter()
+            .map(|&(letter, count, _)| (letter, count))
+            .collect();
+        let unseen = game.unseen_tiles(0);
+        assert_eq!(unseen.counts[&'A'], full[&'A'] - 2);
+        assert_eq!(unseen.counts[&'E'], full[&'E'] - 1);
+        assert_eq!(unseen.counts[&'S'], full[&'S']);
+        assert_eq!(unseen.blanks(), full[&BLANK] as usize - 2);
+        // Bob's rack is out of sight, whatever is on it.
+        assert_eq!(unseen.counts[&'K'], full[&'K']);
+        let total: usize = full.values().map(|&c| c as usize).sum();
+        assert_eq!(unseen.total(), total - 5);
+        assert_eq!(
+            unseen.vowels() + unseen.consonants() + unseen.blanks(),
+            unseen.total()
+        );
+
+        // A seat works it out the same from what it was sent.
+        assert_eq!(game.view(Some(0)).unseen_tiles().counts, unseen.counts);
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use super::Game;
+use super::bag::{Tile, TileSet};
+use super::board::{BOARD_SIZE, Board};
+use super::chat::ChatMessage;
+use super::player::Rack;
+
+// How much of the chat history comes with a view.
+pub const CHAT_BACKLOG: usize = 20;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub id: usize,
+    pub name: String,
+    pub score: u32,
+    pub time_left_ms: Option<i64>,
+}
+
+// What one seat is allowed to see: the board, the scores and its own rack.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameView {
+    // One string per row, '.' for an empty cell, lowercase for a blank.
+    pub board: Vec<String>,
+    // '_' stands for a blank. Empty when seen from outside the game.
+    pub rack: Vec<char>,
+    pub seat: Option<usize>,
+    pub players: Vec<PlayerView>,
+    pub current_player_index: usize,
+    pub turn: u32,
+    pub bag_remaining: usize,
+    pub game_over: bool,
+    // Every rack, in seat order, once the game is over.
+    #[serde(default)]
+    pub revealed_racks: Vec<Vec<char>>,
+    // Latest chat messages, without those from players the seat muted.
+    #[serde(default)]
+    pub chat: Vec<ChatMessage>,
+    #[serde(default)]
+    pub tile_set: TileSet,
+}
+
+impl Game {
+    pub fn view(&self, seat: Option<usize>) -> GameView {
+        let board = self
+            .board
+            .cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell.letter {
+                        Some(ch) if cell.is_blank => ch.to_ascii_lowercase(),
+                        Some(ch) => ch,
+                        None => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let rack = seat
+            .and_then(|idx| self.players.get(idx))
+            .map(|player| player.rack.tiles.iter().map(|t| t.letter).collect())
+            .unwrap_or_default();
+
+        let players = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, p)| PlayerView {
+                id: p.id,
+                name: p.name.clone(),
+                score: p.score,
+                time_left_ms: self.clock.as_ref().map(|clock| clock.remaining(i)),
+            })
+            .collect();
+
+        let revealed_racks = if self.game_over {
+            self.players
+                .iter()
+                .map(|p| p.rack.tiles.iter().map(|t| t.letter).collect())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let reader = seat
+            .and_then(|idx| self.players.get(idx))
+            .map(|p| p.name.as_str());
+        let chat = self.chat.recent(reader, CHAT_BACKLOG);
+
+        GameView {
+            board,
+            rack,
+            seat,
+            players,
+            current_player_index: self.current_player_index,
+            turn: self.turn,
+            bag_remaining: self.bag.tiles.len(),
+            game_over: self.game_over,
+            revealed_racks,
+            chat,
+            tile_set: self.tile_set,
+        }
+    }
+}
+
+impl GameView {
+    pub fn to_board(&self) -> Board {
+        let mut board = Board::new();
+        for (r, row) in self.board.iter().enumerate().take(BOARD_SIZE) {
+            for (c, ch) in row.chars().enumerate().take(BOARD_SIZE) {
+                if ch == '.' {
+                    continue;
+                }
+                board.cells[r][c].letter = Some(ch.to_ascii_uppercase());
+                board.cells[r][c].is_blank = ch.is_ascii_lowercase();
+            }
+        }
+        board
+    }
+
+    pub fn to_rack(&self) -> Rack {
+        let mut rack = Rack::new();
+        rack.tiles = self
+            .rack
+            .iter()
+            .map(|&letter| Tile {
+                letter,
+                value: self.tile_set.letter_value(letter) as u8,
+                is_blank: letter == '_',
+            })
+            .collect();
+        rack
+    }
+}
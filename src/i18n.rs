@@ -0,0 +1,135 @@
+use std::env;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use fluent_bundle::FluentResource;
+use fluent_bundle::concurrent::FluentBundle;
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+const FR_CATALOG: &str = include_str!("../locales/fr.ftl");
+const EN_CATALOG: &str = include_str!("../locales/en.ftl");
+
+// Not a `Language` value: the language hasn't been picked yet.
+const UNSET: u8 = u8::MAX;
+
+static CURRENT: AtomicU8 = AtomicU8::new(UNSET);
+static FR_BUNDLE: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+static EN_BUNDLE: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    Fr,
+    En,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Fr, Language::En];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Fr => "fr",
+            Language::En => "en",
+        }
+    }
+
+    // Accepts "en", "en_GB.UTF-8", "fr-CA"...: only the language part counts.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let lang = tag
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        Self::ALL.into_iter().find(|l| l.code() == lang)
+    }
+
+    // KRABBELS_LANG wins over the usual locale variables; anything we have
+    // no catalog for falls back to French.
+    pub fn from_env() -> Self {
+        ["KRABBELS_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    fn from_u8(n: u8) -> Self {
+        match n {
+            1 => Language::En,
+            _ => Language::Fr,
+        }
+    }
+}
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        UNSET => {
+            let language = Language::from_env();
+            set_language(language);
+            language
+        }
+        n => Language::from_u8(n),
+    }
+}
+
+fn bundle(language: Language) -> &'static FluentBundle<FluentResource> {
+    let (cell, source) = match language {
+        Language::Fr => (&FR_BUNDLE, FR_CATALOG),
+        Language::En => (&EN_BUNDLE, EN_CATALOG),
+    };
+    cell.get_or_init(|| {
+        let langid: LanguageIdentifier = language.code().parse().expect("valid language tag");
+        let resource = FluentResource::try_new(source.to_string())
+            .unwrap_or_else(|(_, errors)| panic!("bad {} catalog: {:?}", language.code(), errors));
+        let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+        // The isolation marks around placeables garble terminal alignment.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .unwrap_or_else(|errors| panic!("bad {} catalog: {:?}", language.code(), errors));
+        bundle
+    })
+}
+
+fn format(language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let bundle = bundle(language);
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    Some(
+        bundle
+            .format_pattern(pattern, args, &mut errors)
+            .into_owned(),
+    )
+}
+
+// Messages missing from a catalog fall back to French, then to their id,
+// so a gap shows up on screen instead of a panic.
+pub fn tr_args(id: &str, args: Option<&FluentArgs>) -> String {
+    format(language(), id, args)
+        .or_else(|| format(Language::Fr, id, args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+pub fn tr(id: &str) -> String {
+    tr_args(id, None)
+}
+
+// t!("move-played", name = player, score = 12)
+#[macro_export]
+macro_rules! t {
+    ($id:expr) => {
+        $crate::i18n::tr($id)
+    };
+    ($id:expr, $($key:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($key), $value);)+
+        $crate::i18n::tr_args($id, Some(&args))
+    }};
+}
@@ -0,0 +1,10 @@
+pub mod app;
+pub mod config;
+pub mod error;
+pub mod game;
+pub mod i18n;
+pub mod net;
+pub mod profile;
+pub mod save;
+pub mod study;
+pub mod ui;
@@ -1,14 +1,138 @@
-mod app;
-mod error;
-mod game;
-mod save;
-mod ui;
+use krabbels::config::{Options, Settings};
+use krabbels::game::judge::JudgeLog;
+use krabbels::game::lexicon::Lexicon;
+use krabbels::net::protocol::DEFAULT_PORT;
+use krabbels::save::file::load_from_path;
+use krabbels::ui::selfplay::{self, SelfplayArgs};
+use krabbels::ui::study::{self, StudyArgs};
+use krabbels::ui::words::{self, WordsArgs};
+use krabbels::ui::{analysis, judge, remote};
+use krabbels::{app, save, t};
 
-use std::io;
+use clap::{Parser, Subcommand};
+use std::{io, path::PathBuf, process};
+
+#[derive(Parser)]
+#[command(name = "krabbels", version, about = "Scrabble in the terminal")]
+struct Cli {
+    #[command(flatten)]
+    options: Options,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Host a network game and play in it
+    Host {
+        #[arg(default_value_t = format!("0.0.0.0:{}", DEFAULT_PORT))]
+        addr: String,
+    },
+    /// Join a network game
+    Join {
+        #[arg(default_value_t = format!("127.0.0.1:{}", DEFAULT_PORT))]
+        addr: String,
+    },
+    /// Search the dictionary: anagrams, patterns, letters contained
+    Words(WordsArgs),
+    /// Anagram flashcards with spaced repetition
+    Study(StudyArgs),
+    /// Word judge screen, on the game's dictionary (see also krabbels-judge)
+    Judge {
+        /// Don't log the adjudications
+        #[arg(long)]
+        no_log: bool,
+    },
+    /// Letters and words that extend a word
+    Hooks {
+        word: String,
+        /// Longer words shown at most
+        #[arg(long, default_value_t = words::HOOKS_SHOWN)]
+        limit: usize,
+    },
+    /// Bots against each other, for strength testing (see also krabbels-selfplay)
+    Selfplay(SelfplayArgs),
+    /// Go over a finished game move by move against the best plays
+    Analyze {
+        /// The save file
+        save: PathBuf,
+        /// Also write the report here (JSON for a .json file)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn lexicon(settings: &Settings) -> &Lexicon {
+    settings.game.lexicon.as_deref().unwrap_or_else(|| {
+        eprintln!("{}", t!("no-dictionary"));
+        process::exit(2);
+    })
+}
 
 fn main() -> io::Result<()> {
-    save::load_game_state()?;
-    app::run();
+    let cli = Cli::parse();
+    let settings = Settings::load(&cli.options).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+    settings.apply();
+
+    match cli.command {
+        Some(Command::Host { addr }) => return remote::host(&addr, &settings),
+        Some(Command::Join { addr }) => return remote::join(&addr, &settings),
+        Some(Command::Words(args)) => {
+            words::run(&args, lexicon(&settings), settings.game.tile_set);
+            return Ok(());
+        }
+        Some(Command::Study(args)) => {
+            return study::run(
+                &args,
+                lexicon(&settings),
+                settings.game.tile_set,
+                &settings.data_dir,
+            );
+        }
+        Some(Command::Judge { no_log }) => {
+            let log = (!no_log).then(|| JudgeLog::new(&settings.data_dir));
+            return judge::run(lexicon(&settings), log.as_ref());
+        }
+        Some(Command::Hooks { word, limit }) => {
+            words::run_hooks(&word, limit, lexicon(&settings), settings.game.tile_set);
+            return Ok(());
+        }
+        Some(Command::Selfplay(args)) => {
+            return selfplay::run(
+                &args,
+                lexicon(&settings),
+                &settings.game,
+                settings.rating_mode,
+                &settings.data_dir,
+            );
+        }
+        Some(Command::Analyze { save, output }) => {
+            let loaded = load_from_path(&save).unwrap_or_else(|e| {
+                eprintln!(
+                    "{}",
+                    t!(
+                        "save-load-failed",
+                        path = save.display().to_string(),
+                        error = e.to_string()
+                    )
+                );
+                process::exit(2);
+            });
+            if let Some(report) = analysis::show(&loaded.game, lexicon(&settings))
+                && let Some(path) = output
+            {
+                analysis::save_report(&report, &path);
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let mut session = save::load_game_state(&settings)?;
+    app::run(&mut session, &settings)?;
 
     Ok(())
 }
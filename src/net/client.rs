@@ -0,0 +1,102 @@
+use std::{
+    io::{self, BufReader},
+    net::TcpStream,
+    sync::mpsc::Sender,
+    thread,
+    time::Duration,
+};
+
+use super::protocol::{
+    ClientMessage, PROTOCOL_VERSION, ServerMessage, read_message, write_message,
+};
+
+const RECONNECT_ATTEMPTS: u32 = 10;
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+pub enum ClientEvent {
+    Server(ServerMessage),
+    Disconnected { generation: u64 },
+    Line(String),
+    InputClosed,
+}
+
+pub struct Connection {
+    addr: String,
+    name: String,
+    token: Option<String>,
+    generation: u64,
+    writer: Option<TcpStream>,
+    events: Sender<ClientEvent>,
+}
+
+impl Connection {
+    pub fn new(addr: String, name: String, events: Sender<ClientEvent>) -> Self {
+        Self {
+            addr,
+            name,
+            token: None,
+            generation: 0,
+            writer: None,
+            events,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Remembered so a reconnection is recognised as the same player and
+    // gets its seats back.
+    pub fn set_token(&mut self, token: String) {
+        self.token = Some(token);
+    }
+
+    pub fn is_current(&self, generation: u64) -> bool {
+        generation == self.generation
+    }
+
+    pub fn connect(&mut self) -> io::Result<()> {
+        let stream = TcpStream::connect(&self.addr)?;
+        self.generation += 1;
+
+        let generation = self.generation;
+        let events = self.events.clone();
+        let mut reader = BufReader::new(stream.try_clone()?);
+        thread::spawn(move || {
+            while let Ok(Some(msg)) = read_message::<_, ServerMessage>(&mut reader) {
+                if events.send(ClientEvent::Server(msg)).is_err() {
+                    return;
+                }
+            }
+            let _ = events.send(ClientEvent::Disconnected { generation });
+        });
+
+        self.writer = Some(stream);
+        self.send(&ClientMessage::Hello {
+            version: PROTOCOL_VERSION,
+            name: self.name.clone(),
+            token: self.token.clone(),
+        })
+    }
+
+    pub fn reconnect(&mut self) -> io::Result<()> {
+        self.writer = None;
+
+        let mut last_error = None;
+        for _ in 0..RECONNECT_ATTEMPTS {
+            thread::sleep(RECONNECT_DELAY);
+            match self.connect() {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| io::Error::other("could not reconnect")))
+    }
+
+    pub fn send(&mut self, msg: &ClientMessage) -> io::Result<()> {
+        match &mut self.writer {
+            Some(stream) => write_message(stream, msg),
+            None => Err(io::Error::from(io::ErrorKind::NotConnected)),
+        }
+    }
+}
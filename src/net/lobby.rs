@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::game::clock::TimeControl;
+use crate::game::rules::{RulePreset, RuleSet};
+use crate::game::{Game, GameOptions};
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameStatus {
+    Waiting,
+    Ongoing,
+    Finished,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub game_id: u32,
+    pub players: Vec<String>,
+    pub seats: usize,
+    pub status: GameStatus,
+    pub time_control: Option<TimeControl>,
+    // None for the server's own rules.
+    #[serde(default)]
+    pub rules: Option<RulePreset>,
+    pub spectators: usize,
+}
+
+pub struct HostedGame {
+    pub game: Game,
+    pub seats: usize,
+    pub rules: Option<RulePreset>,
+    pub spectators: Vec<String>,
+}
+
+impl HostedGame {
+    pub fn status(&self) -> GameStatus {
+        if self.game.game_over {
+            GameStatus::Finished
+        } else if self.game.players.len() == self.seats {
+            GameStatus::Ongoing
+        } else {
+            GameStatus::Waiting
+        }
+    }
+
+    pub fn started(&self) -> bool {
+        self.status() != GameStatus::Waiting
+    }
+
+    pub fn seat_of(&self, name: &str) -> Option<usize> {
+        self.game.players.iter().position(|p| p.name == name)
+    }
+
+    // Players and spectators: everyone who gets the game's events.
+    pub fn audience(&self) -> impl Iterator<Item = &str> {
+        self.game
+            .players
+            .iter()
+            .map(|p| p.name.as_str())
+            .chain(self.spectators.iter().map(String::as_str))
+    }
+
+    fn summary(&self, game_id: u32) -> GameSummary {
+        GameSummary {
+            game_id,
+            players: self.game.players.iter().map(|p| p.name.clone()).collect(),
+            seats: self.seats,
+            status: self.status(),
+            time_control: self.game.clock.as_ref().map(|c| c.control),
+            rules: self.rules,
+            spectators: self.spectators.len(),
+        }
+    }
+}
+
+pub struct Seek {
+    pub name: String,
+    pub rating: f64,
+    pub rating_min: Option<f64>,
+    pub rating_max: Option<f64>,
+    pub time_control: Option<TimeControl>,
+    pub rules: Option<RulePreset>,
+}
+
+impl Seek {
+    fn accepts(&self, rating: f64) -> bool {
+        self.rating_min.is_none_or(|min| rating >= min)
+            && self.rating_max.is_none_or(|max| rating <= max)
+    }
+
+    fn matches(&self, other: &Seek) -> bool {
+        self.name != other.name
+            && self.time_control == other.time_control
+            && self.rules == other.rules
+            && self.accepts(other.rating)
+            && other.accepts(self.rating)
+    }
+}
+
+pub struct Lobby {
+    // What every game here starts from: tiles, board, rules, word list.
+    options: GameOptions,
+    // For games that don't ask for a clock of their own.
+    time_control: Option<TimeControl>,
+    next_game_id: u32,
+    games: BTreeMap<u32, HostedGame>,
+    seeks: Vec<Seek>,
+}
+
+impl Lobby {
+    pub fn new(options: GameOptions, time_control: Option<TimeControl>) -> Self {
+        Self {
+            options,
+            time_control,
+            next_game_id: 0,
+            games: BTreeMap::new(),
+            seeks: Vec::new(),
+        }
+    }
+
+    pub fn create_game(
+        &mut self,
+        seats: usize,
+        time_control: Option<TimeControl>,
+        rules: Option<RulePreset>,
+    ) -> u32 {
+        self.next_game_id += 1;
+
+        let mut options = self.options.clone();
+        if let Some(preset) = rules {
+            options.rules = RuleSet::preset(preset);
+        }
+        let mut game = Game::with_options(&options);
+        if let Some(control) = time_control.or(self.time_control) {
+            game.set_time_control(control);
+        }
+        self.games.insert(
+            self.next_game_id,
+            HostedGame {
+                game,
+                seats,
+                rules,
+                spectators: Vec::new(),
+            },
+        );
+
+        self.next_game_id
+    }
+
+    pub fn get(&self, game_id: u32) -> Option<&HostedGame> {
+        self.games.get(&game_id)
+    }
+
+    pub fn get_mut(&mut self, game_id: u32) -> Option<&mut HostedGame> {
+        self.games.get_mut(&game_id)
+    }
+
+    pub fn games(&self) -> impl Iterator<Item = (u32, &HostedGame)> {
+        self.games.iter().map(|(&id, hosted)| (id, hosted))
+    }
+
+    pub fn summaries(&self) -> Vec<GameSummary> {
+        self.games
+            .iter()
+            .map(|(&id, hosted)| hosted.summary(id))
+            .collect()
+    }
+
+    // Either pairs the seek with a compatible one already waiting and
+    // returns the new two-seat game, or queues it.
+    pub fn seek(&mut self, seek: Seek) -> Option<(u32, [String; 2])> {
+        self.cancel_seek(&seek.name);
+
+        let Some(idx) = self.seeks.iter().position(|other| other.matches(&seek)) else {
+            self.seeks.push(seek);
+            return None;
+        };
+
+        let other = self.seeks.remove(idx);
+        let game_id = self.create_game(2, seek.time_control, seek.rules);
+        Some((game_id, [other.name, seek.name]))
+    }
+
+    pub fn cancel_seek(&mut self, name: &str) -> bool {
+        let before = self.seeks.len();
+        self.seeks.retain(|s| s.name != name);
+        self.seeks.len() != before
+    }
+}
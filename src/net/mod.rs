@@ -0,0 +1,4 @@
+pub mod client;
+pub mod lobby;
+pub mod protocol;
+pub mod server;
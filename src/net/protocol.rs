@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+use super::lobby::GameSummary;
+use crate::error::ServerError;
+use crate::game::action::Action;
+use crate::game::bag::Tile;
+use crate::game::board::{Direction, Position, Word};
+use crate::game::chat::{ChatBody, ChatMessage};
+use crate::game::clock::TimeControl;
+use crate::game::rules::RulePreset;
+use crate::game::view::GameView;
+use crate::game::{GameEvent, MoveRecord};
+
+// Bump on any incompatible change, and describe it in docs/protocol.md.
+pub const PROTOCOL_VERSION: u32 = 5;
+pub const DEFAULT_PORT: u16 = 7878;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Hello {
+        version: u32,
+        name: String,
+        #[serde(default)]
+        token: Option<String>,
+    },
+    ListGames,
+    CreateGame {
+        seats: usize,
+        #[serde(default)]
+        time_control: Option<TimeControl>,
+        // The server's own rules when unset.
+        #[serde(default)]
+        rules: Option<RulePreset>,
+    },
+    JoinGame {
+        game_id: u32,
+    },
+    Play {
+        game_id: u32,
+        play: Play,
+    },
+    Seek {
+        #[serde(default)]
+        rating_min: Option<f64>,
+        #[serde(default)]
+        rating_max: Option<f64>,
+        #[serde(default)]
+        time_control: Option<TimeControl>,
+        #[serde(default)]
+        rules: Option<RulePreset>,
+    },
+    CancelSeek,
+    Watch {
+        game_id: u32,
+    },
+    Review {
+        game_id: u32,
+    },
+    Chat {
+        game_id: u32,
+        body: ChatBody,
+    },
+    Mute {
+        game_id: u32,
+        name: String,
+    },
+    Unmute {
+        game_id: u32,
+        name: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Play {
+    PlaceWord {
+        row: usize,
+        col: usize,
+        dir: Direction,
+        word: String,
+    },
+    // Rack letters, '_' for a blank.
+    Exchange {
+        letters: String,
+    },
+    Challenge,
+    Pass,
+}
+
+impl Play {
+    pub fn from_action(action: Action) -> Option<Self> {
+        match action {
+            Action::PlaceWord { pos, dir, word } => Some(Play::PlaceWord {
+                row: pos.row,
+                col: pos.col,
+                dir,
+                word: word.tiles.iter().map(|t| t.letter).collect(),
+            }),
+            Action::ExchangeTiles { letters } => Some(Play::Exchange {
+                letters: letters.into_iter().collect(),
+            }),
+            Action::Challenge => Some(Play::Challenge),
+            Action::Pass => Some(Play::Pass),
+            _ => None,
+        }
+    }
+
+    pub fn into_action(self) -> Action {
+        match self {
+            Play::PlaceWord {
+                row,
+                col,
+                dir,
+                word,
+            } => Action::PlaceWord {
+                pos: Position { row, col },
+                dir,
+                word: Word {
+                    tiles: word
+                        .to_uppercase()
+                        .chars()
+                        .map(|letter| Tile {
+                            letter,
+                            value: 0,
+                            is_blank: false,
+                        })
+                        .collect(),
+                },
+            },
+            Play::Exchange { letters } => Action::ExchangeTiles {
+                letters: letters.to_uppercase().chars().collect(),
+            },
+            Play::Challenge => Action::Challenge,
+            Play::Pass => Action::Pass,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Welcome {
+        version: u32,
+        token: String,
+    },
+    Error {
+        error: ServerError,
+    },
+    GameList {
+        games: Vec<GameSummary>,
+    },
+    Joined {
+        game_id: u32,
+        seat: usize,
+    },
+    State {
+        game_id: u32,
+        view: Box<GameView>,
+    },
+    Event {
+        game_id: u32,
+        event: GameEvent,
+    },
+    MoveRejected {
+        game_id: u32,
+        reason: ServerError,
+    },
+    Seeking {
+        rating: f64,
+    },
+    SeekCancelled,
+    Watching {
+        game_id: u32,
+    },
+    Review {
+        game_id: u32,
+        view: Box<GameView>,
+        history: Vec<MoveRecord>,
+    },
+    Chat {
+        game_id: u32,
+        message: ChatMessage,
+    },
+    Muted {
+        game_id: u32,
+        name: String,
+        muted: bool,
+    },
+}
+
+pub fn write_message<W: Write, T: Serialize>(out: &mut W, msg: &T) -> io::Result<()> {
+    let line = serde_json::to_string(msg).map_err(io::Error::other)?;
+    writeln!(out, "{}", line)?;
+    out.flush()
+}
+
+// Ok(None) on a clean end of stream.
+pub fn read_message<R: BufRead, T: for<'de> Deserialize<'de>>(
+    input: &mut R,
+) -> io::Result<Option<T>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break;
+        }
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
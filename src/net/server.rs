@@ -0,0 +1,638 @@
+use rand::Rng;
+use std::{
+    collections::HashMap,
+    io::{self, BufReader},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+use super::lobby::{GameStatus, Lobby, Seek};
+use super::protocol::{
+    ClientMessage, PROTOCOL_VERSION, Play, ServerMessage, read_message, write_message,
+};
+use crate::config::Settings;
+use crate::error::{MoveError, ServerError};
+use crate::game::chat::ChatBody;
+use crate::game::clock::TimeControl;
+use crate::game::rules::RulePreset;
+use crate::game::{GameEvent, GameOptions};
+use crate::profile::ProfileStore;
+use crate::profile::rating::{INITIAL_RATING, RatingMode};
+use crate::save::now_unix;
+
+const MAX_SEATS: usize = 4;
+// How often the clocks of the games in progress are looked at.
+const CLOCK_TICK: Duration = Duration::from_millis(500);
+// Pause after a failed accept, so running out of file descriptors
+// doesn't turn into a busy loop.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+// How the games hosted here are played, and where their results go.
+#[derive(Clone, Default)]
+pub struct ServerConfig {
+    // Ratings for matchmaking are read from, and results written to, the
+    // profiles under it.
+    pub data_dir: PathBuf,
+    pub game: GameOptions,
+    // For games created without a clock of their own.
+    pub time_control: Option<TimeControl>,
+    pub rating_mode: RatingMode,
+}
+
+impl ServerConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            data_dir: settings.data_dir.clone(),
+            game: settings.game.clone(),
+            time_control: settings.time_control,
+            rating_mode: settings.rating_mode,
+        }
+    }
+}
+
+struct Client {
+    connection: u64,
+    tx: Sender<ServerMessage>,
+}
+
+struct ServerState {
+    data_dir: PathBuf,
+    rating_mode: RatingMode,
+    next_connection: u64,
+    lobby: Lobby,
+    tokens: HashMap<String, String>,
+    clients: HashMap<String, Client>,
+}
+
+impl ServerState {
+    fn send_to(&self, name: &str, msg: ServerMessage) {
+        if let Some(client) = self.clients.get(name) {
+            // A closed channel only means the player just left.
+            let _ = client.tx.send(msg);
+        }
+    }
+
+    fn push_state(&self, game_id: u32) {
+        let Some(hosted) = self.lobby.get(game_id) else {
+            return;
+        };
+        for (seat, player) in hosted.game.players.iter().enumerate() {
+            self.send_to(
+                &player.name,
+                ServerMessage::State {
+                    game_id,
+                    view: Box::new(hosted.game.view(Some(seat))),
+                },
+            );
+        }
+        for spectator in &hosted.spectators {
+            self.send_to(
+                spectator,
+                ServerMessage::State {
+                    game_id,
+                    view: Box::new(hosted.game.view(None)),
+                },
+            );
+        }
+    }
+
+    fn broadcast(&self, game_id: u32, msg: &ServerMessage) {
+        if let Some(hosted) = self.lobby.get(game_id) {
+            for name in hosted.audience() {
+                self.send_to(name, msg.clone());
+            }
+        }
+    }
+
+    fn rating_of(&self, name: &str) -> f64 {
+        ProfileStore::load(&self.data_dir)
+            .ok()
+            .and_then(|store| store.get(name).map(|p| p.rating.value))
+            .unwrap_or(INITIAL_RATING)
+    }
+
+    fn record_result(&self, game_id: u32) {
+        let Some(hosted) = self.lobby.get(game_id) else {
+            return;
+        };
+        let recorded = ProfileStore::load(&self.data_dir).and_then(|mut store| {
+            store.record_game(&hosted.game, self.rating_mode, now_unix());
+            store.save(&self.data_dir)
+        });
+        if let Err(e) = recorded {
+            eprintln!("could not record the result of game {}: {e}", game_id);
+        }
+    }
+
+    // Someone who just stops playing never sends a move that would be
+    // caught out, so the flags are looked at on a timer too.
+    fn check_clocks(&mut self) {
+        let ongoing: Vec<u32> = self
+            .lobby
+            .games()
+            .filter(|(_, hosted)| hosted.status() == GameStatus::Ongoing)
+            .map(|(game_id, _)| game_id)
+            .collect();
+        for game_id in ongoing {
+            let flagged = self
+                .lobby
+                .get_mut(game_id)
+                .is_some_and(|hosted| hosted.game.check_timeout());
+            if flagged {
+                self.push_state(game_id);
+                self.record_result(game_id);
+            }
+        }
+    }
+}
+
+fn new_token() -> String {
+    format!("{:016x}", rand::rng().random::<u64>())
+}
+
+fn error(error: ServerError) -> ServerMessage {
+    ServerMessage::Error { error }
+}
+
+fn hello(
+    state: &mut ServerState,
+    tx: &Sender<ServerMessage>,
+    connection: u64,
+    version: u32,
+    name: String,
+    token: Option<String>,
+) -> Result<String, ServerMessage> {
+    if version != PROTOCOL_VERSION {
+        return Err(error(ServerError::UnsupportedVersion {
+            version,
+            server: PROTOCOL_VERSION,
+        }));
+    }
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(error(ServerError::EmptyName));
+    }
+
+    // The first connection under a name claims it; later ones must show
+    // the token handed out then.
+    let token = match state.tokens.get(&name) {
+        Some(known) if token.as_deref() == Some(known.as_str()) => known.clone(),
+        Some(_) => return Err(error(ServerError::NameTaken)),
+        None => {
+            let fresh = new_token();
+            state.tokens.insert(name.clone(), fresh.clone());
+            fresh
+        }
+    };
+
+    let _ = tx.send(ServerMessage::Welcome {
+        version: PROTOCOL_VERSION,
+        token,
+    });
+    state.clients.insert(
+        name.clone(),
+        Client {
+            connection,
+            tx: tx.clone(),
+        },
+    );
+
+    // Bring a reconnecting player back up to date.
+    for (game_id, hosted) in state.lobby.games() {
+        if let Some(seat) = hosted.seat_of(&name) {
+            state.send_to(&name, ServerMessage::Joined { game_id, seat });
+            if !hosted.started() {
+                continue;
+            }
+            state.send_to(
+                &name,
+                ServerMessage::State {
+                    game_id,
+                    view: Box::new(hosted.game.view(Some(seat))),
+                },
+            );
+        }
+    }
+
+    Ok(name)
+}
+
+fn create_game(
+    state: &mut ServerState,
+    name: &str,
+    seats: usize,
+    time_control: Option<TimeControl>,
+    rules: Option<RulePreset>,
+) -> Result<(), ServerMessage> {
+    if !(1..=MAX_SEATS).contains(&seats) {
+        return Err(error(ServerError::SeatCount { max: MAX_SEATS }));
+    }
+
+    let game_id = state.lobby.create_game(seats, time_control, rules);
+    join_game(state, name, game_id)
+}
+
+fn join_game(state: &mut ServerState, name: &str, game_id: u32) -> Result<(), ServerMessage> {
+    let hosted = state
+        .lobby
+        .get_mut(game_id)
+        .ok_or_else(|| error(ServerError::NoGame { game_id }))?;
+
+    let seat = match hosted.seat_of(name) {
+        Some(seat) => seat,
+        None if hosted.started() => return Err(error(ServerError::GameFull)),
+        None => {
+            hosted.game.add_player(name.to_string());
+            hosted.spectators.retain(|s| s != name);
+            hosted.game.players.len() - 1
+        }
+    };
+
+    let started = hosted.started();
+    if started {
+        hosted.game.start_turn();
+    }
+
+    state.lobby.cancel_seek(name);
+    state.send_to(name, ServerMessage::Joined { game_id, seat });
+    // Nobody sees a board until every seat is taken.
+    if started {
+        state.push_state(game_id);
+    }
+    Ok(())
+}
+
+fn seek(
+    state: &mut ServerState,
+    name: &str,
+    rating_min: Option<f64>,
+    rating_max: Option<f64>,
+    time_control: Option<TimeControl>,
+    rules: Option<RulePreset>,
+) -> Result<(), ServerMessage> {
+    let rating = state.rating_of(name);
+    let matched = state.lobby.seek(Seek {
+        name: name.to_string(),
+        rating,
+        rating_min,
+        rating_max,
+        time_control,
+        rules,
+    });
+
+    match matched {
+        Some((game_id, players)) => {
+            for player in &players {
+                join_game(state, player, game_id)?;
+            }
+        }
+        None => state.send_to(name, ServerMessage::Seeking { rating }),
+    }
+    Ok(())
+}
+
+fn watch(state: &mut ServerState, name: &str, game_id: u32) -> Result<(), ServerMessage> {
+    let hosted = state
+        .lobby
+        .get_mut(game_id)
+        .ok_or_else(|| error(ServerError::NoGame { game_id }))?;
+
+    if hosted.seat_of(name).is_some() {
+        return Err(error(ServerError::AlreadyPlaying));
+    }
+    if !hosted.spectators.iter().any(|s| s == name) {
+        hosted.spectators.push(name.to_string());
+    }
+
+    let view = hosted.game.view(None);
+    state.send_to(name, ServerMessage::Watching { game_id });
+    state.send_to(
+        name,
+        ServerMessage::State {
+            game_id,
+            view: Box::new(view),
+        },
+    );
+    Ok(())
+}
+
+fn review(state: &ServerState, name: &str, game_id: u32) -> Result<(), ServerMessage> {
+    let hosted = state
+        .lobby
+        .get(game_id)
+        .ok_or_else(|| error(ServerError::NoGame { game_id }))?;
+
+    if hosted.status() != GameStatus::Finished {
+        return Err(error(ServerError::NotFinished));
+    }
+
+    state.send_to(
+        name,
+        ServerMessage::Review {
+            game_id,
+            view: Box::new(hosted.game.view(None)),
+            history: hosted.game.history.clone(),
+        },
+    );
+    Ok(())
+}
+
+fn chat(
+    state: &mut ServerState,
+    name: &str,
+    game_id: u32,
+    body: ChatBody,
+) -> Result<(), ServerMessage> {
+    let hosted = state
+        .lobby
+        .get_mut(game_id)
+        .ok_or_else(|| error(ServerError::NoGame { game_id }))?;
+
+    if hosted.seat_of(name).is_none() {
+        return Err(error(ServerError::NotAPlayer));
+    }
+    let message = hosted
+        .game
+        .chat
+        .post(name, body, now_unix())
+        .map_err(|e| error(ServerError::Chat(e)))?
+        .clone();
+
+    let hosted = state.lobby.get(game_id).unwrap();
+    for reader in hosted.audience() {
+        if !hosted.game.chat.is_muted(reader, name) {
+            state.send_to(
+                reader,
+                ServerMessage::Chat {
+                    game_id,
+                    message: message.clone(),
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+fn set_muted(
+    state: &mut ServerState,
+    name: &str,
+    game_id: u32,
+    target: String,
+    muted: bool,
+) -> Result<(), ServerMessage> {
+    let hosted = state
+        .lobby
+        .get_mut(game_id)
+        .ok_or_else(|| error(ServerError::NoGame { game_id }))?;
+
+    if hosted.seat_of(name).is_none() {
+        return Err(error(ServerError::NotAPlayer));
+    }
+    if target == name {
+        return Err(error(ServerError::MuteSelf));
+    }
+    hosted
+        .game
+        .set_muted(name, &target, muted)
+        .map_err(|e| error(ServerError::Chat(e)))?;
+
+    state.send_to(
+        name,
+        ServerMessage::Muted {
+            game_id,
+            name: target,
+            muted,
+        },
+    );
+    Ok(())
+}
+
+fn play(
+    state: &mut ServerState,
+    name: &str,
+    game_id: u32,
+    play: Play,
+) -> Result<(), ServerMessage> {
+    let hosted = state
+        .lobby
+        .get_mut(game_id)
+        .ok_or_else(|| error(ServerError::NoGame { game_id }))?;
+
+    let rejected = |reason: ServerError| ServerMessage::MoveRejected { game_id, reason };
+
+    match hosted.status() {
+        GameStatus::Waiting => return Err(rejected(ServerError::WaitingForPlayers)),
+        GameStatus::Finished => return Err(rejected(ServerError::GameOver)),
+        GameStatus::Ongoing => {}
+    }
+    if hosted.game.current_player().name != name {
+        return Err(rejected(ServerError::NotYourTurn));
+    }
+
+    let game = &mut hosted.game;
+    let events = if game.check_timeout() {
+        Vec::new()
+    } else {
+        let events = game.apply_move(play.into_action()).map_err(|e| {
+            if let MoveError::Integrity(_) = e {
+                eprintln!("game {} is inconsistent: {e}", game_id);
+            }
+            rejected(ServerError::Move(e))
+        })?;
+        if !events.iter().any(GameEvent::keeps_turn) {
+            game.end_turn();
+            game.start_turn();
+        }
+        events
+    };
+    let finished = game.game_over;
+
+    for event in events {
+        state.broadcast(game_id, &ServerMessage::Event { game_id, event });
+    }
+    state.push_state(game_id);
+
+    if finished {
+        state.record_result(game_id);
+    }
+    Ok(())
+}
+
+fn handle_message(
+    state: &Mutex<ServerState>,
+    tx: &Sender<ServerMessage>,
+    connection: u64,
+    name: &mut Option<String>,
+    msg: ClientMessage,
+) {
+    let mut state = lock(state);
+
+    let result = match (msg, name.as_deref()) {
+        (
+            ClientMessage::Hello {
+                version,
+                name: wanted,
+                token,
+            },
+            _,
+        ) => hello(&mut state, tx, connection, version, wanted, token).map(|n| {
+            *name = Some(n);
+        }),
+        (_, None) => Err(error(ServerError::HelloFirst)),
+        (ClientMessage::ListGames, Some(_)) => {
+            let games = state.lobby.summaries();
+            let _ = tx.send(ServerMessage::GameList { games });
+            Ok(())
+        }
+        (
+            ClientMessage::CreateGame {
+                seats,
+                time_control,
+                rules,
+            },
+            Some(me),
+        ) => create_game(&mut state, me, seats, time_control, rules),
+        (ClientMessage::JoinGame { game_id }, Some(me)) => join_game(&mut state, me, game_id),
+        (ClientMessage::Play { game_id, play: p }, Some(me)) => play(&mut state, me, game_id, p),
+        (
+            ClientMessage::Seek {
+                rating_min,
+                rating_max,
+                time_control,
+                rules,
+            },
+            Some(me),
+        ) => seek(&mut state, me, rating_min, rating_max, time_control, rules),
+        (ClientMessage::CancelSeek, Some(me)) => {
+            if state.lobby.cancel_seek(me) {
+                let _ = tx.send(ServerMessage::SeekCancelled);
+            }
+            Ok(())
+        }
+        (ClientMessage::Watch { game_id }, Some(me)) => watch(&mut state, me, game_id),
+        (ClientMessage::Review { game_id }, Some(me)) => review(&state, me, game_id),
+        (ClientMessage::Chat { game_id, body }, Some(me)) => chat(&mut state, me, game_id, body),
+        (
+            ClientMessage::Mute {
+                game_id,
+                name: target,
+            },
+            Some(me),
+        ) => set_muted(&mut state, me, game_id, target, true),
+        (
+            ClientMessage::Unmute {
+                game_id,
+                name: target,
+            },
+            Some(me),
+        ) => set_muted(&mut state, me, game_id, target, false),
+    };
+
+    if let Err(reply) = result {
+        let _ = tx.send(reply);
+    }
+}
+
+// A thread that panicked while holding the lock leaves the other games as
+// they were; keep serving them rather than failing every connection.
+fn lock(state: &Mutex<ServerState>) -> MutexGuard<'_, ServerState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn handle_client(stream: TcpStream, state: Arc<Mutex<ServerState>>) -> io::Result<()> {
+    let connection = {
+        let mut state = lock(&state);
+        state.next_connection += 1;
+        state.next_connection
+    };
+
+    // Everything bound for this client goes through one writer thread, so
+    // pushes triggered by other players never interleave with replies.
+    let (tx, rx) = mpsc::channel::<ServerMessage>();
+    let mut writer = stream.try_clone()?;
+    thread::spawn(move || {
+        for msg in rx {
+            if write_message(&mut writer, &msg).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut reader = BufReader::new(stream);
+    let mut name: Option<String> = None;
+
+    loop {
+        match read_message::<_, ClientMessage>(&mut reader) {
+            Ok(Some(msg)) => handle_message(&state, &tx, connection, &mut name, msg),
+            Ok(None) => break,
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                let _ = tx.send(error(ServerError::BadMessage {
+                    message: e.to_string(),
+                }));
+            }
+            Err(_) => break,
+        }
+    }
+
+    if let Some(name) = name {
+        let mut state = lock(&state);
+        // Only forget the player if a newer connection hasn't replaced this one.
+        if state
+            .clients
+            .get(&name)
+            .is_some_and(|c| c.connection == connection)
+        {
+            state.clients.remove(&name);
+            state.lobby.cancel_seek(&name);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn serve(listener: TcpListener, config: ServerConfig) -> io::Result<()> {
+    let state = Arc::new(Mutex::new(ServerState {
+        data_dir: config.data_dir,
+        rating_mode: config.rating_mode,
+        next_connection: 0,
+        lobby: Lobby::new(config.game, config.time_control),
+        tokens: HashMap::new(),
+        clients: HashMap::new(),
+    }));
+
+    let clocks = Arc::clone(&state);
+    thread::spawn(move || {
+        loop {
+            thread::sleep(CLOCK_TICK);
+            lock(&clocks).check_clocks();
+        }
+    });
+
+    for stream in listener.incoming() {
+        // One connection that couldn't be set up mustn't take every game
+        // in progress down with it.
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("could not accept a connection: {e}");
+                thread::sleep(ACCEPT_BACKOFF);
+                continue;
+            }
+        };
+        let state = Arc::clone(&state);
+        thread::spawn(move || {
+            let peer = stream.peer_addr().ok();
+            if let Err(e) = handle_client(stream, state) {
+                eprintln!("connection {:?} closed: {e}", peer);
+            }
+        });
+    }
+
+    Ok(())
+}
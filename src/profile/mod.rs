@@ -0,0 +1,138 @@
+pub mod rating;
+pub mod stats;
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use self::rating::{Rating, RatingMode, rating_deltas};
+use self::stats::Stats;
+use crate::error::SaveError;
+use crate::game::Game;
+use crate::save::file::{invalid_data, read_checked, write_checked};
+
+// Kept out of the top-level data directory, which only holds game saves.
+const PROFILES_DIR: &str = "profiles";
+const PROFILES_FILE: &str = "players.ron";
+const PROFILES_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub stats: Stats,
+    #[serde(default)]
+    pub rating: Rating,
+}
+
+impl Profile {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            is_bot: false,
+            stats: Stats::default(),
+            rating: Rating::default(),
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ProfileStore {
+    profiles: BTreeMap<String, Profile>,
+}
+
+fn store_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(PROFILES_DIR).join(PROFILES_FILE)
+}
+
+impl ProfileStore {
+    pub fn load(data_dir: &Path) -> io::Result<Self> {
+        let path = store_path(data_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let (version, body) = read_checked(&path)?;
+        if version != PROFILES_VERSION {
+            return Err(invalid_data(SaveError::UnsupportedVersion { version }));
+        }
+        ron::from_str::<Self>(&body).map_err(|e| invalid_data(SaveError::Parse(format!("{e}"))))
+    }
+
+    pub fn save(&self, data_dir: &Path) -> io::Result<()> {
+        let body = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .expect("serialize ProfileStore to RON");
+        fs::create_dir_all(data_dir.join(PROFILES_DIR))?;
+        write_checked(&store_path(data_dir), PROFILES_VERSION, &body)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+
+    pub fn get_or_create(&mut self, name: &str) -> &mut Profile {
+        self.profiles
+            .entry(name.to_string())
+            .or_insert_with(|| Profile::new(name.to_string()))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Profile> {
+        self.profiles.values()
+    }
+
+    pub fn leaderboard(&self) -> Vec<&Profile> {
+        let mut ranked: Vec<&Profile> = self
+            .profiles
+            .values()
+            .filter(|p| p.rating.games > 0)
+            .collect();
+        ranked.sort_by(|a, b| b.rating.value.total_cmp(&a.rating.value));
+        ranked
+    }
+
+    // A game between computer players: same as any other, but their
+    // profiles are marked as bots.
+    pub fn record_bot_game(&mut self, game: &Game, mode: RatingMode, at_unix: i64) {
+        for player in &game.players {
+            self.get_or_create(&player.name).is_bot = true;
+        }
+        self.record_game(game, mode, at_unix);
+    }
+
+    pub fn record_game(&mut self, game: &Game, mode: RatingMode, at_unix: i64) {
+        let scores = game.result_scores();
+        let best = scores.iter().copied().max().unwrap_or(0);
+        let leaders = scores.iter().filter(|&&s| s == best).count();
+        let contested = game.players.len() > 1;
+
+        for (player, &result) in game.players.iter().zip(&scores) {
+            let stats = &mut self.get_or_create(&player.name).stats;
+            for record in game.history.iter().filter(|r| r.player_id == player.id) {
+                stats.record_move(record);
+            }
+            // Solo games and shared first places are not wins.
+            let won = contested && result == best && leaders == 1;
+            stats.record_game(player.score, won);
+        }
+
+        let ratings: Vec<Rating> = game
+            .players
+            .iter()
+            .map(|p| self.get_or_create(&p.name).rating.clone())
+            .collect();
+        let deltas = rating_deltas(&ratings, &scores, mode);
+
+        if game.players.len() > 1 {
+            for (player, delta) in game.players.iter().zip(deltas) {
+                self.get_or_create(&player.name)
+                    .rating
+                    .apply(delta, at_unix);
+            }
+        }
+    }
+}
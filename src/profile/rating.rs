@@ -0,0 +1,183 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+pub const INITIAL_RATING: f64 = 1500.0;
+const K_FACTOR: f64 = 32.0;
+const PROVISIONAL_K_FACTOR: f64 = 48.0;
+const PROVISIONAL_GAMES: u32 = 10;
+// A spread of this many points counts as roughly a 3:1 result.
+const SPREAD_SCALE: f64 = 100.0;
+
+// What a game counts as for the ratings: who won, or by how much.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingMode {
+    #[default]
+    WinLoss,
+    Spread,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RatingEntry {
+    pub at_unix: i64,
+    pub rating: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rating {
+    pub value: f64,
+    pub games: u32,
+    pub history: Vec<RatingEntry>,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            value: INITIAL_RATING,
+            games: 0,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl Rating {
+    pub fn is_provisional(&self) -> bool {
+        self.games < PROVISIONAL_GAMES
+    }
+
+    fn k_factor(&self) -> f64 {
+        if self.is_provisional() {
+            PROVISIONAL_K_FACTOR
+        } else {
+            K_FACTOR
+        }
+    }
+
+    pub fn apply(&mut self, delta: f64, at_unix: i64) {
+        self.value += delta;
+        self.games += 1;
+        self.history.push(RatingEntry {
+            at_unix,
+            rating: self.value,
+        });
+    }
+}
+
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+fn actual_score(score: u32, opponent: u32, mode: RatingMode) -> f64 {
+    let spread = score as f64 - opponent as f64;
+    match mode {
+        RatingMode::WinLoss => match spread.partial_cmp(&0.0) {
+            Some(std::cmp::Ordering::Greater) => 1.0,
+            Some(std::cmp::Ordering::Less) => 0.0,
+            _ => 0.5,
+        },
+        RatingMode::Spread => 1.0 / (1.0 + (-spread / SPREAD_SCALE).exp()),
+    }
+}
+
+// Every player is compared with every other one, and the K factor is shared
+// between the pairings so a four-player game doesn't move ratings three
+// times as much as a duel.
+pub fn rating_deltas(ratings: &[Rating], scores: &[u32], mode: RatingMode) -> Vec<f64> {
+    let n = ratings.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+
+    (0..n)
+        .map(|i| {
+            let total: f64 = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| {
+                    actual_score(scores[i], scores[j], mode)
+                        - expected_score(ratings[i].value, ratings[j].value)
+                })
+                .sum();
+            ratings[i].k_factor() * total / (n - 1) as f64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated(value: f64, games: u32) -> Rating {
+        Rating {
+            value,
+            games,
+            history: Vec::new(),
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn expected_score_follows_the_logistic_curve() {
+        assert_eq!(expected_score(1500.0, 1500.0), 0.5);
+        // 400 points apart is 10:1.
+        assert!(close(expected_score(1900.0, 1500.0), 10.0 / 11.0));
+        assert!(close(expected_score(1600.0, 1400.0), 0.76));
+        assert!(close(
+            expected_score(1600.0, 1400.0) + expected_score(1400.0, 1600.0),
+            1.0
+        ));
+    }
+
+    #[test]
+    fn equal_ratings_win_half_the_k_factor() {
+        let ratings = [rated(1500.0, 20), rated(1500.0, 20)];
+        let deltas = rating_deltas(&ratings, &[400, 300], RatingMode::WinLoss);
+        assert_eq!(deltas, [16.0, -16.0]);
+
+        let deltas = rating_deltas(&ratings, &[350, 350], RatingMode::WinLoss);
+        assert_eq!(deltas, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn provisional_ratings_move_faster() {
+        let ratings = [rated(1500.0, 0), rated(1500.0, PROVISIONAL_GAMES)];
+        let deltas = rating_deltas(&ratings, &[400, 300], RatingMode::WinLoss);
+        assert_eq!(deltas, [24.0, -16.0]);
+        assert!(ratings[0].is_provisional());
+        assert!(!ratings[1].is_provisional());
+    }
+
+    #[test]
+    fn unequal_ratings_reward_the_upset() {
+        let ratings = [rated(1600.0, 20), rated(1400.0, 20)];
+        let expected = expected_score(1600.0, 1400.0);
+
+        let favorite_wins = rating_deltas(&ratings, &[400, 300], RatingMode::WinLoss);
+        assert!(close(favorite_wins[0], 32.0 * (1.0 - expected)));
+        assert!(close(favorite_wins[0], 7.69));
+        assert!(close(favorite_wins[1], -7.69));
+
+        let upset = rating_deltas(&ratings, &[300, 400], RatingMode::WinLoss);
+        assert!(close(upset[0], -24.31));
+        assert!(close(upset[1], 24.31));
+    }
+
+    #[test]
+    fn spread_mode_scales_with_the_margin() {
+        let ratings = [rated(1500.0, 20), rated(1500.0, 20)];
+        let narrow = rating_deltas(&ratings, &[310, 300], RatingMode::Spread);
+        let wide = rating_deltas(&ratings, &[500, 300], RatingMode::Spread);
+        assert!(narrow[0] > 0.0 && narrow[0] < wide[0] && wide[0] < 16.0);
+        assert!(close(narrow[0], -narrow[1]));
+    }
+
+    #[test]
+    fn k_factor_is_shared_between_opponents() {
+        let ratings = [rated(1500.0, 20), rated(1500.0, 20), rated(1500.0, 20)];
+        let deltas = rating_deltas(&ratings, &[400, 300, 200], RatingMode::WinLoss);
+        assert_eq!(deltas, [16.0, 0.0, -16.0]);
+    }
+}
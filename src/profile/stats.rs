@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::game::MoveRecord;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub games_played: u32,
+    pub games_won: u32,
+    pub total_score: u64,
+    pub moves_played: u32,
+    pub total_move_score: u64,
+    pub bingos: u32,
+    pub blanks_played: u32,
+    pub best_word: Option<(String, u32)>,
+    pub best_move: Option<(String, u32)>,
+    pub word_counts: BTreeMap<String, u32>,
+}
+
+fn keep_best(best: &mut Option<(String, u32)>, word: &str, score: u32) {
+    if best.as_ref().is_none_or(|(_, s)| score > *s) {
+        *best = Some((word.to_string(), score));
+    }
+}
+
+impl Stats {
+    pub fn record_move(&mut self, record: &MoveRecord) {
+        self.moves_played += 1;
+        self.total_move_score += record.score as u64;
+        self.blanks_played += record.blanks_played as u32;
+        if record.bingo {
+            self.bingos += 1;
+        }
+
+        for (word, score) in &record.words {
+            keep_best(&mut self.best_word, word, *score);
+            *self.word_counts.entry(word.clone()).or_insert(0) += 1;
+        }
+
+        // The main word comes first, so it names the move.
+        if let Some((main, _)) = record.words.first() {
+            keep_best(&mut self.best_move, main, record.score);
+        }
+    }
+
+    pub fn record_game(&mut self, score: u32, won: bool) {
+        self.games_played += 1;
+        self.total_score += score as u64;
+        if won {
+            self.games_won += 1;
+        }
+    }
+
+    pub fn average_score(&self) -> f64 {
+        if self.games_played == 0 {
+            return 0.0;
+        }
+        self.total_score as f64 / self.games_played as f64
+    }
+
+    pub fn average_move_score(&self) -> f64 {
+        if self.moves_played == 0 {
+            return 0.0;
+        }
+        self.total_move_score as f64 / self.moves_played as f64
+    }
+
+    pub fn most_used_words(&self, n: usize) -> Vec<(&str, u32)> {
+        let mut words: Vec<(&str, u32)> = self
+            .word_counts
+            .iter()
+            .map(|(word, &count)| (word.as_str(), count))
+            .collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        words.truncate(n);
+        words
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::{Direction, Position};
+
+    fn record(words: &[(&str, u32)], score: u32, bingo: bool) -> MoveRecord {
+        MoveRecord {
+            player_id: 0,
+            turn: 0,
+            pos: Position { row: 7, col: 7 },
+            dir: Direction::Across,
+            words: words.iter().map(|&(w, s)| (w.to_string(), s)).collect(),
+            score,
+            tiles_placed: 3,
+            blanks_played: 1,
+            bingo,
+            rack: Vec::new(),
+            placed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn two_games_add_up() {
+        let mut stats = Stats::default();
+        stats.record_move(&record(&[("CAT", 10)], 10, false));
+        stats.record_move(&record(&[("CATS", 12), ("AS", 4)], 16, false));
+        stats.record_game(310, true);
+        stats.record_move(&record(&[("QI", 11), ("ZAX", 30)], 41, false));
+        stats.record_move(&record(&[("RETAINS", 66)], 66, true));
+        stats.record_game(285, false);
+
+        assert_eq!((stats.games_played, stats.games_won), (2, 1));
+        assert_eq!(stats.average_score(), 297.5);
+        assert_eq!(stats.average_move_score(), 133.0 / 4.0);
+        assert_eq!((stats.bingos, stats.blanks_played), (1, 4));
+        assert_eq!(stats.best_move, Some(("RETAINS".to_string(), 66)));
+        assert_eq!(stats.best_word, Some(("RETAINS".to_string(), 66)));
+        assert_eq!(stats.most_used_words(2), [("AS", 1), ("CAT", 1)]);
+    }
+
+    #[test]
+    fn the_main_word_names_the_best_move() {
+        let mut stats = Stats::default();
+        stats.record_move(&record(&[("QI", 11), ("ZAX", 30)], 41, false));
+        assert_eq!(stats.best_move, Some(("QI".to_string(), 41)));
+        assert_eq!(stats.best_word, Some(("ZAX".to_string(), 30)));
+        assert_eq!(stats.average_score(), 0.0);
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use super::SaveFile;
+use super::migrate::{self, CURRENT_VERSION};
+use crate::error::SaveError;
+
+const HEADER_PREFIX: &str = "// krabbels-save";
+
+// FNV-1a, good enough to catch truncated or hand-mangled files.
+fn checksum(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+pub fn invalid_data(err: SaveError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+pub fn save_error(err: &io::Error) -> Option<&SaveError> {
+    err.get_ref()
+        .and_then(|inner| inner.downcast_ref::<SaveError>())
+}
+
+pub fn save_to_path(path: &Path, save: &SaveFile) -> io::Result<()> {
+    let body = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default())
+        .expect("serialize SaveFile to RON");
+    write_checked(path, CURRENT_VERSION, &body)
+}
+
+pub fn load_from_path(path: &Path) -> io::Result<SaveFile> {
+    let (version, body) = read_checked(path)?;
+    let save = migrate::upgrade(version, &body).map_err(invalid_data)?;
+    save.game
+        .verify_integrity()
+        .map_err(|e| invalid_data(SaveError::Integrity(e)))?;
+    Ok(save)
+}
+
+pub fn write_checked(path: &Path, version: u32, body: &str) -> io::Result<()> {
+    let text = format!(
+        "{} version={} checksum={:016x}\n{}",
+        HEADER_PREFIX,
+        version,
+        checksum(body),
+        body
+    );
+
+    // Write next to the target and rename over it, so a crash mid-write
+    // leaves either the old save or the new one, never half of each.
+    let tmp_path = path.with_extension("ron.tmp");
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(text.as_bytes())?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+pub fn read_checked(path: &Path) -> io::Result<(u32, String)> {
+    let text = fs::read_to_string(path)?;
+    let (version, body) = split_header(&text).map_err(invalid_data)?;
+    Ok((version, body.to_string()))
+}
+
+fn split_header(text: &str) -> Result<(u32, &str), SaveError> {
+    // Files written before the header existed are plain RON.
+    let Some(rest) = text.strip_prefix(HEADER_PREFIX) else {
+        return Ok((1, text));
+    };
+
+    let (header, body) = rest.split_once('\n').ok_or(SaveError::MalformedHeader)?;
+
+    let mut version = None;
+    let mut expected = None;
+    for field in header.split_whitespace() {
+        match field.split_once('=') {
+            Some(("version", v)) => version = v.parse::<u32>().ok(),
+            Some(("checksum", c)) => expected = u64::from_str_radix(c, 16).ok(),
+            _ => return Err(SaveError::MalformedHeader),
+        }
+    }
+
+    let (Some(version), Some(expected)) = (version, expected) else {
+        return Err(SaveError::MalformedHeader);
+    };
+
+    let found = checksum(body);
+    if found != expected {
+        return Err(SaveError::ChecksumMismatch { expected, found });
+    }
+
+    Ok((version, body))
+}
@@ -0,0 +1,454 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    SaveFile, Session, archive_save, delete_save, format_unix, list_saves, load_from_path,
+    now_unix, prompt_line, rename_save, save_error, save_to_path,
+};
+use crate::config::Settings;
+use crate::error::SaveError;
+use crate::game::Game;
+use crate::game::clock::TimeControl;
+use crate::profile::ProfileStore;
+use crate::t;
+use crate::ui::{analysis, display};
+
+const PAGE_SIZE: usize = 10;
+
+enum Command {
+    Load(usize),
+    New,
+    Rename(usize, Option<String>),
+    Delete(usize),
+    Archive(usize),
+    Analyze(usize),
+    Filter(Option<String>),
+    Stats(Option<String>),
+    Leaderboard,
+    NextPage,
+    PrevPage,
+}
+
+fn parse_index(arg: &str) -> Option<usize> {
+    arg.parse::<usize>().ok().filter(|&idx| idx >= 1)
+}
+
+fn parse_command(input: &str) -> Option<Command> {
+    let (cmd, rest) = match input.split_once(' ') {
+        Some((cmd, rest)) => (cmd, rest.trim()),
+        None => (input, ""),
+    };
+
+    match cmd.to_lowercase().as_str() {
+        "n" => Some(Command::New),
+        ">" => Some(Command::NextPage),
+        "<" => Some(Command::PrevPage),
+        "f" if rest.is_empty() => Some(Command::Filter(None)),
+        "f" => Some(Command::Filter(Some(rest.to_string()))),
+        "l" => Some(Command::Leaderboard),
+        "s" if rest.is_empty() => Some(Command::Stats(None)),
+        "s" => Some(Command::Stats(Some(rest.to_string()))),
+        "r" => {
+            let (idx, title) = match rest.split_once(' ') {
+                Some((idx, title)) => (idx, Some(title.trim().to_string())),
+                None => (rest, None),
+            };
+            parse_index(idx).map(|idx| Command::Rename(idx, title))
+        }
+        "d" => parse_index(rest).map(Command::Delete),
+        "a" => parse_index(rest).map(Command::Archive),
+        "x" => parse_index(rest).map(Command::Analyze),
+        _ => parse_index(input).map(Command::Load),
+    }
+}
+
+fn prompt_time_control() -> io::Result<Option<TimeControl>> {
+    loop {
+        let input = prompt_line(&t!("prompt-time-control"))?;
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let Some(mut control) = TimeControl::parse(&input) else {
+            println!("{}", t!("time-control-invalid"));
+            continue;
+        };
+        control.forfeit_on_timeout = confirm(&t!("prompt-forfeit"))?;
+
+        return Ok(Some(control));
+    }
+}
+
+fn create_new_game(settings: &Settings) -> io::Result<Session> {
+    let names = loop {
+        let input = if settings.players.is_empty() {
+            prompt_line(&t!("prompt-player-names"))?
+        } else {
+            let defaults = settings.players.join(", ");
+            match prompt_line(&t!(
+                "prompt-player-names-default",
+                players = defaults.as_str()
+            ))? {
+                input if input.is_empty() => defaults,
+                input => input,
+            }
+        };
+        let names: Vec<String> = input
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if !names.is_empty() {
+            break names;
+        }
+    };
+
+    // A clock set in the config or on the command line isn't asked for.
+    let time_control = match settings.time_control {
+        Some(control) => Some(control),
+        None => prompt_time_control()?,
+    };
+
+    let mut game = Game::with_options(&settings.game);
+    for name in names {
+        game.add_player(name);
+    }
+    if let Some(control) = time_control {
+        game.set_time_control(control);
+    }
+
+    let now = now_unix();
+    let save = SaveFile {
+        title: format!("game_{}", now),
+        created_at_unix: now,
+        updated_at_unix: now,
+        game,
+    };
+
+    let filename = format!("game_{}.ron", now);
+    let path = settings.data_dir.join(filename);
+
+    save_to_path(&path, &save)?;
+    println!("{}", t!("save-created", path = path.display().to_string()));
+
+    Ok(Session { path, save })
+}
+
+fn load_entries(
+    save_dir: &Path,
+    filter: Option<&str>,
+) -> io::Result<Vec<(PathBuf, io::Result<SaveFile>)>> {
+    let mut entries: Vec<(PathBuf, io::Result<SaveFile>)> = list_saves(save_dir)?
+        .into_iter()
+        .map(|path| {
+            let loaded = load_from_path(&path);
+            (path, loaded)
+        })
+        .filter(|(_, loaded)| match (filter, loaded) {
+            (None, _) => true,
+            (Some(query), Ok(save)) => save.has_player(query),
+            (Some(_), Err(_)) => false,
+        })
+        .collect();
+
+    // Most recently played first; unreadable files sink to the bottom.
+    entries.sort_by_key(|(_, loaded)| {
+        std::cmp::Reverse(loaded.as_ref().map(|s| s.updated_at_unix).ok())
+    });
+
+    Ok(entries)
+}
+
+fn describe(save: &SaveFile) -> String {
+    let status = if save.game.game_over {
+        t!("save-status-finished")
+    } else {
+        t!("save-status-turn", turn = save.game.turn)
+    };
+
+    t!(
+        "save-summary",
+        title = &save.title,
+        scores = save.scores(),
+        status = status,
+        date = format_unix(save.updated_at_unix)
+    )
+}
+
+fn show_page(
+    entries: &[(PathBuf, io::Result<SaveFile>)],
+    page: usize,
+    page_count: usize,
+    filter: Option<&str>,
+) {
+    let title = match filter {
+        Some(query) => t!(
+            "saves-page-filtered",
+            query = query,
+            page = page + 1,
+            pages = page_count
+        ),
+        None => t!("saves-page", page = page + 1, pages = page_count),
+    };
+    println!("{}", title);
+
+    for (i, (path, loaded)) in entries
+        .iter()
+        .enumerate()
+        .skip(page * PAGE_SIZE)
+        .take(PAGE_SIZE)
+    {
+        let fname = path.file_name().and_then(|s| s.to_str()).unwrap_or("???");
+
+        match loaded {
+            Ok(save) => {
+                println!("  {}) {}", i + 1, describe(save));
+            }
+            Err(e) => {
+                // Don't spam the full parse error in the menu; keep it readable
+                let reason = t!(match save_error(e) {
+                    Some(SaveError::ChecksumMismatch { .. }) => "save-corrupted",
+                    Some(SaveError::UnsupportedVersion { .. }) => "save-too-new",
+                    Some(SaveError::Integrity(_)) => "save-inconsistent",
+                    _ => "save-unreadable",
+                });
+                println!("  {}) {} — ({})", i + 1, fname, reason);
+            }
+        }
+    }
+
+    for help in [
+        "saves-help-new",
+        "saves-help-manage",
+        "saves-help-analyze",
+        "saves-help-filter",
+        "saves-help-stats",
+    ] {
+        println!("  {}", t!(help));
+    }
+}
+
+// A game still being played isn't thrown away by mistake; files that
+// can't be read at all can go, nothing else can be done with them.
+fn deletable(loaded: &io::Result<SaveFile>) -> bool {
+    match loaded {
+        Ok(save) => save.game.game_over,
+        Err(_) => true,
+    }
+}
+
+fn confirm(prompt: &str) -> io::Result<bool> {
+    let answer = prompt_line(prompt)?;
+    // "y" works whatever the language, so muscle memory still does.
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case(&t!("answer-yes")))
+}
+
+pub fn choose_or_create_game(settings: &Settings) -> io::Result<Session> {
+    let save_dir = settings.data_dir.as_path();
+    let mut page = 0;
+    let mut filter: Option<String> = None;
+
+    loop {
+        let mut entries = load_entries(save_dir, filter.as_deref())?;
+
+        if entries.is_empty() {
+            if filter.is_none() {
+                println!("{}", t!("saves-none"));
+                return create_new_game(settings);
+            }
+            println!(
+                "{}",
+                t!("saves-no-match", query = filter.as_deref().unwrap_or(""))
+            );
+            filter = None;
+            continue;
+        }
+
+        let page_count = entries.len().div_ceil(PAGE_SIZE);
+        page = page.min(page_count - 1);
+        show_page(&entries, page, page_count, filter.as_deref());
+
+        let choice = prompt_line(&t!("prompt-choose-save"))?;
+
+        let Some(command) = parse_command(&choice) else {
+            println!("{}", t!("menu-invalid-choice"));
+            continue;
+        };
+
+        let entry_at = |idx: usize| {
+            if idx <= entries.len() {
+                Some(idx - 1)
+            } else {
+                println!("{}", t!("save-no-number", number = idx));
+                None
+            }
+        };
+
+        match command {
+            Command::New => return create_new_game(settings),
+            Command::NextPage => page = (page + 1).min(page_count - 1),
+            Command::PrevPage => page = page.saturating_sub(1),
+            Command::Filter(query) => {
+                filter = query;
+                page = 0;
+            }
+            Command::Stats(name) => {
+                let store = ProfileStore::load(save_dir)?;
+                match name {
+                    None => display::show_profiles(&store),
+                    Some(name) => match store.get(&name) {
+                        Some(profile) => display::show_profile(profile),
+                        None => println!("{}", t!("profile-unknown", name = &name)),
+                    },
+                }
+            }
+            Command::Leaderboard => {
+                display::show_leaderboard(&ProfileStore::load(save_dir)?);
+            }
+            Command::Load(idx) => {
+                let Some(i) = entry_at(idx) else { continue };
+                let (path, loaded) = entries.swap_remove(i);
+
+                match loaded {
+                    Ok(save) => {
+                        println!(
+                            "{}",
+                            t!(
+                                "save-loaded",
+                                path = path.display().to_string(),
+                                players = save.player_names()
+                            )
+                        );
+                        return Ok(Session { path, save });
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "{}",
+                            t!(
+                                "save-load-failed",
+                                path = path.display().to_string(),
+                                error = e.to_string()
+                            )
+                        );
+                        println!("{}", t!("save-pick-another"));
+                    }
+                }
+            }
+            Command::Rename(idx, title) => {
+                let Some(i) = entry_at(idx) else { continue };
+                let title = match title {
+                    Some(title) if !title.is_empty() => title,
+                    _ => prompt_line(&t!("prompt-new-title"))?,
+                };
+                if title.is_empty() {
+                    continue;
+                }
+                match rename_save(&entries[i].0, title) {
+                    Ok(()) => println!("{}", t!("save-renamed")),
+                    Err(e) => eprintln!(
+                        "{}",
+                        t!(
+                            "save-rename-failed",
+                            path = entries[i].0.display().to_string(),
+                            error = e.to_string()
+                        )
+                    ),
+                }
+            }
+            Command::Delete(idx) => {
+                let Some(i) = entry_at(idx) else { continue };
+                let (path, loaded) = &entries[i];
+                if !deletable(loaded) {
+                    println!("{}", t!("save-delete-unfinished"));
+                    continue;
+                }
+                let shown = path.display().to_string();
+                if confirm(&t!("prompt-delete", path = &shown))? {
+                    match delete_save(path) {
+                        Ok(()) => println!("{}", t!("save-deleted")),
+                        Err(e) => eprintln!(
+                            "{}",
+                            t!("save-delete-failed", path = &shown, error = e.to_string())
+                        ),
+                    }
+                }
+            }
+            Command::Analyze(idx) => {
+                let Some(i) = entry_at(idx) else { continue };
+                let save = match &entries[i] {
+                    (_, Ok(save)) => save,
+                    (path, Err(e)) => {
+                        eprintln!(
+                            "{}",
+                            t!(
+                                "save-load-failed",
+                                path = path.display().to_string(),
+                                error = e.to_string()
+                            )
+                        );
+                        continue;
+                    }
+                };
+                let Some(lexicon) = settings.game.lexicon.as_deref() else {
+                    println!("{}", t!("no-dictionary"));
+                    continue;
+                };
+                let Some(report) = analysis::show(&save.game, lexicon) else {
+                    continue;
+                };
+                let path = prompt_line(&t!("prompt-analysis-export"))?;
+                if !path.is_empty() {
+                    analysis::save_report(&report, Path::new(&path));
+                }
+            }
+            Command::Archive(idx) => {
+                let Some(i) = entry_at(idx) else { continue };
+                let (path, loaded) = &entries[i];
+                if !matches!(loaded, Ok(save) if save.game.game_over) {
+                    println!("{}", t!("save-archive-unfinished"));
+                    continue;
+                }
+                match archive_save(save_dir, path) {
+                    Ok(target) => println!(
+                        "{}",
+                        t!("save-archived", path = target.display().to_string())
+                    ),
+                    Err(e) => eprintln!(
+                        "{}",
+                        t!(
+                            "save-archive-failed",
+                            path = path.display().to_string(),
+                            error = e.to_string()
+                        )
+                    ),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn save(game_over: bool) -> SaveFile {
+        let mut game = Game::new();
+        game.add_player("Ann".to_string());
+        game.game_over = game_over;
+        SaveFile {
+            title: "test".to_string(),
+            created_at_unix: 1,
+            updated_at_unix: 2,
+            game,
+        }
+    }
+
+    #[test]
+    fn only_finished_or_unreadable_saves_can_be_deleted() {
+        assert!(deletable(&Ok(save(true))));
+        assert!(!deletable(&Ok(save(false))));
+        assert!(deletable(&Err(io::Error::other("unreadable"))));
+    }
+}
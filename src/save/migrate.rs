@@ -0,0 +1,76 @@
+use serde::Deserialize;
+
+use super::SaveFile;
+use crate::error::SaveError;
+use crate::game::Game;
+
+pub const CURRENT_VERSION: u32 = 3;
+
+// Frozen copies of older formats. Never edit these: add a new version
+// and a step in `upgrade` instead.
+mod v1 {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct PlayerData {
+        pub name: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct SaveFile {
+        pub created_at_unix: i64,
+        pub player: PlayerData,
+    }
+}
+
+mod v2 {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct PlayerData {
+        pub name: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct SaveFile {
+        pub created_at_unix: i64,
+        pub players: Vec<PlayerData>,
+    }
+}
+
+fn parse<'a, T: Deserialize<'a>>(body: &'a str) -> Result<T, SaveError> {
+    ron::from_str::<T>(body).map_err(|e| SaveError::Parse(format!("{e}")))
+}
+
+fn v1_to_v2(old: v1::SaveFile) -> v2::SaveFile {
+    v2::SaveFile {
+        created_at_unix: old.created_at_unix,
+        players: vec![v2::PlayerData {
+            name: old.player.name,
+        }],
+    }
+}
+
+// v2 only knew the player names, so the game starts over from a fresh bag.
+fn v2_to_v3(old: v2::SaveFile) -> SaveFile {
+    let mut game = Game::new();
+    for player in old.players {
+        game.add_player(player.name);
+    }
+
+    SaveFile {
+        title: format!("game_{}", old.created_at_unix),
+        created_at_unix: old.created_at_unix,
+        updated_at_unix: old.created_at_unix,
+        game,
+    }
+}
+
+pub fn upgrade(version: u32, body: &str) -> Result<SaveFile, SaveError> {
+    match version {
+        1 => parse::<v1::SaveFile>(body).map(v1_to_v2).map(v2_to_v3),
+        2 => parse::<v2::SaveFile>(body).map(v2_to_v3),
+        CURRENT_VERSION => parse::<SaveFile>(body),
+        _ => Err(SaveError::UnsupportedVersion { version }),
+    }
+}
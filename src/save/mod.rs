@@ -1,4 +1,7 @@
-use ron;
+pub mod file;
+mod manager;
+mod migrate;
+
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
@@ -7,19 +10,66 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct PlayerData {
-    name: String,
+use self::file::{load_from_path, save_error, save_to_path};
+use crate::config::Settings;
+use crate::game::Game;
+use crate::t;
+
+pub const DEFAULT_DATA_DIR: &str = "./data";
+const ARCHIVE_DIR: &str = "archive";
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub title: String,
+    pub created_at_unix: i64,
+    pub updated_at_unix: i64,
+    pub game: Game,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct SaveFile {
-    created_at_unix: i64,
-    player: PlayerData,
+impl SaveFile {
+    fn player_names(&self) -> String {
+        self.game
+            .players
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn scores(&self) -> String {
+        self.game
+            .players
+            .iter()
+            .map(|p| format!("{} {}", p.name, p.score))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn has_player(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.game
+            .players
+            .iter()
+            .any(|p| p.name.to_lowercase().contains(&query))
+    }
+}
+
+pub struct Session {
+    pub path: PathBuf,
+    pub save: SaveFile,
+}
+
+impl Session {
+    // Also charges the player on the move for the time spent so far.
+    pub fn store(&mut self) -> io::Result<()> {
+        self.save.game.charge_clock();
+        self.save.updated_at_unix = now_unix();
+        save_to_path(&self.path, &self.save)
+    }
 }
 
 fn prompt_line(prompt: &str) -> io::Result<String> {
-    print!("{}", prompt);
+    print!("{} ", prompt);
     io::stdout().flush()?;
 
     let mut s = String::new();
@@ -27,13 +77,39 @@ fn prompt_line(prompt: &str) -> io::Result<String> {
     Ok(s.trim().to_string())
 }
 
-fn now_unix() -> i64 {
+pub fn now_unix() -> i64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as i64
 }
 
+// UTC "YYYY-MM-DD HH:MM", using the days-to-civil conversion so we don't
+// need a date crate for a single label.
+pub fn format_unix(ts: i64) -> String {
+    let days = ts.div_euclid(86_400);
+    let secs = ts.rem_euclid(86_400);
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
 fn ensure_dir(path: &Path) -> io::Result<()> {
     fs::create_dir_all(path)
 }
@@ -59,112 +135,91 @@ fn list_saves(save_dir: &Path) -> io::Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-fn save_to_path(path: &Path, save: &SaveFile) -> io::Result<()> {
-    let ron_text = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default())
-        .expect("serialize SaveFile to RON");
-    fs::write(path, ron_text)?;
-    Ok(())
+fn rename_save(path: &Path, title: String) -> io::Result<()> {
+    let mut save = load_from_path(path)?;
+    save.title = title;
+    save_to_path(path, &save)
 }
 
-fn load_from_path(path: &Path) -> io::Result<SaveFile> {
-    let text = fs::read_to_string(path)?;
-    ron::from_str::<SaveFile>(&text)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{e}")))
+fn delete_save(path: &Path) -> io::Result<()> {
+    fs::remove_file(path)
 }
 
-fn create_new_game(save_dir: &Path) -> io::Result<(PathBuf, SaveFile)> {
-    let name = prompt_line("Enter your name: ")?;
-    let save = SaveFile {
-        created_at_unix: now_unix(),
-        player: PlayerData { name },
-    };
+fn archive_save(save_dir: &Path, path: &Path) -> io::Result<PathBuf> {
+    let archive_dir = save_dir.join(ARCHIVE_DIR);
+    ensure_dir(&archive_dir)?;
 
-    let filename = format!("game_{}.ron", save.created_at_unix);
-    let path = save_dir.join(filename);
-
-    save_to_path(&path, &save)?;
-    println!("Created new game: {}", path.display());
-
-    Ok((path, save))
+    let target = archive_dir.join(path.file_name().unwrap_or_default());
+    fs::rename(path, &target)?;
+    Ok(target)
 }
 
-fn choose_or_create_game(save_dir: &Path) -> io::Result<(PathBuf, SaveFile)> {
-    let saves = list_saves(save_dir)?;
+pub fn load_game_state(settings: &Settings) -> io::Result<Session> {
+    ensure_dir(&settings.data_dir)?;
 
-    if saves.is_empty() {
-        println!("No saved games found. Starting fresh!");
-        return create_new_game(save_dir);
-    }
+    let mut session = manager::choose_or_create_game(settings)?;
+    // The word list isn't saved with the game; the current one applies.
+    session.save.game.lexicon = settings.game.lexicon.clone();
 
-    let shown = saves.len().min(5);
+    println!(
+        "{}",
+        t!("current-players", players = session.save.player_names())
+    );
 
-    let mut entries: Vec<(PathBuf, Result<SaveFile, String>)> = Vec::with_capacity(shown);
+    Ok(session)
+}
 
-    for path in saves.iter().take(shown) {
-        let loaded = match load_from_path(path) {
-            Ok(save) => Ok(save),
-            Err(e) => Err(format!("{e}")),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_save(dir: &Path, name: &str) -> PathBuf {
+        let mut game = Game::new();
+        game.add_player("Ann".to_string());
+        let save = SaveFile {
+            title: name.to_string(),
+            created_at_unix: 1,
+            updated_at_unix: 2,
+            game,
         };
-        entries.push((path.clone(), loaded));
-    }
-
-    println!("Saved games:");
-    for (i, (path, loaded)) in entries.iter().enumerate() {
-        let fname = path.file_name().and_then(|s| s.to_str()).unwrap_or("???");
-
-        match loaded {
-            Ok(save) => {
-                // For now: single player name
-                println!("  {}) {} — player: {}", i + 1, fname, save.player.name);
-            }
-            Err(_) => {
-                // Don't spam the full parse error in the menu; keep it readable
-                println!("  {}) {} — (could not load)", i + 1, fname);
-            }
-        }
+        let path = dir.join(name);
+        save_to_path(&path, &save).unwrap();
+        path
     }
-    println!("  n) Start a new game");
 
-    loop {
-        let choice = prompt_line("Choose a save (1..), or 'n': ")?;
+    #[test]
+    fn list_keeps_save_files_newest_name_first() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(list_saves(&dir.path().join("missing")).unwrap().is_empty());
 
-        if choice.eq_ignore_ascii_case("n") {
-            return create_new_game(save_dir);
-        }
-
-        if let Ok(idx) = choice.parse::<usize>() {
-            if idx >= 1 && idx <= shown {
-                let (path, loaded) = &entries[idx - 1];
-
-                match loaded {
-                    Ok(save) => {
-                        println!(
-                            "Loaded game: {} (player: {})",
-                            path.display(),
-                            save.player.name
-                        );
-                        return Ok((path.clone(), save.clone()));
-                    }
-                    Err(e) => {
-                        eprintln!("Could not load {}: {e}", path.display());
-                        println!("Pick another save or choose 'n' for new.");
-                    }
-                }
-            }
-        }
+        let older = write_save(dir.path(), "game_100.ron");
+        let newer = write_save(dir.path(), "game_200.ron");
+        fs::write(dir.path().join("notes.txt"), "not a save").unwrap();
+        fs::create_dir(dir.path().join(ARCHIVE_DIR)).unwrap();
 
-        println!("Invalid choice.");
+        assert_eq!(list_saves(dir.path()).unwrap(), [newer, older]);
     }
-}
 
-pub fn load_game_state() -> io::Result<()> {
-    let data_dir = PathBuf::from("./data");
+    #[test]
+    fn archive_moves_the_save_out_of_the_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_save(dir.path(), "game_100.ron");
 
-    ensure_dir(&data_dir)?;
-
-    let (_path, save) = choose_or_create_game(&data_dir)?;
+        let target = archive_save(dir.path(), &path).unwrap();
+        assert_eq!(target, dir.path().join(ARCHIVE_DIR).join("game_100.ron"));
+        assert!(!path.exists());
+        assert_eq!(load_from_path(&target).unwrap().title, "game_100.ron");
+        assert!(list_saves(dir.path()).unwrap().is_empty());
+    }
 
-    println!("Current player: {}", save.player.name);
+    #[test]
+    fn delete_removes_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_save(dir.path(), "game_100.ron");
+        let kept = write_save(dir.path(), "game_200.ron");
 
-    Ok(())
+        delete_save(&path).unwrap();
+        assert_eq!(list_saves(dir.path()).unwrap(), [kept]);
+        assert!(delete_save(&path).is_err());
+    }
 }
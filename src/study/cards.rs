@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use super::search::draw_probability;
+use crate::error::SaveError;
+use crate::game::bag::TileSet;
+use crate::game::lexicon::Lexicon;
+use crate::save::file::{invalid_data, read_checked, write_checked};
+
+// Next to the profiles, out of the game saves' way.
+const STUDY_DIR: &str = "study";
+const CARDS_FILE: &str = "cards.ron";
+const CARDS_VERSION: u32 = 1;
+const DAY: i64 = 86_400;
+
+// A card reviewed at this interval or longer counts as learnt.
+pub const MASTERED_DAYS: u32 = 21;
+const START_EASINESS: f64 = 2.5;
+const MIN_EASINESS: f64 = 1.3;
+
+// The letters of a word in alphabetical order: AEINRST for ENTRAIS.
+pub fn alphagram(word: &str) -> String {
+    let mut letters: Vec<char> = word.trim().to_uppercase().chars().collect();
+    letters.sort_unstable();
+    letters.into_iter().collect()
+}
+
+// Which alphagrams a quiz draws its new cards from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuizSource {
+    // Every alphagram of that length, in random order.
+    Length(usize),
+    // Ranks `from..=to` (from 1) by how likely the tiles are to be drawn,
+    // most likely first.
+    Probability {
+        length: usize,
+        from: usize,
+        to: usize,
+    },
+    // Words or alphagrams picked by the player, in their order.
+    List(Vec<String>),
+}
+
+// Alphagram -> the words it spells, built once from the lexicon.
+pub struct AnagramIndex {
+    groups: HashMap<String, Vec<String>>,
+}
+
+impl AnagramIndex {
+    pub fn new(lexicon: &Lexicon) -> Self {
+        let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+        for word in lexicon.words() {
+            groups
+                .entry(alphagram(word))
+                .or_default()
+                .push(word.to_string());
+        }
+        for words in groups.values_mut() {
+            words.sort();
+        }
+        Self { groups }
+    }
+
+    pub fn solutions(&self, alphagram: &str) -> &[String] {
+        self.groups.get(alphagram).map_or(&[], Vec::as_slice)
+    }
+
+    // Alphagrams with at least one word, in the order the source asks for.
+    pub fn select(&self, source: &QuizSource, tile_set: TileSet) -> Vec<String> {
+        let of_length = |length: usize| -> Vec<String> {
+            let mut found: Vec<String> = self
+                .groups
+                .keys()
+                .filter(|a| a.chars().count() == length)
+                .cloned()
+                .collect();
+            found.sort();
+            found
+        };
+        match source {
+            QuizSource::Length(length) => {
+                let mut found = of_length(*length);
+                found.shuffle(&mut rand::rng());
+                found
+            }
+            QuizSource::Probability { length, from, to } => {
+                let from = (*from).max(1);
+                let mut ranked: Vec<(f64, String)> = of_length(*length)
+                    .into_iter()
+                    .map(|a| (draw_probability(&a, tile_set), a))
+                    .collect();
+                ranked.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+                ranked
+                    .into_iter()
+                    .skip(from - 1)
+                    .take((to + 1).saturating_sub(from))
+                    .map(|(_, a)| a)
+                    .collect()
+            }
+            QuizSource::List(entries) => {
+                let mut seen = HashSet::new();
+                entries
+                    .iter()
+                    .map(|entry| alphagram(entry))
+                    .filter(|a| self.groups.contains_key(a) && seen.insert(a.clone()))
+                    .collect()
+            }
+        }
+    }
+}
+
+// SM-2 grade from 0 to 5: right answers are graded by speed, wrong ones by
+// whether any word was found.
+pub fn quality(correct: bool, partly: bool, response_ms: u64) -> u8 {
+    match (correct, partly) {
+        (true, _) if response_ms < 10_000 => 5,
+        (true, _) if response_ms < 30_000 => 4,
+        (true, _) => 3,
+        (false, true) => 1,
+        (false, false) => 0,
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Review {
+    pub at: i64,
+    pub correct: bool,
+    pub response_ms: u64,
+    pub quality: u8,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Card {
+    pub alphagram: String,
+    pub easiness: f64,
+    pub interval_days: u32,
+    // Right answers in a row.
+    pub repetitions: u32,
+    pub due: i64,
+    #[serde(default)]
+    pub reviews: Vec<Review>,
+}
+
+impl Card {
+    pub fn new(alphagram: String, now: i64) -> Self {
+        Self {
+            alphagram,
+            easiness: START_EASINESS,
+            interval_days: 0,
+            repetitions: 0,
+            due: now,
+            reviews: Vec::new(),
+        }
+    }
+
+    // SM-2: a failed card starts over the next day; a passed one comes back
+    // after 1 day, 6 days, then the last interval times its easiness.
+    pub fn review(&mut self, quality: u8, response_ms: u64, now: i64) {
+        let q = quality.min(5) as f64;
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        } else {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval_days as f64 * self.easiness).round() as u32,
+            };
+            self.repetitions += 1;
+        }
+        self.easiness =
+            (self.easiness + 0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02)).max(MIN_EASINESS);
+        self.due = now + self.interval_days as i64 * DAY;
+        self.reviews.push(Review {
+            at: now,
+            correct: quality >= 3,
+            response_ms,
+            quality,
+        });
+    }
+
+    pub fn is_mastered(&self) -> bool {
+        self.interval_days >= MASTERED_DAYS
+    }
+}
+
+// One day of study.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayProgress {
+    // Days since 1970-01-01, UTC.
+    pub day: i64,
+    pub reviews: usize,
+    pub correct: usize,
+    pub total_ms: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Summary {
+    pub cards: usize,
+    pub due: usize,
+    pub mastered: usize,
+    pub reviews: usize,
+    pub correct: usize,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct CardStore {
+    cards: BTreeMap<String, Card>,
+}
+
+fn store_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(STUDY_DIR).join(CARDS_FILE)
+}
+
+impl CardStore {
+    pub fn load(data_dir: &Path) -> io::Result<Self> {
+        let path = store_path(data_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let (version, body) = read_checked(&path)?;
+        if version != CARDS_VERSION {
+            return Err(invalid_data(SaveError::UnsupportedVersion { version }));
+        }
+        ron::from_str::<Self>(&body).map_err(|e| invalid_data(SaveError::Parse(format!("{e}"))))
+    }
+
+    pub fn save(&self, data_dir: &Path) -> io::Result<()> {
+        let body = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .expect("serialize CardStore to RON");
+        fs::create_dir_all(data_dir.join(STUDY_DIR))?;
+        write_checked(&store_path(data_dir), CARDS_VERSION, &body)
+    }
+
+    pub fn get(&self, alphagram: &str) -> Option<&Card> {
+        self.cards.get(alphagram)
+    }
+
+    pub fn review(&mut self, alphagram: &str, quality: u8, response_ms: u64, now: i64) {
+        self.cards
+            .entry(alphagram.to_string())
+            .or_insert_with(|| Card::new(alphagram.to_string(), now))
+            .review(quality, response_ms, now);
+    }
+
+    // Cards of the quiz due by now, longest overdue first, then up to
+    // `new_cards` never seen; `size` in all.
+    pub fn session(
+        &self,
+        candidates: &[String],
+        size: usize,
+        new_cards: usize,
+        now: i64,
+    ) -> Vec<String> {
+        let mut due: Vec<&Card> = candidates
+            .iter()
+            .filter_map(|a| self.cards.get(a))
+            .filter(|card| card.due <= now)
+            .collect();
+        due.sort_by_key(|card| card.due);
+        let fresh = candidates
+            .iter()
+            .filter(|a| !self.cards.contains_key(*a))
+            .take(new_cards)
+            .cloned();
+        due.into_iter()
+            .map(|card| card.alphagram.clone())
+            .chain(fresh)
+            .take(size)
+            .collect()
+    }
+
+    pub fn summary(&self, now: i64) -> Summary {
+        let reviews = self.cards.values().flat_map(|card| &card.reviews);
+        Summary {
+            cards: self.cards.len(),
+            due: self.cards.values().filter(|card| card.due <= now).count(),
+            mastered: self
+                .cards
+                .values()
+                .filter(|card| card.is_mastered())
+                .count(),
+            reviews: reviews.clone().count(),
+            correct: reviews.filter(|review| review.correct).count(),
+        }
+    }
+
+    // Every day with reviews in the last `days`, oldest first.
+    pub fn progress(&self, days: i64, now: i64) -> Vec<DayProgress> {
+        let since = now.div_euclid(DAY) - days + 1;
+        let mut by_day: BTreeMap<i64, DayProgress> = BTreeMap::new();
+        for review in self.cards.values().flat_map(|card| &card.reviews) {
+            let day = review.at.div_euclid(DAY);
+            if day < since {
+                continue;
+            }
+            let entry = by_day.entry(day).or_insert_with(|| DayProgress {
+                day,
+                ..DayProgress::default()
+            });
+            entry.reviews += 1;
+            entry.correct += review.correct as usize;
+            entry.total_ms += review.response_ms;
+        }
+        by_day.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intervals(card: &mut Card, qualities: &[u8]) -> Vec<u32> {
+        qualities
+            .iter()
+            .map(|&q| {
+                card.review(q, 1_000, 0);
+                card.interval_days
+            })
+            .collect()
+    }
+
+    #[test]
+    fn intervals_go_one_six_then_grow_by_easiness() {
+        // Quality 4 leaves the easiness at 2.5.
+        let mut card = Card::new("AEINRST".to_string(), 0);
+        assert_eq!(intervals(&mut card, &[4, 4, 4, 4]), [1, 6, 15, 38]);
+        assert_eq!(card.easiness, START_EASINESS);
+        assert_eq!(card.repetitions, 4);
+
+        // Quality 5 adds 0.1 each time, before the next interval is worked out.
+        let mut card = Card::new("AEINRST".to_string(), 0);
+        assert_eq!(intervals(&mut card, &[5, 5, 5]), [1, 6, 16]);
+        assert!((card.easiness - 2.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_failed_card_starts_over() {
+        let mut card = Card::new("AEINRST".to_string(), 0);
+        intervals(&mut card, &[5, 5, 5]);
+        let easiness = card.easiness;
+
+        card.review(2, 40_000, 100 * DAY);
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval_days, 1);
+        assert_eq!(card.due, 101 * DAY);
+        assert!(card.easiness < easiness);
+        assert!(!card.reviews.last().unwrap().correct);
+
+        assert_eq!(intervals(&mut card, &[4, 4]), [1, 6]);
+    }
+
+    #[test]
+    fn easiness_never_drops_below_the_floor() {
+        let mut card = Card::new("AEINRST".to_string(), 0);
+        intervals(&mut card, &[0; 10]);
+        assert_eq!(card.easiness, MIN_EASINESS);
+
+        // Back from the floor, the easiness still multiplies the interval.
+        intervals(&mut card, &[3, 3, 3]);
+        assert_eq!(card.easiness, MIN_EASINESS);
+        assert_eq!(card.interval_days, 8);
+    }
+
+    #[test]
+    fn quality_follows_speed_and_partial_answers() {
+        assert_eq!(quality(true, true, 5_000), 5);
+        assert_eq!(quality(true, true, 20_000), 4);
+        assert_eq!(quality(true, true, 60_000), 3);
+        assert_eq!(quality(false, true, 5_000), 1);
+        assert_eq!(quality(false, false, 5_000), 0);
+    }
+}
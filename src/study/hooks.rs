@@ -0,0 +1,190 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::game::bag::TileSet;
+use crate::game::board::{BOARD_SIZE, Board, Position};
+use crate::game::lexicon::Lexicon;
+use crate::game::unseen::BLANK;
+
+// How a word grows into others.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hooks {
+    pub word: String,
+    // Letters that make a word in front of it: S for -OUS.
+    pub front: Vec<char>,
+    // And behind it: S for CHAT.
+    pub back: Vec<char>,
+    // One letter slipped inside: CHANT from CHAT.
+    pub inner: Vec<String>,
+    // Longer words with it inside, two letters or more added; shortest first.
+    pub longer: Vec<String>,
+}
+
+fn alphabet(tile_set: TileSet) -> impl Iterator<Item = char> {
+    tile_set
+        .distribution()
+        .iter()
+        .map(|&(letter, _, _)| letter)
+        .filter(|&letter| letter != BLANK)
+}
+
+pub fn find_hooks(word: &str, lexicon: &Lexicon, tile_set: TileSet) -> Hooks {
+    let word = word.trim().to_uppercase();
+    let letters: Vec<char> = word.chars().collect();
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    let mut inner = BTreeSet::new();
+    for letter in alphabet(tile_set) {
+        if lexicon.contains(&format!("{}{}", letter, word)) {
+            front.push(letter);
+        }
+        if lexicon.contains(&format!("{}{}", word, letter)) {
+            back.push(letter);
+        }
+        for at in 1..letters.len() {
+            let candidate: String = letters[..at]
+                .iter()
+                .chain([&letter])
+                .chain(&letters[at..])
+                .collect();
+            if lexicon.contains(&candidate) {
+                inner.insert(candidate);
+            }
+        }
+    }
+
+    let length = letters.len();
+    let mut longer: Vec<String> = lexicon
+        .words()
+        .filter(|w| w.chars().count() > length + 1 && w.contains(word.as_str()))
+        .map(str::to_string)
+        .collect();
+    longer.sort_by(|a, b| a.chars().count().cmp(&b.chars().count()).then(a.cmp(b)));
+
+    Hooks {
+        word,
+        front,
+        back,
+        inner: inner.into_iter().collect(),
+        longer,
+    }
+}
+
+fn letter_at(board: &Board, row: isize, col: isize) -> Option<char> {
+    if row < 0 || col < 0 || row >= BOARD_SIZE as isize || col >= BOARD_SIZE as isize {
+        return None;
+    }
+    board.cells[row as usize][col as usize]
+        .letter
+        .map(|ch| ch.to_ascii_uppercase())
+}
+
+// The letters next to `pos`, walking away from it by (dr, dc).
+fn run_from(board: &Board, pos: Position, (dr, dc): (isize, isize)) -> Vec<char> {
+    let mut run = Vec::new();
+    let (mut row, mut col) = (pos.row as isize + dr, pos.col as isize + dc);
+    while let Some(letter) = letter_at(board, row, col) {
+        run.push(letter);
+        row += dr;
+        col += dc;
+    }
+    run
+}
+
+// For every empty square at either end of a word on the board, the letters
+// that could go there alone: every word they would touch has to be valid.
+pub fn board_hooks(
+    board: &Board,
+    lexicon: &Lexicon,
+    tile_set: TileSet,
+) -> BTreeMap<Position, Vec<char>> {
+    let mut hooks = BTreeMap::new();
+    for row in 0..BOARD_SIZE {
+        for col in 0..BOARD_SIZE {
+            if board.cells[row][col].letter.is_some() {
+                continue;
+            }
+            let pos = Position { row, col };
+            // Letters before and after the square, across then down.
+            let lines: Vec<(Vec<char>, Vec<char>)> = [(0, 1), (1, 0)]
+                .into_iter()
+                .map(|(dr, dc)| {
+                    let mut before = run_from(board, pos, (-dr, -dc));
+                    before.reverse();
+                    (before, run_from(board, pos, (dr, dc)))
+                })
+                .collect();
+            if !lines
+                .iter()
+                .any(|(before, after)| before.len() >= 2 || after.len() >= 2)
+            {
+                continue;
+            }
+            let letters: Vec<char> = alphabet(tile_set)
+                .filter(|&letter| {
+                    lines
+                        .iter()
+                        .filter(|(before, after)| !before.is_empty() || !after.is_empty())
+                        .all(|(before, after)| {
+                            let word: String =
+                                before.iter().chain([&letter]).chain(after).collect();
+                            lexicon.contains(&word)
+                        })
+                })
+                .collect();
+            if !letters.is_empty() {
+                hooks.insert(pos, letters);
+            }
+        }
+    }
+    hooks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon() -> Lexicon {
+        Lexicon::from_words(["CHAT", "CHATS", "ACHAT", "ACHATS", "CHANT", "CHATONS"])
+    }
+
+    fn at(row: usize, col: usize) -> Position {
+        Position { row, col }
+    }
+
+    #[test]
+    fn hooks_at_the_front_the_back_and_inside() {
+        let hooks = find_hooks("chat", &lexicon(), TileSet::French);
+        assert_eq!(hooks.word, "CHAT");
+        assert_eq!(hooks.front, ['A']);
+        assert_eq!(hooks.back, ['S']);
+        assert_eq!(hooks.inner, ["CHANT"]);
+        // CHATS is a back hook, not a longer word.
+        assert_eq!(hooks.longer, ["ACHATS", "CHATONS"]);
+
+        assert_eq!(
+            find_hooks("ZZZ", &lexicon(), TileSet::French),
+            Hooks {
+                word: "ZZZ".to_string(),
+                ..Hooks::default()
+            }
+        );
+    }
+
+    #[test]
+    fn board_hooks_need_every_word_they_touch_valid() {
+        let mut board = Board::new();
+        for (col, letter) in (5..).zip("CHAT".chars()) {
+            board.cells[7][col].letter = Some(letter);
+        }
+        let hooks = board_hooks(&board, &lexicon(), TileSet::French);
+        assert_eq!(
+            hooks,
+            BTreeMap::from([(at(7, 4), vec!['A']), (at(7, 9), vec!['S'])])
+        );
+
+        // CHATS still works across, but SX down doesn't.
+        board.cells[8][9].letter = Some('X');
+        let hooks = board_hooks(&board, &lexicon(), TileSet::French);
+        assert_eq!(hooks, BTreeMap::from([(at(7, 4), vec!['A'])]));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cards;
+pub mod hooks;
+pub mod search;
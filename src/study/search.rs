@@ -0,0 +1,371 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::error::QueryError;
+use crate::game::bag::TileSet;
+use crate::game::lexicon::Lexicon;
+use crate::game::unseen::BLANK;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    // Longest first, then alphabetical.
+    #[default]
+    Length,
+    Alphabetical,
+    // Easiest to draw from a full bag first.
+    Probability,
+    // Highest face value first; letters taken by blanks count nothing.
+    Score,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Letter(char),
+    // `?`
+    Any,
+    // `*`, zero letters or more.
+    Run,
+    // `[AEI]`, or `[^AEI]` when negated.
+    Class { letters: Vec<char>, negated: bool },
+}
+
+// A word shape: `C?A*` is C, any letter, A, then anything.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+impl Pattern {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let invalid = || QueryError::InvalidPattern(input.to_string());
+        let mut tokens = Vec::new();
+        let mut chars = input
+            .trim()
+            .to_uppercase()
+            .chars()
+            .collect::<Vec<_>>()
+            .into_iter();
+        while let Some(ch) = chars.next() {
+            tokens.push(match ch {
+                '?' | '.' => Token::Any,
+                '*' => Token::Run,
+                '[' => {
+                    let mut letters = Vec::new();
+                    let mut negated = false;
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some('^') if letters.is_empty() && !negated => negated = true,
+                            Some(ch) if ch.is_alphabetic() => letters.push(ch),
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    if letters.is_empty() {
+                        return Err(invalid());
+                    }
+                    Token::Class { letters, negated }
+                }
+                ch if ch.is_alphabetic() => Token::Letter(ch),
+                _ => return Err(invalid()),
+            });
+        }
+        if tokens.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self { tokens })
+    }
+
+    pub fn matches(&self, word: &[char]) -> bool {
+        // reachable[i]: the tokens so far can spell the first i letters.
+        let mut reachable = vec![false; word.len() + 1];
+        reachable[0] = true;
+        for token in &self.tokens {
+            let mut next = vec![false; word.len() + 1];
+            for i in 0..=word.len() {
+                if !reachable[i] {
+                    continue;
+                }
+                match token {
+                    Token::Run => next[i..].iter_mut().for_each(|r| *r = true),
+                    _ if i == word.len() => {}
+                    Token::Any => next[i + 1] = true,
+                    Token::Letter(ch) => next[i + 1] |= word[i] == *ch,
+                    Token::Class { letters, negated } => {
+                        next[i + 1] |= letters.contains(&word[i]) != *negated
+                    }
+                }
+            }
+            reachable = next;
+        }
+        reachable[word.len()]
+    }
+}
+
+// Rack letters as players type them, `?` or `_` for a blank.
+pub fn parse_letters(input: &str) -> Result<Vec<char>, QueryError> {
+    input
+        .trim()
+        .to_uppercase()
+        .chars()
+        .map(|ch| match ch {
+            '?' | '_' => Ok(BLANK),
+            ch if ch.is_alphabetic() => Ok(ch),
+            _ => Err(QueryError::InvalidLetters(input.to_string())),
+        })
+        .collect()
+}
+
+// Everything set has to hold; an empty query lists the whole lexicon.
+#[derive(Clone, Debug, Default)]
+pub struct WordQuery {
+    // Tiles the word is made from, blanks as '_'.
+    pub rack: Option<Vec<char>>,
+    // Use every tile of the rack (anagrams) rather than some (sub-anagrams).
+    pub whole_rack: bool,
+    pub pattern: Option<Pattern>,
+    // Letters found anywhere in the word, repeats counted: "EE" needs two.
+    pub contains: Vec<char>,
+    pub starts: String,
+    pub ends: String,
+    pub min_length: usize,
+    pub max_length: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Found {
+    pub word: String,
+    // Letters played with a blank, by index in the word.
+    pub blanks: Vec<usize>,
+    // Face value of the tiles used.
+    pub score: u32,
+    // Chance that a draw of as many tiles from a full bag spells the word.
+    pub probability: f64,
+}
+
+impl Found {
+    // Blank letters in lowercase, as on the board.
+    pub fn display_word(&self) -> String {
+        self.word
+            .chars()
+            .enumerate()
+            .map(|(i, ch)| {
+                if self.blanks.contains(&i) {
+                    ch.to_lowercase().next().unwrap_or(ch)
+                } else {
+                    ch
+                }
+            })
+            .collect()
+    }
+}
+
+fn letter_counts(letters: impl IntoIterator<Item = char>) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for ch in letters {
+        *counts.entry(ch).or_insert(0) += 1;
+    }
+    counts
+}
+
+// Which letters need a blank, or None when the rack can't make the word.
+fn blanks_needed(word: &[char], rack: &HashMap<char, usize>) -> Option<Vec<usize>> {
+    let mut left = rack.clone();
+    let mut blanks = Vec::new();
+    for (i, ch) in word.iter().enumerate() {
+        match left.get_mut(ch) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => match left.get_mut(&BLANK) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    blanks.push(i);
+                }
+                _ => return None,
+            },
+        }
+    }
+    Some(blanks)
+}
+
+fn choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+// Draws of `word.len()` tiles that spell the word, blanks standing in for
+// any letter, over all the draws of that size.
+pub fn draw_probability(word: &str, tile_set: TileSet) -> f64 {
+    let dist = tile_set.distribution();
+    let count_of = |letter: char| {
+        dist.iter()
+            .find(|&&(ch, _, _)| ch == letter)
+            .map_or(0, |&(_, count, _)| count as usize)
+    };
+    let needed = letter_counts(word.chars());
+    let length: usize = needed.values().sum();
+
+    // ways[b]: ways to draw the real tiles when b letters are left to blanks.
+    let mut ways = vec![0.0; length + 1];
+    ways[0] = 1.0;
+    for (&letter, &need) in &needed {
+        let available = count_of(letter);
+        let mut next = vec![0.0; length + 1];
+        for (b, &w) in ways.iter().enumerate().filter(|(_, w)| **w > 0.0) {
+            for by_blank in 0..=need.min(length - b) {
+                next[b + by_blank] += w * choose(available, need - by_blank);
+            }
+        }
+        ways = next;
+    }
+    let blanks = count_of(BLANK);
+    let draws: f64 = ways
+        .iter()
+        .enumerate()
+        .map(|(b, w)| w * choose(blanks, b))
+        .sum();
+    draws / choose(tile_set.tile_count(), length)
+}
+
+impl WordQuery {
+    fn accepts(&self, word: &[char]) -> bool {
+        let length = word.len();
+        length >= self.min_length
+            && self.max_length.is_none_or(|max| length <= max)
+            && self.pattern.as_ref().is_none_or(|p| p.matches(word))
+            && word.starts_with(&self.starts.chars().collect::<Vec<_>>())
+            && word.ends_with(&self.ends.chars().collect::<Vec<_>>())
+            && letter_counts(self.contains.iter().copied())
+                .iter()
+                .all(|(ch, &n)| word.iter().filter(|&c| c == ch).count() >= n)
+    }
+
+    pub fn search(&self, lexicon: &Lexicon, tile_set: TileSet) -> Vec<Found> {
+        let rack = self
+            .rack
+            .as_ref()
+            .map(|tiles| letter_counts(tiles.iter().copied()));
+        let rack_size = self.rack.as_ref().map_or(0, Vec::len);
+        lexicon
+            .words()
+            .filter_map(|word| {
+                let letters: Vec<char> = word.chars().collect();
+                if !self.accepts(&letters) {
+                    return None;
+                }
+                let blanks = match &rack {
+                    Some(_) if self.whole_rack && letters.len() != rack_size => return None,
+                    Some(rack) => blanks_needed(&letters, rack)?,
+                    None => Vec::new(),
+                };
+                let score = letters
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !blanks.contains(i))
+                    .map(|(_, &ch)| tile_set.letter_value(ch))
+                    .sum();
+                Some(Found {
+                    word: word.to_string(),
+                    blanks,
+                    score,
+                    probability: draw_probability(word, tile_set),
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn sort_words(words: &mut [Found], order: SortOrder) {
+    match order {
+        SortOrder::Length => {
+            words.sort_by_key(|f| (Reverse(f.word.chars().count()), f.word.clone()))
+        }
+        SortOrder::Alphabetical => words.sort_by(|a, b| a.word.cmp(&b.word)),
+        SortOrder::Probability => words.sort_by(|a, b| {
+            b.probability
+                .total_cmp(&a.probability)
+                .then(a.word.cmp(&b.word))
+        }),
+        SortOrder::Score => words.sort_by_key(|f| (Reverse(f.score), f.word.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    fn matches(pattern: &str, word: &str) -> bool {
+        Pattern::parse(pattern).unwrap().matches(&letters(word))
+    }
+
+    #[test]
+    fn runs_match_zero_letters_or_more() {
+        assert!(matches("C*T", "CT"));
+        assert!(matches("C*T", "CHAT"));
+        assert!(!matches("C*T", "CATS"));
+        assert!(matches("*", ""));
+        assert!(matches("**S", "S"));
+        assert!(matches("c?t", "CAT"));
+        assert!(!matches("C?T", "CT"));
+    }
+
+    #[test]
+    fn classes_match_one_letter_in_or_out_of_the_set() {
+        assert!(matches("[AEI]T", "IT"));
+        assert!(!matches("[AEI]T", "UT"));
+        assert!(matches("[^AEI]T", "UT"));
+        assert!(!matches("[^AEI]T", "AT"));
+        assert!(!matches("[^AEI]T", "T"));
+    }
+
+    #[test]
+    fn malformed_patterns_are_refused() {
+        for input in ["[AB", "C[", "[]", "[^]", "[A^B]", "C-T", "", "  "] {
+            assert_eq!(
+                Pattern::parse(input),
+                Err(QueryError::InvalidPattern(input.to_string())),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn blanks_fill_in_for_missing_letters() {
+        let rack = letter_counts(letters("CA_"));
+        assert_eq!(blanks_needed(&letters("CAT"), &rack), Some(vec![2]));
+        assert_eq!(blanks_needed(&letters("AC"), &rack), Some(vec![]));
+        assert_eq!(blanks_needed(&letters("TACT"), &rack), None);
+
+        let query = WordQuery {
+            rack: Some(parse_letters("at?").unwrap()),
+            whole_rack: true,
+            ..WordQuery::default()
+        };
+        let lexicon = Lexicon::from_words(["CAT", "ACT", "AT", "TACT"]);
+        let mut found = query.search(&lexicon, TileSet::English);
+        sort_words(&mut found, SortOrder::Alphabetical);
+        let shown: Vec<String> = found.iter().map(Found::display_word).collect();
+        assert_eq!(shown, ["AcT", "cAT"]);
+        // The blank scores nothing.
+        assert!(found.iter().all(|f| f.score == 2));
+    }
+
+    #[test]
+    fn draw_probability_counts_blanks_as_any_letter() {
+        // One Q, nine I and two blanks among 100 tiles: QI drawn for real
+        // 1 × 9 ways, with one blank 2 × (9 + 1), with both blanks 1.
+        let expected = (9.0 + 20.0 + 1.0) / 4950.0;
+        let probability = draw_probability("QI", TileSet::English);
+        assert!((probability - expected).abs() < 1e-12, "{probability}");
+        // One Q and two blanks can't make four.
+        assert_eq!(draw_probability("QQQQ", TileSet::English), 0.0);
+    }
+}
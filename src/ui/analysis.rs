@@ -0,0 +1,174 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::Game;
+use crate::game::analysis::{self, Choice, GameAnalysis, Play, TurnAnalysis};
+use crate::game::board::Direction;
+use crate::game::lexicon::Lexicon;
+use crate::t;
+
+// As typed at the move prompt: `h8 h CHAT`.
+fn describe_play(play: &Play) -> String {
+    match play {
+        Play::Place { pos, dir, word, .. } => {
+            let dir = match dir {
+                Direction::Across => 'h',
+                Direction::Down => 'v',
+            };
+            format!("{} {} {}", pos, dir, word)
+        }
+        Play::Exchange { letters } => t!("analysis-exchange", letters = letters.as_str()),
+        Play::Pass => t!("analysis-pass"),
+        Play::Withdrawn { words } => t!("analysis-withdrawn", words = words.join(", ")),
+        Play::ChallengeLost => t!("analysis-challenge-lost"),
+    }
+}
+
+fn describe_choice(choice: &Choice) -> String {
+    t!(
+        "analysis-choice",
+        play = describe_play(&choice.play),
+        score = choice.score,
+        equity = format!("{:.1}", choice.equity)
+    )
+}
+
+fn turn_lines(turn: &TurnAnalysis, name: &str, lines: &mut Vec<String>) {
+    let mark = if turn.is_top() {
+        format!("  {}", t!("analysis-top"))
+    } else {
+        String::new()
+    };
+    lines.push(format!(
+        "{:>4}. {:<12} {:<8} {}{}",
+        turn.turn,
+        name,
+        turn.rack,
+        describe_choice(&turn.played),
+        mark
+    ));
+    let indent = " ".repeat(6);
+    if let Some(best) = turn.best_score.as_ref().filter(|_| turn.points_lost > 0) {
+        lines.push(format!(
+            "{}{}",
+            indent,
+            t!(
+                "analysis-best-score",
+                play = describe_choice(best),
+                lost = turn.points_lost
+            )
+        ));
+    }
+    if !turn.is_top() {
+        lines.push(format!(
+            "{}{}",
+            indent,
+            t!(
+                "analysis-best-equity",
+                play = describe_choice(&turn.best_equity),
+                lost = format!("{:.1}", turn.equity_lost)
+            )
+        ));
+    }
+    if let Some(bingo) = &turn.missed_bingo {
+        lines.push(format!(
+            "{}{}",
+            indent,
+            t!("analysis-missed-bingo", play = describe_choice(bingo))
+        ));
+    }
+    if !turn.phonies.is_empty() {
+        lines.push(format!(
+            "{}{}",
+            indent,
+            t!("analysis-phonies", words = turn.phonies.join(", "))
+        ));
+    }
+}
+
+// The report as plain lines, the same on screen and in a file.
+pub fn report(analysis: &GameAnalysis) -> Vec<String> {
+    let name = |id: usize| {
+        analysis
+            .players
+            .iter()
+            .find(|p| p.player_id == id)
+            .map_or("?", |p| p.name.as_str())
+    };
+    let mut lines = vec![t!("analysis-title"), String::new()];
+    for turn in &analysis.turns {
+        turn_lines(turn, name(turn.player_id), &mut lines);
+    }
+    lines.push(String::new());
+    lines.push(t!("analysis-summary"));
+    for player in &analysis.players {
+        lines.push(format!(
+            "  {}",
+            t!(
+                "analysis-player",
+                name = player.name.as_str(),
+                equity = format!("{:.1}", player.equity_lost),
+                points = player.points_lost,
+                accuracy = format!("{:.0}", player.accuracy),
+                top = player.top_plays,
+                turns = player.turns
+            )
+        ));
+        lines.push(format!(
+            "    {}",
+            t!(
+                "analysis-player-bingos",
+                bingos = player.bingos,
+                missed = player.missed_bingos,
+                phonies = player.phonies
+            )
+        ));
+    }
+    lines
+}
+
+// JSON for a .json file, the text report otherwise.
+pub fn export(analysis: &GameAnalysis, path: &Path) -> io::Result<()> {
+    let text = if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        serde_json::to_string_pretty(analysis).map_err(io::Error::other)?
+    } else {
+        report(analysis).join("\n") + "\n"
+    };
+    fs::write(path, text)
+}
+
+// Prints the analysis of a finished game; None when there is none.
+pub fn show(game: &Game, lexicon: &Lexicon) -> Option<GameAnalysis> {
+    println!("{}", t!("analysis-running"));
+    match analysis::analyze(game, lexicon) {
+        Ok(analysis) => {
+            for line in report(&analysis) {
+                println!("{}", line);
+            }
+            Some(analysis)
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        }
+    }
+}
+
+pub fn save_report(analysis: &GameAnalysis, path: &Path) {
+    let shown = path.display().to_string();
+    match export(analysis, path) {
+        Ok(()) => println!("{}", t!("analysis-exported", path = &shown)),
+        Err(e) => eprintln!(
+            "{}",
+            t!(
+                "analysis-export-failed",
+                path = &shown,
+                error = e.to_string()
+            )
+        ),
+    }
+}
@@ -0,0 +1,131 @@
+use colored::*;
+
+use crate::error::InputError;
+use crate::game::chat::{ChatBody, ChatMessage, Emote};
+use crate::t;
+use crate::ui::keywords;
+
+// Messages shown in the pane under the board.
+pub const PANE_LINES: usize = 6;
+const PANE_WIDTH: usize = 60;
+
+pub enum ChatCommand {
+    Say(ChatBody),
+    Mute(String),
+    Unmute(String),
+    Show,
+}
+
+// Keyword typed after /emote.
+fn emote_keyword(emote: Emote) -> &'static str {
+    match emote {
+        Emote::Hello => "salut",
+        Emote::NiceBingo => "scrabble",
+        Emote::WellPlayed => "bravo",
+        Emote::Thinking => "hmm",
+        Emote::Oops => "oups",
+        Emote::GoodGame => "gg",
+    }
+}
+
+pub fn emote_text(emote: Emote) -> String {
+    t!(match emote {
+        Emote::Hello => "emote-hello",
+        Emote::NiceBingo => "emote-nice-bingo",
+        Emote::WellPlayed => "emote-well-played",
+        Emote::Thinking => "emote-thinking",
+        Emote::Oops => "emote-oops",
+        Emote::GoodGame => "emote-good-game",
+    })
+}
+
+pub fn show_help() {
+    let emotes: Vec<&str> = Emote::ALL.iter().map(|&e| emote_keyword(e)).collect();
+    println!("{}", t!("chat-help", emotes = emotes.join("|")));
+}
+
+// Chat lines start with '/'; anything else is left to the move parser.
+pub fn parse_command(line: &str) -> Option<Result<ChatCommand, InputError>> {
+    let rest = line.trim().strip_prefix('/')?;
+    let (cmd, arg) = match rest.split_once(char::is_whitespace) {
+        Some((cmd, arg)) => (cmd, arg.trim()),
+        None => (rest, ""),
+    };
+
+    let named = |names: &[&str]| keywords::is(names, cmd);
+    let command = if named(keywords::SAY) || named(keywords::MUTE) || named(keywords::UNMUTE) {
+        if arg.is_empty() {
+            Err(InputError::EmptyInput)
+        } else if named(keywords::SAY) {
+            Ok(ChatCommand::Say(ChatBody::Text {
+                text: arg.to_string(),
+            }))
+        } else if named(keywords::MUTE) {
+            Ok(ChatCommand::Mute(arg.to_string()))
+        } else {
+            Ok(ChatCommand::Unmute(arg.to_string()))
+        }
+    } else if cmd.eq_ignore_ascii_case("emote") {
+        Emote::ALL
+            .iter()
+            .find(|&&e| emote_keyword(e).eq_ignore_ascii_case(arg))
+            .map(|&emote| ChatCommand::Say(ChatBody::Emote { emote }))
+            .ok_or(InputError::InvalidFormat)
+    } else if cmd.eq_ignore_ascii_case("chat") {
+        Ok(ChatCommand::Show)
+    } else {
+        Err(InputError::InvalidFormat)
+    };
+    Some(command)
+}
+
+pub fn show_error(err: &dyn std::fmt::Display) {
+    eprintln!("{}", t!("chat-refused", error = err.to_string()));
+}
+
+fn format_time(at_unix: i64) -> String {
+    let secs = at_unix.rem_euclid(86_400);
+    format!("{:02}:{:02}", secs / 3600, secs % 3600 / 60)
+}
+
+// Plain text form, for places that do their own styling.
+pub fn format_message(message: &ChatMessage) -> String {
+    let time = format_time(message.at_unix);
+    match &message.body {
+        ChatBody::Text { text } => format!("{} {} : {}", time, message.from, text),
+        ChatBody::Emote { emote } => format!("{} {} {}", time, message.from, emote_text(*emote)),
+    }
+}
+
+pub fn show_message(message: &ChatMessage) {
+    let time = format_time(message.at_unix).dimmed();
+    match &message.body {
+        ChatBody::Text { text } => {
+            println!("{} {} : {}", time, message.from.bold(), text);
+        }
+        ChatBody::Emote { emote } => {
+            println!(
+                "{} {} {}",
+                time,
+                message.from.bold(),
+                emote_text(*emote).italic()
+            );
+        }
+    }
+}
+
+// Kept apart from the board so the two can be laid out independently.
+pub fn show_pane(messages: &[ChatMessage]) {
+    if messages.is_empty() {
+        return;
+    }
+    println!(
+        "{}",
+        format!("{:─^1$}", format!(" {} ", t!("chat-title")), PANE_WIDTH).dimmed()
+    );
+    let start = messages.len().saturating_sub(PANE_LINES);
+    for message in &messages[start..] {
+        show_message(message);
+    }
+    println!("{}", "─".repeat(PANE_WIDTH).dimmed());
+}
@@ -1,29 +1,476 @@
 use crate::game::bag::Bag;
-use crate::game::board::{BOARD_SIZE, Board, CellKind};
+use crate::game::board::{BOARD_SIZE, Board, CellKind, Position};
+use crate::game::definitions::Definitions;
 use crate::game::player::Rack;
-use colored::*;
+use crate::profile::{Profile, ProfileStore};
+use crate::t;
+use crate::ui::linear;
+use crate::ui::theme::{Frame, appearance};
 use std::collections::BTreeMap;
 use std::fmt;
 
 const CELL_W: usize = 4;
 
-use crate::game::Game;
+use crate::game::MoveRecord;
+use crate::game::unseen::Unseen;
+use crate::game::view::GameView;
+use crate::game::{Game, GameEvent};
+use crate::net::lobby::{GameStatus, GameSummary};
 
 pub fn show_game(game: &Game) {
+    if appearance().linear {
+        return linear::show_game(game);
+    }
     println!();
     println!("{}", game.board);
-    println!("{}", game.players[0].rack);
+    show_clocks(game);
+    println!("{}", game.current_player().rack);
+}
+
+// Two letters on a square, in the players' language ("LD" / "DL").
+pub fn premium_label(kind: CellKind) -> String {
+    t!(match kind {
+        CellKind::Normal => return " ".to_string(),
+        CellKind::DoubleLetter => "premium-double-letter",
+        CellKind::TripleLetter => "premium-triple-letter",
+        CellKind::DoubleWord => "premium-double-word",
+        CellKind::TripleWord => "premium-triple-word",
+    })
+}
+
+pub fn format_clock(ms: i64) -> String {
+    let secs = ms.abs() / 1000;
+    let sign = if ms < 0 { "-" } else { "" };
+    format!("{}{:02}:{:02}", sign, secs / 60, secs % 60)
+}
+
+pub fn show_clocks(game: &Game) {
+    let Some(clock) = &game.clock else {
+        return;
+    };
+    let look = appearance();
+
+    let parts: Vec<String> = game
+        .players
+        .iter()
+        .enumerate()
+        .map(|(i, player)| {
+            let marker = if i == game.current_player_index {
+                look.frame().marker
+            } else {
+                " "
+            };
+            let time = format_clock(clock.remaining(i));
+            let time = if clock.is_flagged(i) {
+                look.theme.alert().apply(&time).to_string()
+            } else {
+                time
+            };
+            format!("{} {} {}", marker, player.name, time)
+        })
+        .collect();
+    println!("{}", parts.join("   "));
+}
+
+pub fn show_view(view: &GameView) {
+    let look = appearance();
+    if look.linear {
+        return linear::show_view(view);
+    }
+    println!();
+    println!("{}", view.to_board());
+
+    let parts: Vec<String> = view
+        .players
+        .iter()
+        .enumerate()
+        .map(|(i, player)| {
+            let marker = if i == view.current_player_index {
+                look.frame().marker
+            } else {
+                " "
+            };
+            match player.time_left_ms {
+                Some(ms) if ms < 0 => format!(
+                    "{} {} {} {}",
+                    marker,
+                    player.name,
+                    player.score,
+                    look.theme.alert().apply(&format_clock(ms))
+                ),
+                Some(ms) => format!(
+                    "{} {} {} {}",
+                    marker,
+                    player.name,
+                    player.score,
+                    format_clock(ms)
+                ),
+                None => format!("{} {} {}", marker, player.name, player.score),
+            }
+        })
+        .collect();
+    println!("{}", parts.join("   "));
+    println!("{}", t!("bag-remaining", count = view.bag_remaining));
+
+    if view.seat.is_some() {
+        println!("{}", view.to_rack());
+    }
+
+    for (player, rack) in view.players.iter().zip(&view.revealed_racks) {
+        let letters: String = rack.iter().collect();
+        println!(
+            "{}",
+            t!("rack-left", name = &player.name, letters = letters)
+        );
+    }
 }
 
-pub fn show_events(events: &[impl std::fmt::Debug]) {
-    println!("-> Coup accepté !");
-    for e in events {
-        println!("Event: {:?}", e);
+pub fn show_review(view: &GameView, history: &[MoveRecord]) {
+    show_view(view);
+    println!("{}", t!("review-moves"));
+    for record in history {
+        let name = view
+            .players
+            .iter()
+            .find(|p| p.id == record.player_id)
+            .map(|p| p.name.as_str())
+            .unwrap_or("?");
+        let word = record
+            .words
+            .first()
+            .map(|(word, _)| word.as_str())
+            .unwrap_or("-");
+        let col = (b'a' + record.pos.col as u8) as char;
+        println!(
+            "  {:>3}. {:<12} {:<15} {}{} {:>4}{}",
+            record.turn,
+            name,
+            word,
+            col,
+            record.pos.row + 1,
+            record.score,
+            if record.bingo { " *" } else { "" }
+        );
+    }
+}
+
+// "A×3 B·  C×1 ... _×1": every letter, a dot for those all gone.
+// "A:3 B.  C:1" in ASCII.
+pub fn format_unseen_letters(unseen: &Unseen) -> String {
+    let ascii = appearance().ascii;
+    unseen
+        .counts
+        .iter()
+        .map(|(letter, count)| match (count, ascii) {
+            (0, false) => format!("{}· ", letter),
+            (0, true) => format!("{}. ", letter),
+            (n, false) => format!("{}×{}", letter, n),
+            (n, true) => format!("{}:{}", letter, n),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn format_unseen_ratio(unseen: &Unseen) -> String {
+    let ratio = t!(
+        "unseen-ratio",
+        vowels = unseen.vowels(),
+        consonants = unseen.consonants(),
+        blanks = unseen.blanks()
+    );
+    if appearance().ascii {
+        ratio.replace('·', ",")
+    } else {
+        ratio
+    }
+}
+
+pub fn show_unseen(unseen: &Unseen) {
+    println!("{}", t!("unseen-title", count = unseen.total()));
+    println!("  {}", format_unseen_letters(unseen));
+    println!("  {}", format_unseen_ratio(unseen));
+}
+
+pub fn show_game_list(games: &[GameSummary]) {
+    if games.is_empty() {
+        println!("{}", t!("lobby-no-games"));
+        return;
+    }
+    for game in games {
+        let status = t!(match game.status {
+            GameStatus::Waiting => "lobby-waiting",
+            GameStatus::Ongoing => "lobby-ongoing",
+            GameStatus::Finished => "lobby-finished",
+        });
+        let clock = game
+            .time_control
+            .map(|tc| format!(" {}+{}", tc.initial_secs / 60, tc.increment_secs))
+            .unwrap_or_default();
+        let rules = game
+            .rules
+            .map(|preset| format!(" ({})", preset.name()))
+            .unwrap_or_default();
+        let spectators = if game.spectators > 0 {
+            format!(", {}", t!("lobby-spectators", count = game.spectators))
+        } else {
+            String::new()
+        };
+        println!(
+            "  {}) {} [{}/{}]{}{} {}{}",
+            game.game_id,
+            game.players.join(", "),
+            game.players.len(),
+            game.seats,
+            clock,
+            rules,
+            status,
+            spectators
+        );
+    }
+}
+
+// Exchanges, challenges and resignations, which the move log doesn't show.
+pub fn describe_event(event: &GameEvent, name_of: &dyn Fn(usize) -> String) -> Option<String> {
+    match event {
+        GameEvent::Resigned { player_id } => {
+            Some(t!("player-resigned", name = name_of(*player_id)))
+        }
+        GameEvent::TilesExchanged { player_id, count } => Some(t!(
+            "tiles-exchanged",
+            name = name_of(*player_id),
+            count = *count
+        )),
+        GameEvent::ChallengeResolved {
+            challenged_id,
+            upheld: true,
+            words,
+            ..
+        } => Some(t!(
+            "challenge-upheld",
+            words = words.join(", "),
+            name = name_of(*challenged_id)
+        )),
+        GameEvent::ChallengeResolved {
+            challenger_id,
+            challenged_id,
+            penalty,
+            turn_lost,
+            ..
+        } => {
+            let mut text = t!("challenge-failed", name = name_of(*challenged_id));
+            if *penalty > 0 {
+                let lost = t!(
+                    "challenge-penalty",
+                    name = name_of(*challenger_id),
+                    points = *penalty
+                );
+                text = format!("{} {}", text, lost);
+            }
+            if *turn_lost {
+                let lost = t!("challenge-turn-lost", name = name_of(*challenger_id));
+                text = format!("{} {}", text, lost);
+            }
+            Some(text)
+        }
+        _ => None,
+    }
+}
+
+pub fn show_remote_event(view: Option<&GameView>, event: &GameEvent) {
+    let name_of = |player_id: usize| {
+        view.and_then(|v| v.players.iter().find(|p| p.id == player_id))
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| t!("player-fallback", id = player_id))
+    };
+
+    match event {
+        GameEvent::WordPlaced { word, score, .. } => {
+            println!("  {} ({})", word, score);
+        }
+        GameEvent::MoveScored {
+            player_id,
+            score,
+            bingo,
+        } => {
+            let scored = t!("move-scored", name = name_of(*player_id), score = *score);
+            if *bingo {
+                println!("{} {}", scored, t!("bingo-note"));
+            } else {
+                println!("{}", scored);
+            }
+        }
+        event => {
+            if let Some(text) = describe_event(event, &name_of) {
+                println!("{}", text);
+            }
+        }
+    }
+}
+
+// "CHAT : petit félin", or that there is no definition.
+pub fn definition_line(definitions: &Definitions, word: &str) -> String {
+    match definitions.get(word) {
+        Some(text) => t!("definition", word = word.to_uppercase(), text = text),
+        None => t!("definition-missing", word = word.to_uppercase()),
+    }
+}
+
+// Meanings worth showing after an event: the words placed, and the words of
+// a challenge, known or not.
+pub fn event_definitions(event: &GameEvent, definitions: &Definitions) -> Vec<String> {
+    match event {
+        GameEvent::WordPlaced { word, .. } => definitions
+            .get(word)
+            .map(|text| t!("definition", word = word, text = text))
+            .into_iter()
+            .collect(),
+        GameEvent::ChallengeResolved { words, .. } => words
+            .iter()
+            .map(|word| definition_line(definitions, word))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn show_scores(game: &Game) {
+    for (i, player) in game.players.iter().enumerate() {
+        let mut notes = Vec::new();
+        let penalty = game.overtime_penalty(i);
+        if penalty > 0 {
+            notes.push(t!("overtime-penalty", points = penalty));
+        }
+        if game.forfeited_by == Some(player.id) {
+            notes.push(t!("lost-on-time"));
+        }
+        if game.resigned_by == Some(player.id) {
+            notes.push(t!("resigned"));
+        }
+
+        if notes.is_empty() {
+            println!("{:<12} {:>4}", player.name, player.score);
+        } else {
+            println!(
+                "{:<12} {:>4}  ({})",
+                player.name,
+                player.score,
+                notes.join(", ")
+            );
+        }
+    }
+}
+
+pub fn show_profile(profile: &Profile) {
+    let stats = &profile.stats;
+    let rating = &profile.rating;
+    println!("=== {} ===", profile.name);
+    let value = t!("profile-rating", rating = format!("{:.0}", rating.value));
+    if rating.is_provisional() {
+        println!("{} {}", value, t!("profile-provisional"));
+    } else {
+        println!("{}", value);
+    }
+    if !rating.history.is_empty() {
+        let recent: Vec<String> = rating
+            .history
+            .iter()
+            .rev()
+            .take(10)
+            .rev()
+            .map(|entry| format!("{:.0}", entry.rating))
+            .collect();
+        println!("{}", t!("profile-history", ratings = recent.join(" → ")));
+    }
+    println!(
+        "{}",
+        t!(
+            "profile-games",
+            played = stats.games_played,
+            won = stats.games_won
+        )
+    );
+    println!(
+        "{}",
+        t!(
+            "profile-average-score",
+            score = format!("{:.1}", stats.average_score())
+        )
+    );
+    println!(
+        "{}",
+        t!(
+            "profile-average-move",
+            score = format!("{:.1}", stats.average_move_score()),
+            moves = stats.moves_played
+        )
+    );
+    println!("{}", t!("profile-bingos", count = stats.bingos));
+    println!("{}", t!("profile-blanks", count = stats.blanks_played));
+    if let Some((word, score)) = &stats.best_word {
+        println!("{}", t!("profile-best-word", word = word, score = *score));
+    }
+    if let Some((word, score)) = &stats.best_move {
+        println!("{}", t!("profile-best-move", word = word, score = *score));
+    }
+
+    let words = stats.most_used_words(5);
+    if !words.is_empty() {
+        let list: Vec<String> = words
+            .iter()
+            .map(|(word, count)| format!("{} ×{}", word, count))
+            .collect();
+        println!("{}", t!("profile-top-words", words = list.join(", ")));
+    }
+}
+
+pub fn show_profiles(store: &ProfileStore) {
+    let mut any = false;
+    for profile in store.iter() {
+        show_profile(profile);
+        println!();
+        any = true;
+    }
+    if !any {
+        println!("{}", t!("profile-none"));
+    }
+}
+
+pub fn show_leaderboard(store: &ProfileStore) {
+    let ranked = store.leaderboard();
+    if ranked.is_empty() {
+        println!("{}", t!("leaderboard-empty"));
+        return;
+    }
+
+    println!(
+        "{:>3}  {:<16} {:>6} {:>7}",
+        "#",
+        t!("leaderboard-player"),
+        "Elo",
+        t!("leaderboard-games")
+    );
+    for (i, profile) in ranked.iter().enumerate() {
+        let name = if profile.is_bot {
+            format!("{} (bot)", profile.name)
+        } else {
+            profile.name.clone()
+        };
+        println!(
+            "{:>3}  {:<16} {:>6.0}{} {:>6}",
+            i + 1,
+            name,
+            profile.rating.value,
+            if profile.rating.is_provisional() {
+                "?"
+            } else {
+                " "
+            },
+            profile.rating.games
+        );
     }
 }
 
 pub fn show_move_error(err: &dyn std::fmt::Display) {
-    eprintln!("-> Coup impossible: {}. Recommencez.", err);
+    eprintln!("{}", t!("move-rejected", reason = err.to_string()));
 }
 
 fn center(text: &str, width: usize) -> String {
@@ -51,75 +498,118 @@ fn strip_ansi_len(text: &str) -> usize {
 
 fn col_labels() -> [char; BOARD_SIZE] {
     let mut labels = [' '; BOARD_SIZE];
-    for i in 0..BOARD_SIZE {
-        labels[i] = (b'A' + i as u8) as char;
+    for (i, label) in labels.iter_mut().enumerate() {
+        *label = (b'A' + i as u8) as char;
     }
     labels
 }
 
-impl fmt::Display for Board {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let cols = col_labels();
-        write!(f, "     ")?;
-        for &c in &cols {
-            write!(f, "{}", center(&c.to_string(), CELL_W + 1))?;
-        }
-        writeln!(f)?;
+fn border(f: &mut fmt::Formatter<'_>, frame: &Frame, joints: [&str; 3]) -> fmt::Result {
+    write!(f, "    {}", joints[0])?;
+    for x in 0..BOARD_SIZE {
+        write!(f, "{}", frame.horizontal.repeat(CELL_W))?;
+        write!(
+            f,
+            "{}",
+            if x + 1 == BOARD_SIZE {
+                joints[2]
+            } else {
+                joints[1]
+            }
+        )?;
+    }
+    writeln!(f)
+}
 
-        write!(f, "    ")?;
-        write!(f, "┌")?;
-        for x in 0..BOARD_SIZE {
-            write!(f, "{}", "─".repeat(CELL_W))?;
-            write!(f, "{}", if x + 1 == BOARD_SIZE { "┐" } else { "┬" })?;
-        }
-        writeln!(f)?;
+// `{:#}` draws the board in plain ASCII, as does the --ascii option.
+// "+AES" on a square where A, E or S would hook a word; "+12" past three.
+fn hook_label(letters: &[char]) -> String {
+    if letters.len() < CELL_W {
+        format!("+{}", letters.iter().collect::<String>())
+    } else {
+        format!("+{}", letters.len())
+    }
+}
 
-        for r in 0..BOARD_SIZE {
-            write!(f, "{:>3} ", r + 1)?;
-
-            write!(f, "│")?;
-            for c in 0..BOARD_SIZE {
-                let cell = &self.cells[r][c];
-                let label = match cell.letter {
-                    Some(ch) => ch.to_ascii_uppercase().to_string().bold().to_string(),
-                    None => {
-                        if r == 7 && c == 7 {
-                            "★".to_string()
-                        } else {
-                            match cell.kind {
-                                CellKind::Normal => " ".to_string(),
-                                CellKind::DoubleLetter => "LD".blue().to_string(),
-                                CellKind::TripleLetter => "LT".cyan().to_string(),
-                                CellKind::DoubleWord => "MD".red().to_string(),
-                                CellKind::TripleWord => "MT".magenta().to_string(),
-                            }
-                        }
-                    }
-                };
-                write!(f, "{}", center(&label, CELL_W))?;
-                write!(f, "│")?;
-            }
-            writeln!(f)?;
-
-            write!(f, "    ")?;
-            if r + 1 == BOARD_SIZE {
-                write!(f, "└")?;
-                for x in 0..BOARD_SIZE {
-                    write!(f, "{}", "─".repeat(CELL_W))?;
-                    write!(f, "{}", if x + 1 == BOARD_SIZE { "┘" } else { "┴" })?;
+fn write_board(
+    board: &Board,
+    hooks: Option<&BTreeMap<Position, Vec<char>>>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let look = appearance();
+    let frame = Frame::get(f.alternate() || look.ascii);
+    let cols = col_labels();
+    write!(f, "     ")?;
+    for &c in &cols {
+        write!(f, "{}", center(&c.to_string(), CELL_W + 1))?;
+    }
+    writeln!(f)?;
+    border(f, frame, frame.top)?;
+
+    for r in 0..BOARD_SIZE {
+        write!(f, "{:>3} ", r + 1)?;
+
+        write!(f, "{}", frame.vertical)?;
+        for c in 0..BOARD_SIZE {
+            let cell = &board.cells[r][c];
+            let hook = hooks.and_then(|hooks| hooks.get(&Position { row: r, col: c }));
+            let label = match (cell.letter, hook) {
+                // Blanks show in lowercase, as on a real board.
+                (Some(ch), _) if cell.is_blank => look
+                    .theme
+                    .letter()
+                    .apply(&ch.to_ascii_lowercase().to_string())
+                    .to_string(),
+                (Some(ch), _) => look
+                    .theme
+                    .letter()
+                    .apply(&ch.to_ascii_uppercase().to_string())
+                    .to_string(),
+                (None, Some(letters)) => {
+                    look.theme.success().apply(&hook_label(letters)).to_string()
                 }
-                writeln!(f)?;
-            } else {
-                write!(f, "├")?;
-                for x in 0..BOARD_SIZE {
-                    write!(f, "{}", "─".repeat(CELL_W))?;
-                    write!(f, "{}", if x + 1 == BOARD_SIZE { "┤" } else { "┼" })?;
+                (None, None) if r == BOARD_SIZE / 2 && c == BOARD_SIZE / 2 => {
+                    frame.centre.to_string()
                 }
-                writeln!(f)?;
-            }
+                (None, None) => match cell.kind {
+                    CellKind::Normal => " ".to_string(),
+                    kind => look
+                        .theme
+                        .premium(kind)
+                        .apply(&premium_label(kind))
+                        .to_string(),
+                },
+            };
+            write!(f, "{}", center(&label, CELL_W))?;
+            write!(f, "{}", frame.vertical)?;
         }
+        writeln!(f)?;
 
-        Ok(())
+        if r + 1 == BOARD_SIZE {
+            border(f, frame, frame.bottom)?;
+        } else {
+            border(f, frame, frame.middle)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_board(self, None, f)
+    }
+}
+
+// The board with the hook letters on the empty squares next to each word.
+pub struct HookedBoard<'a> {
+    pub board: &'a Board,
+    pub hooks: &'a BTreeMap<Position, Vec<char>>,
+}
+
+impl fmt::Display for HookedBoard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_board(self.board, Some(self.hooks), f)
     }
 }
 
@@ -132,19 +622,25 @@ impl fmt::Display for Bag {
             entry.1 += 1;
         }
 
-        writeln!(f, "--- Bag contents ---")?;
-        writeln!(f, "{:<3} | {:<5} | {:<5}", "Let", "Val", "Count")?;
+        writeln!(f, "--- {} ---", t!("bag-title"))?;
+        writeln!(
+            f,
+            "{:<3} | {:<5} | {:<5}",
+            t!("bag-letter"),
+            t!("bag-value"),
+            t!("bag-count")
+        )?;
         writeln!(f, "--------------------")?;
 
         for (letter, (value, count)) in counts {
             writeln!(f, "{:<3} | {:<5} | {:<5}", letter, value, count)?;
         }
 
-        writeln!(f, "Total tiles: {}", self.tiles.len())
+        writeln!(f, "{}", t!("bag-total", count = self.tiles.len()))
     }
 }
 
-fn superscript_num(n: u8) -> String {
+pub fn superscript_num(n: u8) -> String {
     const SUP: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
     if n == 0 {
         return SUP[0].to_string();
@@ -161,24 +657,36 @@ fn superscript_num(n: u8) -> String {
 
 impl fmt::Display for Rack {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Chevalet:")?;
+        writeln!(f, "{}", t!("rack-title"))?;
         if self.tiles.is_empty() {
-            return writeln!(f, "(empty rack)");
+            return writeln!(f, "{}", t!("rack-empty"));
         }
 
-        let ascii_fallback = f.alternate();
+        let ascii_fallback = f.alternate() || appearance().ascii;
+        let frame = Frame::get(ascii_fallback);
         let cell_w = 6;
         let tile_count = self.tiles.len();
+        let border = |f: &mut fmt::Formatter<'_>, joints: [&str; 3]| {
+            write!(f, "{}", joints[0])?;
+            for i in 0..tile_count {
+                write!(f, "{}", frame.horizontal.repeat(cell_w))?;
+                write!(
+                    f,
+                    "{}",
+                    if i + 1 == tile_count {
+                        joints[2]
+                    } else {
+                        joints[1]
+                    }
+                )?;
+            }
+            writeln!(f)
+        };
 
-        write!(f, "┌")?;
-        for i in 0..tile_count {
-            write!(f, "{}", "─".repeat(cell_w))?;
-            write!(f, "{}", if i + 1 == tile_count { "┐" } else { "┬" })?;
-        }
-        writeln!(f)?;
+        border(f, frame.top)?;
 
-        write!(f, "│")?;
-        for (_, tile) in self.tiles.iter().enumerate() {
+        write!(f, "{}", frame.vertical)?;
+        for tile in self.tiles.iter() {
             let ch = if tile.value == 0 {
                 '_'
             } else {
@@ -205,17 +713,10 @@ impl fmt::Display for Rack {
                 " ".repeat(right_pad)
             )?;
 
-            write!(f, "│")?;
-        }
-        writeln!(f)?;
-
-        write!(f, "└")?;
-        for i in 0..tile_count {
-            write!(f, "{}", "─".repeat(cell_w))?;
-            write!(f, "{}", if i + 1 == tile_count { "┘" } else { "┴" })?;
+            write!(f, "{}", frame.vertical)?;
         }
         writeln!(f)?;
 
-        Ok(())
+        border(f, frame.bottom)
     }
 }
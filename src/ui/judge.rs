@@ -0,0 +1,94 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use colored::Colorize;
+
+use crate::game::judge::{self, Adjudication, JudgeLog, Verdict};
+use crate::game::lexicon::Lexicon;
+use crate::save::now_unix;
+use crate::t;
+use crate::ui::keywords;
+use crate::ui::theme::appearance;
+
+fn show_verdict(verdict: Verdict) {
+    let theme = appearance().theme;
+    let text = match verdict {
+        Verdict::Acceptable => theme.success().apply(&t!("judge-acceptable")),
+        Verdict::NotAcceptable => theme.alert().apply(&t!("judge-not-acceptable")),
+    };
+    println!();
+    println!("    {}", text.bold());
+    println!();
+}
+
+// Judges the play, says the verdict and writes it down.
+pub fn adjudicate(words: Vec<String>, lexicon: &Lexicon, log: Option<&JudgeLog>) -> Verdict {
+    let verdict = judge::judge(lexicon, &words);
+    show_verdict(verdict);
+    if let Some(log) = log {
+        let adjudication = Adjudication {
+            at: now_unix(),
+            words,
+            verdict,
+        };
+        if let Err(e) = log.append(&adjudication) {
+            eprintln!("{}", t!("judge-log-failed", error = e.to_string()));
+        }
+    }
+    verdict
+}
+
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+}
+
+fn read_line() -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+// The judge screen: one play after another until "quitter" (or "quit"). In a terminal
+// the screen is wiped before the next play, so the players who come next
+// don't see the last verdict.
+pub fn run(lexicon: &Lexicon, log: Option<&JudgeLog>) -> io::Result<()> {
+    let terminal = io::stdin().is_terminal() && io::stdout().is_terminal();
+    let mut fresh = true;
+    loop {
+        if fresh {
+            if terminal {
+                clear_screen();
+            }
+            println!("=== {} ===", t!("judge-title"));
+            println!("{}", t!("judge-help"));
+            fresh = false;
+        }
+        print!("{} ", t!("judge-prompt"));
+        io::stdout().flush()?;
+
+        let Some(line) = read_line()? else {
+            return Ok(());
+        };
+        if keywords::is(keywords::QUIT, &line) {
+            return Ok(());
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let Some(words) = judge::parse_words(&line) else {
+            println!("{}", t!("judge-invalid"));
+            continue;
+        };
+        adjudicate(words, lexicon, log);
+
+        if terminal {
+            print!("{} ", t!("judge-next"));
+            io::stdout().flush()?;
+            if read_line()?.is_none() {
+                return Ok(());
+            }
+            fresh = true;
+        }
+    }
+}
@@ -0,0 +1,29 @@
+// Typed commands. Both languages are understood whatever the interface
+// language, the help shows the ones of the interface language.
+pub const PASS: &[&str] = &["passe", "pass"];
+pub const CHALLENGE: &[&str] = &["conteste", "challenge"];
+pub const EXCHANGE: &[&str] = &["echange", "échange", "exchange"];
+pub const UNSEEN: &[&str] = &["tuiles", "tiles"];
+pub const BOARD: &[&str] = &["plateau", "board"];
+pub const HOOKS: &[&str] = &["crochets", "hooks"];
+pub const DEFINE: &[&str] = &["definir", "définir", "define"];
+pub const QUIT: &[&str] = &["quitter", "quit"];
+
+// Chat, after the '/'.
+pub const SAY: &[&str] = &["dire", "say"];
+pub const MUTE: &[&str] = &["muet", "mute"];
+pub const UNMUTE: &[&str] = &["parle", "unmute"];
+
+// Lobby of a remote game.
+pub const LIST: &[&str] = &["liste", "list"];
+pub const NEW: &[&str] = &["nouvelle", "new"];
+pub const JOIN: &[&str] = &["rejoindre", "join"];
+pub const WATCH: &[&str] = &["regarder", "watch"];
+pub const REVIEW: &[&str] = &["revoir", "review"];
+pub const SEEK: &[&str] = &["chercher", "seek"];
+pub const CANCEL: &[&str] = &["annuler", "cancel"];
+
+pub fn is(keywords: &[&str], input: &str) -> bool {
+    let input = input.to_lowercase();
+    keywords.contains(&input.as_str())
+}
@@ -0,0 +1,177 @@
+// Screen-reader output: everything the boxes and colors show, told line by
+// line in plain sentences.
+use crate::error::square_name;
+use crate::game::board::{BOARD_SIZE, Board, Direction};
+use crate::game::player::Rack;
+use crate::game::view::GameView;
+use crate::game::{Game, MoveRecord};
+use crate::t;
+use crate::ui::display::format_clock;
+
+// "CHAT de h8 à k8 (joker : a)", or "X en m3" for a lone letter.
+fn describe_run(board: &Board, row: usize, start: usize, end: usize) -> String {
+    let cells = &board.cells[row][start..=end];
+    let letters: String = cells.iter().filter_map(|cell| cell.letter).collect();
+    let mut text = if start == end {
+        t!(
+            "linear-single",
+            letter = letters,
+            pos = square_name(row, start)
+        )
+    } else {
+        t!(
+            "linear-run",
+            letters = letters,
+            from = square_name(row, start),
+            to = square_name(row, end)
+        )
+    };
+    let blanks: Vec<String> = cells
+        .iter()
+        .filter(|cell| cell.is_blank)
+        .filter_map(|cell| cell.letter)
+        .map(|ch| ch.to_ascii_lowercase().to_string())
+        .collect();
+    if !blanks.is_empty() {
+        text = format!(
+            "{} {}",
+            text,
+            t!("linear-blank-note", letters = blanks.join(", "))
+        );
+    }
+    text
+}
+
+// One line per row that holds letters; empty rows are left out.
+pub fn board_lines(board: &Board) -> Vec<String> {
+    let mut lines = Vec::new();
+    for row in 0..BOARD_SIZE {
+        let mut runs = Vec::new();
+        let mut col = 0;
+        while col < BOARD_SIZE {
+            if board.cells[row][col].letter.is_none() {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            while col + 1 < BOARD_SIZE && board.cells[row][col + 1].letter.is_some() {
+                col += 1;
+            }
+            runs.push(describe_run(board, row, start, col));
+            col += 1;
+        }
+        if !runs.is_empty() {
+            lines.push(t!("linear-row", row = row + 1, runs = runs.join("; ")));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(t!("linear-board-empty"));
+    }
+    lines
+}
+
+pub fn rack_line(rack: &Rack) -> String {
+    if rack.tiles.is_empty() {
+        return t!("linear-rack-empty");
+    }
+    let tiles: Vec<String> = rack
+        .tiles
+        .iter()
+        .map(|tile| {
+            if tile.value == 0 {
+                t!("linear-blank")
+            } else {
+                format!("{} ({})", tile.letter.to_ascii_uppercase(), tile.value)
+            }
+        })
+        .collect();
+    t!("linear-rack", tiles = tiles.join(", "))
+}
+
+// "Alice : 12 points (24:10)", the clock only when the game has one.
+fn score_entry(name: &str, score: u32, time_left_ms: Option<i64>) -> String {
+    let entry = t!("linear-score-entry", name = name, score = score);
+    match time_left_ms {
+        Some(ms) => format!("{} ({})", entry, format_clock(ms)),
+        None => entry,
+    }
+}
+
+fn show_status(turn: u32, to_move: &str, scores: &[String], bag: usize) {
+    println!("{}", t!("linear-turn", turn = turn, name = to_move));
+    println!("{}", t!("linear-scores", scores = scores.join(", ")));
+    println!("{}", t!("bag-remaining", count = bag));
+}
+
+pub fn show_game(game: &Game) {
+    println!();
+    for line in board_lines(&game.board) {
+        println!("{}", line);
+    }
+    let scores: Vec<String> = game
+        .players
+        .iter()
+        .enumerate()
+        .map(|(i, player)| {
+            let time = game.clock.as_ref().map(|clock| clock.remaining(i));
+            score_entry(&player.name, player.score, time)
+        })
+        .collect();
+    show_status(
+        game.turn,
+        &game.current_player().name,
+        &scores,
+        game.bag.tiles.len(),
+    );
+    println!("{}", rack_line(&game.current_player().rack));
+}
+
+pub fn show_view(view: &GameView) {
+    println!();
+    for line in board_lines(&view.to_board()) {
+        println!("{}", line);
+    }
+    let scores: Vec<String> = view
+        .players
+        .iter()
+        .map(|player| score_entry(&player.name, player.score, player.time_left_ms))
+        .collect();
+    let to_move = view
+        .players
+        .get(view.current_player_index)
+        .map(|p| p.name.as_str())
+        .unwrap_or("?");
+    show_status(view.turn, to_move, &scores, view.bag_remaining);
+    if view.seat.is_some() {
+        println!("{}", rack_line(&view.to_rack()));
+    }
+}
+
+// "Alice joue CHAT en h8, horizontalement, pour 12 points." plus the other
+// words the move made.
+pub fn describe_move(name: &str, record: &MoveRecord) -> String {
+    let word = record.words.first().map(|(w, _)| w.as_str()).unwrap_or("-");
+    let direction = t!(match record.dir {
+        Direction::Across => "linear-across",
+        Direction::Down => "linear-down",
+    });
+    let mut text = t!(
+        "linear-move",
+        name = name,
+        word = word,
+        pos = square_name(record.pos.row, record.pos.col),
+        direction = direction,
+        score = record.score
+    );
+    if record.words.len() > 1 {
+        let others: Vec<String> = record.words[1..]
+            .iter()
+            .map(|(word, score)| format!("{} ({})", word, score))
+            .collect();
+        text = format!("{} {}", text, t!("linear-also", words = others.join(", ")));
+    }
+    if record.bingo {
+        text = format!("{} {}", text, t!("bingo-note"));
+    }
+    text
+}
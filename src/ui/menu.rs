@@ -6,9 +6,24 @@ use crate::game::action::Action::PlaceWord;
 use crate::game::bag::Tile;
 use crate::game::board::{Board, Direction, Position, Word};
 use crate::game::player::Rack;
+use crate::t;
+use crate::ui::chat::{self, ChatCommand};
+use crate::ui::keywords;
+
+// What the player typed on their turn: a move, or something that doesn't
+// use the turn up.
+pub enum TurnInput {
+    Play(Action),
+    Chat(ChatCommand),
+    ShowUnseen,
+    ShowBoard,
+    // The board's hook squares, or the hooks of one word.
+    ShowHooks(Option<String>),
+    Define(String),
+}
 
 fn read_input(prompt: &str) -> Result<String, InputError> {
-    print!("{}", prompt);
+    print!("{} ", prompt);
     io::stdout().flush().unwrap();
     let mut input = String::new();
     io::stdin()
@@ -21,36 +36,61 @@ fn read_input(prompt: &str) -> Result<String, InputError> {
     Ok(trimmed)
 }
 
-fn read_position() -> Result<Position, InputError> {
-    let input = read_input("Position où commence le mot (ex: h8) : ")?.to_lowercase();
-
-    if input.len() < 2 {
-        return Err(InputError::InvalidPosition);
-    }
-
-    let (col_char, row_part) = input.split_at(1);
-    let col_char = col_char.chars().next().unwrap();
+// Moves that aren't a word on the board.
+fn is_move_command(command: &str) -> bool {
+    [keywords::PASS, keywords::CHALLENGE, keywords::EXCHANGE]
+        .iter()
+        .any(|names| keywords::is(names, command))
+}
 
-    let ascii_code = col_char as u8;
+// Commands that only show something: "tuiles", "plateau", "crochets
+// [MOT]" and "definir MOT", or their English names. None for anything
+// else.
+pub fn parse_lookup(input: &str) -> Option<Result<TurnInput, InputError>> {
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    let command = *parts.first()?;
+    let word = parts.get(1).map(|word| word.to_uppercase());
+    let lookup = if keywords::is(keywords::UNSEEN, command) {
+        (parts.len() == 1).then_some(TurnInput::ShowUnseen)
+    } else if keywords::is(keywords::BOARD, command) {
+        (parts.len() == 1).then_some(TurnInput::ShowBoard)
+    } else if keywords::is(keywords::HOOKS, command) {
+        (parts.len() <= 2).then_some(TurnInput::ShowHooks(word))
+    } else if keywords::is(keywords::DEFINE, command) {
+        word.filter(|_| parts.len() == 2).map(TurnInput::Define)
+    } else {
+        return None;
+    };
+    Some(lookup.ok_or(InputError::InvalidFormat))
+}
 
-    if !(b'a'..=b'o').contains(&ascii_code) {
-        return Err(InputError::InvalidPosition);
+// The first prompt of a turn also accepts chat commands, the lookups above
+// and the other moves.
+fn read_position_or_command() -> Result<Result<Position, TurnInput>, InputError> {
+    let input = read_input(&t!("prompt-position"))?;
+    let command = input.split_whitespace().next().unwrap_or("");
+    if is_move_command(command) {
+        return Ok(Err(TurnInput::Play(parse_move(&input)?)));
     }
+    if let Some(lookup) = parse_lookup(&input) {
+        return Ok(Err(lookup?));
+    }
+    match chat::parse_command(&input) {
+        Some(command) => Ok(Err(TurnInput::Chat(command?))),
+        None => Ok(Ok(parse_position(&input)?)),
+    }
+}
 
-    let col = (ascii_code - b'a') as usize;
-
-    let row = match row_part.parse::<usize>() {
-        Ok(n) if (1..=15).contains(&n) => n - 1,
-        _ => {
-            return Err(InputError::InvalidPosition);
-        }
-    };
-
-    Ok(Position { row, col })
+pub fn parse_position(input: &str) -> Result<Position, InputError> {
+    Position::parse(input).ok_or(InputError::InvalidPosition)
 }
 
 fn read_direction() -> Result<Direction, InputError> {
-    let input = read_input("Direction (h/v) : ")?.to_lowercase();
+    parse_direction(&read_input(&t!("prompt-direction"))?)
+}
+
+pub fn parse_direction(input: &str) -> Result<Direction, InputError> {
+    let input = input.to_lowercase();
     let direction = match input.as_str() {
         "h" => Direction::Across,
         "v" => Direction::Down,
@@ -62,7 +102,11 @@ fn read_direction() -> Result<Direction, InputError> {
 }
 
 fn read_word() -> Result<Word, InputError> {
-    let input = read_input("Votre coup: ")?.to_uppercase();
+    parse_word(&read_input(&t!("prompt-word"))?)
+}
+
+pub fn parse_word(input: &str) -> Result<Word, InputError> {
+    let input = input.to_uppercase();
     if input.is_empty() || !input.chars().all(|ch| ch.is_alphabetic()) {
         return Err(InputError::InvalidFormat);
     }
@@ -79,25 +123,60 @@ fn read_word() -> Result<Word, InputError> {
     Ok(Word { tiles })
 }
 
-pub fn prompt_action(board: &Board, rack: &Rack) -> Action {
+// Rack letters to exchange, '?' or '_' for a blank.
+fn parse_rack_letters(input: &str) -> Result<Vec<char>, InputError> {
+    input
+        .to_uppercase()
+        .chars()
+        .map(|ch| match ch {
+            '?' | '_' => Ok('_'),
+            ch if ch.is_alphabetic() => Ok(ch),
+            _ => Err(InputError::InvalidFormat),
+        })
+        .collect()
+}
+
+// One-line form used where a step-by-step prompt would get in the way,
+// e.g. "h8 h CHAT", "passe", "echange KWZ" or "conteste" (or "pass",
+// "exchange KWZ", "challenge").
+pub fn parse_move(line: &str) -> Result<Action, InputError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts.as_slice() {
+        [] => Err(InputError::EmptyInput),
+        [cmd] if keywords::is(keywords::PASS, cmd) => Ok(Action::Pass),
+        [cmd] if keywords::is(keywords::CHALLENGE, cmd) => Ok(Action::Challenge),
+        [cmd, letters] if keywords::is(keywords::EXCHANGE, cmd) => Ok(Action::ExchangeTiles {
+            letters: parse_rack_letters(letters)?,
+        }),
+        [pos, dir, word] => Ok(PlaceWord {
+            pos: parse_position(pos)?,
+            dir: parse_direction(dir)?,
+            word: parse_word(word)?,
+        }),
+        _ => Err(InputError::InvalidFormat),
+    }
+}
+
+pub fn prompt_turn(_board: &Board, _rack: &Rack) -> TurnInput {
     loop {
         let pos = loop {
-            match read_position() {
-                Ok(pos) => break pos,
-                Err(e) => println!("Erreur: {}. Réessayez.", e),
+            match read_position_or_command() {
+                Ok(Ok(pos)) => break pos,
+                Ok(Err(input)) => return input,
+                Err(e) => println!("{}", t!("input-retry", error = e.to_string())),
             }
         };
 
         let dir = loop {
             match read_direction() {
                 Ok(dir) => break dir,
-                Err(e) => println!("Erreur: {}. Réessayez.", e),
+                Err(e) => println!("{}", t!("input-retry", error = e.to_string())),
             }
         };
 
         match read_word() {
-            Ok(word) => return PlaceWord { pos, dir, word },
-            Err(e) => println!("Erreur: {}. Réessayez.", e),
+            Ok(word) => return TurnInput::Play(PlaceWord { pos, dir, word }),
+            Err(e) => println!("{}", t!("input-retry", error = e.to_string())),
         }
     }
 }
@@ -1,2 +1,13 @@
+pub mod analysis;
+pub mod chat;
 pub mod display;
+pub mod judge;
+pub mod keywords;
+pub mod linear;
 pub mod menu;
+pub mod remote;
+pub mod selfplay;
+pub mod study;
+pub mod theme;
+pub mod tui;
+pub mod words;
@@ -0,0 +1,412 @@
+use std::{
+    io::{self, BufRead, Write},
+    net::TcpListener,
+    sync::{
+        Arc,
+        mpsc::{self, Sender},
+    },
+    thread,
+};
+
+use crate::config::Settings;
+use crate::game::chat::ChatMessage;
+use crate::game::clock::TimeControl;
+use crate::game::definitions::Definitions;
+use crate::game::lexicon::Lexicon;
+use crate::game::rules::RulePreset;
+use crate::game::view::GameView;
+use crate::net::client::{ClientEvent, Connection};
+use crate::net::protocol::{ClientMessage, Play, ServerMessage};
+use crate::net::server::{self, ServerConfig};
+use crate::study::hooks;
+use crate::t;
+use crate::ui::chat::{self, ChatCommand};
+use crate::ui::menu::{self, TurnInput};
+use crate::ui::{display, keywords, words};
+
+struct RemoteGame {
+    game_id: Option<u32>,
+    view: Option<GameView>,
+    chat: Vec<ChatMessage>,
+    // This player's own word list and definitions, for the lookups.
+    lexicon: Option<Arc<Lexicon>>,
+    definitions: Option<Arc<Definitions>>,
+}
+
+impl RemoteGame {
+    fn my_turn(&self) -> bool {
+        self.view
+            .as_ref()
+            .is_some_and(|v| !v.game_over && v.seat == Some(v.current_player_index))
+    }
+}
+
+// Stdin is read on its own thread so server pushes show up while a player
+// is still thinking about their move.
+fn spawn_stdin(events: Sender<ClientEvent>) {
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if events.send(ClientEvent::Line(line)).is_err() {
+                return;
+            }
+        }
+        let _ = events.send(ClientEvent::InputClosed);
+    });
+}
+
+fn prompt(text: &str) {
+    print!("{} ", text);
+    let _ = io::stdout().flush();
+}
+
+fn show_lobby_help() {
+    println!("{}", t!("lobby-help"));
+    prompt(">");
+}
+
+// "chercher 1400-1600 25+0 tournament": every part is optional and they may
+// come in any order.
+fn parse_seek(args: &[&str]) -> Option<ClientMessage> {
+    let mut rating_min = None;
+    let mut rating_max = None;
+    let mut time_control = None;
+    let mut rules = None;
+
+    for arg in args {
+        if let Some(preset) = RulePreset::parse(arg) {
+            rules = Some(preset);
+        } else if let Some((min, max)) = arg.split_once('-') {
+            rating_min = Some(min.parse::<f64>().ok()?);
+            rating_max = Some(max.parse::<f64>().ok()?);
+        } else {
+            time_control = Some(TimeControl::parse(arg)?);
+        }
+    }
+
+    Some(ClientMessage::Seek {
+        rating_min,
+        rating_max,
+        time_control,
+        rules,
+    })
+}
+
+// "nouvelle 2 family": the seats, then the rules if not the server's.
+fn parse_new_game(seats: &str, rules: Option<&str>) -> Option<ClientMessage> {
+    Some(ClientMessage::CreateGame {
+        seats: seats.parse().ok()?,
+        time_control: None,
+        rules: match rules {
+            Some(name) => Some(RulePreset::parse(name)?),
+            None => None,
+        },
+    })
+}
+
+fn prompt_move() {
+    prompt(&t!("prompt-remote-move"));
+}
+
+fn lobby_command(conn: &mut Connection, line: &str) -> io::Result<()> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let msg = match parts.as_slice() {
+        [cmd] if keywords::is(keywords::LIST, cmd) => ClientMessage::ListGames,
+        [cmd, seats, rules @ ..] if keywords::is(keywords::NEW, cmd) && rules.len() <= 1 => {
+            match parse_new_game(seats, rules.first().copied()) {
+                Some(msg) => msg,
+                None => {
+                    show_lobby_help();
+                    return Ok(());
+                }
+            }
+        }
+        [cmd, id] if keywords::is(keywords::JOIN, cmd) => match id.parse::<u32>() {
+            Ok(game_id) => ClientMessage::JoinGame { game_id },
+            Err(_) => {
+                show_lobby_help();
+                return Ok(());
+            }
+        },
+        [cmd, id] if keywords::is(keywords::WATCH, cmd) => match id.parse::<u32>() {
+            Ok(game_id) => ClientMessage::Watch { game_id },
+            Err(_) => {
+                show_lobby_help();
+                return Ok(());
+            }
+        },
+        [cmd, id] if keywords::is(keywords::REVIEW, cmd) => match id.parse::<u32>() {
+            Ok(game_id) => ClientMessage::Review { game_id },
+            Err(_) => {
+                show_lobby_help();
+                return Ok(());
+            }
+        },
+        [cmd] if keywords::is(keywords::CANCEL, cmd) => ClientMessage::CancelSeek,
+        [cmd, args @ ..] if keywords::is(keywords::SEEK, cmd) => match parse_seek(args) {
+            Some(msg) => msg,
+            None => {
+                show_lobby_help();
+                return Ok(());
+            }
+        },
+        _ => {
+            show_lobby_help();
+            return Ok(());
+        }
+    };
+    conn.send(&msg)
+}
+
+fn show_lookup(remote: &RemoteGame, lookup: TurnInput) {
+    let Some(view) = &remote.view else {
+        return;
+    };
+    match lookup {
+        TurnInput::ShowUnseen => display::show_unseen(&view.unseen_tiles()),
+        TurnInput::ShowBoard => display::show_view(view),
+        TurnInput::ShowHooks(word) => match (&remote.lexicon, word) {
+            (None, _) => println!("{}", t!("no-dictionary")),
+            (Some(lexicon), Some(word)) => {
+                words::run_hooks(&word, words::HOOKS_SHOWN, lexicon, view.tile_set)
+            }
+            (Some(lexicon), None) => {
+                let board = view.to_board();
+                words::show_board_hooks(&board, &hooks::board_hooks(&board, lexicon, view.tile_set))
+            }
+        },
+        TurnInput::Define(word) => match &remote.definitions {
+            Some(definitions) => println!("{}", display::definition_line(definitions, &word)),
+            None => println!("{}", t!("no-definitions")),
+        },
+        TurnInput::Play(_) | TurnInput::Chat(_) => {}
+    }
+}
+
+fn game_command(conn: &mut Connection, remote: &RemoteGame, line: &str) -> io::Result<()> {
+    let Some(game_id) = remote.game_id else {
+        return Ok(());
+    };
+    if let Some(command) = chat::parse_command(line) {
+        let msg = match command {
+            Ok(ChatCommand::Say(body)) => ClientMessage::Chat { game_id, body },
+            Ok(ChatCommand::Mute(name)) => ClientMessage::Mute { game_id, name },
+            Ok(ChatCommand::Unmute(name)) => ClientMessage::Unmute { game_id, name },
+            Ok(ChatCommand::Show) => {
+                chat::show_pane(&remote.chat);
+                return Ok(());
+            }
+            Err(e) => {
+                chat::show_error(&e);
+                chat::show_help();
+                return Ok(());
+            }
+        };
+        return conn.send(&msg);
+    }
+    if let Some(lookup) = menu::parse_lookup(line) {
+        match lookup {
+            Ok(lookup) => show_lookup(remote, lookup),
+            Err(e) => println!("{}", t!("input-retry", error = e.to_string())),
+        }
+        return Ok(());
+    }
+    if remote.view.as_ref().is_some_and(|v| v.seat.is_none()) {
+        println!("{}", t!("remote-spectator"));
+        return Ok(());
+    }
+    if !remote.my_turn() {
+        println!("{}", t!("remote-not-your-turn"));
+        return Ok(());
+    }
+
+    match menu::parse_move(line) {
+        Ok(action) => {
+            if let Some(play) = Play::from_action(action) {
+                conn.send(&ClientMessage::Play { game_id, play })?;
+            }
+        }
+        Err(e) => {
+            println!("{}", t!("input-retry", error = e.to_string()));
+            prompt_move();
+        }
+    }
+    Ok(())
+}
+
+fn on_server_message(
+    conn: &mut Connection,
+    remote: &mut RemoteGame,
+    msg: ServerMessage,
+) -> io::Result<()> {
+    match msg {
+        ServerMessage::Welcome { token, .. } => {
+            conn.set_token(token);
+            if remote.game_id.is_none() {
+                println!("{}", t!("remote-connected", name = conn.name()));
+                conn.send(&ClientMessage::ListGames)?;
+            }
+        }
+        ServerMessage::Error { error } => {
+            println!("{}", t!("remote-server-says", message = error.to_string()));
+        }
+        ServerMessage::GameList { games } => {
+            display::show_game_list(&games);
+            show_lobby_help();
+        }
+        ServerMessage::Joined { game_id, seat } => {
+            remote.game_id = Some(game_id);
+            println!("{}", t!("remote-joined", game = game_id, seat = seat + 1));
+            if remote.view.is_none() {
+                println!("{}", t!("remote-waiting-players"));
+            }
+        }
+        ServerMessage::State { game_id, view } => {
+            if remote.game_id != Some(game_id) {
+                return Ok(());
+            }
+            let first = remote.view.is_none();
+            display::show_view(&view);
+            remote.chat = view.chat.clone();
+            remote.view = Some(*view);
+            if first {
+                chat::show_help();
+                chat::show_pane(&remote.chat);
+            }
+
+            if remote.my_turn() {
+                prompt_move();
+            } else if let Some(view) = &remote.view {
+                if view.game_over {
+                    println!("{}", t!("game-over"));
+                } else if let Some(player) = view.players.get(view.current_player_index) {
+                    println!("{}", t!("remote-waiting-for", name = &player.name));
+                }
+            }
+        }
+        ServerMessage::Event { event, .. } => {
+            display::show_remote_event(remote.view.as_ref(), &event);
+            if let Some(definitions) = &remote.definitions {
+                for line in display::event_definitions(&event, definitions) {
+                    println!("{}", line);
+                }
+            }
+        }
+        ServerMessage::MoveRejected { reason, .. } => {
+            println!("{}", t!("move-rejected", reason = reason.to_string()));
+            prompt_move();
+        }
+        ServerMessage::Seeking { rating } => {
+            println!(
+                "{}",
+                t!("remote-seeking", rating = format!("{:.0}", rating))
+            );
+        }
+        ServerMessage::SeekCancelled => {
+            println!("{}", t!("remote-seek-cancelled"));
+            prompt(">");
+        }
+        ServerMessage::Watching { game_id } => {
+            remote.game_id = Some(game_id);
+            println!("{}", t!("remote-watching", game = game_id));
+        }
+        ServerMessage::Review { view, history, .. } => {
+            display::show_review(&view, &history);
+            show_lobby_help();
+        }
+        ServerMessage::Chat { game_id, message } => {
+            if remote.game_id == Some(game_id) {
+                chat::show_message(&message);
+                remote.chat.push(message);
+            }
+        }
+        ServerMessage::Muted { name, muted, .. } => {
+            if muted {
+                println!("{}", t!("remote-muted", name = name));
+            } else {
+                println!("{}", t!("remote-unmuted", name = name));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn join(addr: &str, settings: &Settings) -> io::Result<()> {
+    let name = loop {
+        prompt(&t!("prompt-name"));
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Ok(());
+        }
+        let name = input.trim().to_string();
+        if !name.is_empty() {
+            break name;
+        }
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut conn = Connection::new(addr.to_string(), name, tx.clone());
+    conn.connect()?;
+    spawn_stdin(tx);
+
+    let mut remote = RemoteGame {
+        game_id: None,
+        view: None,
+        chat: Vec::new(),
+        lexicon: settings.game.lexicon.clone(),
+        definitions: settings.definitions.clone(),
+    };
+
+    for event in rx {
+        match event {
+            ClientEvent::Server(msg) => {
+                if let Err(e) = on_server_message(&mut conn, &mut remote, msg) {
+                    println!("{}", t!("remote-send-failed", error = e.to_string()));
+                }
+            }
+            ClientEvent::Disconnected { generation } => {
+                if !conn.is_current(generation) {
+                    continue;
+                }
+                println!("{}", t!("remote-reconnecting", addr = addr));
+                conn.reconnect()?;
+            }
+            ClientEvent::Line(line) => {
+                let line = line.trim();
+                if keywords::is(keywords::QUIT, line) {
+                    break;
+                }
+                let sent = if remote.game_id.is_some() {
+                    game_command(&mut conn, &remote, line)
+                } else {
+                    lobby_command(&mut conn, line)
+                };
+                // A broken connection is picked up by the reader thread,
+                // which triggers the reconnection above.
+                if let Err(e) = sent {
+                    println!("{}", t!("remote-send-failed", error = e.to_string()));
+                }
+            }
+            ClientEvent::InputClosed => break,
+        }
+    }
+
+    Ok(())
+}
+
+// The host runs the server in the background and plays from the same
+// terminal like any other client.
+pub fn host(addr: &str, settings: &Settings) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    println!("{}", t!("remote-hosting", addr = local.to_string()));
+
+    let config = ServerConfig::from_settings(settings);
+    thread::spawn(move || {
+        if let Err(e) = server::serve(listener, config) {
+            eprintln!("{}", t!("remote-server-stopped", error = e.to_string()));
+        }
+    });
+
+    join(&format!("127.0.0.1:{}", local.port()), settings)
+}
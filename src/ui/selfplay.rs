@@ -0,0 +1,277 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::Instant;
+
+use clap::Args;
+
+use crate::game::bot::Strategy;
+use crate::game::lexicon::Lexicon;
+use crate::game::movegen::Trie;
+use crate::game::selfplay::{self, Aborted, EntrantStats, GameRecord};
+use crate::game::{Game, GameOptions};
+use crate::profile::ProfileStore;
+use crate::profile::rating::RatingMode;
+use crate::save::now_unix;
+use crate::t;
+
+const SELFPLAY_DIR: &str = "selfplay";
+// Progress lines over a run.
+const PROGRESS_STEPS: u64 = 10;
+
+// `krabbels selfplay`: bots against each other, no board drawn.
+#[derive(Debug, Args)]
+pub struct SelfplayArgs {
+    /// Games to play
+    #[arg(long, short, default_value_t = 100)]
+    pub games: u64,
+    /// Bots at the table, 2 to 4, comma separated
+    #[arg(long, short, value_enum, value_delimiter = ',', default_values_t = [Strategy::Greedy, Strategy::Equity])]
+    pub bots: Vec<Strategy>,
+    /// Seed of the first game; game n uses seed + n
+    #[arg(long, short, default_value_t = 1)]
+    pub seed: u64,
+    /// Games played at once [default: one per CPU]
+    #[arg(long, short)]
+    pub threads: Option<usize>,
+    /// Archive of the games, one JSON object per line
+    /// [default: <data dir>/selfplay/selfplay_<time>.jsonl]
+    #[arg(long, conflicts_with = "no_archive")]
+    pub archive: Option<PathBuf>,
+    /// Don't keep the games
+    #[arg(long)]
+    pub no_archive: bool,
+    /// Leave the bots' ratings in the player profiles alone
+    #[arg(long)]
+    pub no_ratings: bool,
+}
+
+// A table with the names left-aligned and the figures right-aligned,
+// every column as wide as its longest cell.
+fn show_stats(entrants: &[selfplay::Entrant], stats: &[EntrantStats]) {
+    let header = [
+        "selfplay-player",
+        "selfplay-wins",
+        "selfplay-win-rate",
+        "selfplay-spread",
+        "selfplay-score",
+        "selfplay-bingos",
+        "selfplay-move",
+    ]
+    .map(|id| t!(id))
+    .to_vec();
+    let mut rows = vec![header];
+    for (entrant, stats) in entrants.iter().zip(stats) {
+        let (low, high) = stats.confidence_interval();
+        rows.push(vec![
+            entrant.name.clone(),
+            format!("{:.1}", stats.wins),
+            format!(
+                "{:.1}% ({:.1}-{:.1})",
+                100.0 * stats.win_rate(),
+                100.0 * low,
+                100.0 * high
+            ),
+            format!("{:+.1}", stats.average_spread()),
+            format!("{:.1}", stats.average_score()),
+            format!("{:.2}", stats.bingo_rate()),
+            format!("{:.1}", stats.average_move()),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| {
+                if i == 0 {
+                    format!("{:<width$}", cell)
+                } else {
+                    format!("{:>width$}", cell)
+                }
+            })
+            .collect();
+        println!("{}", cells.join("  "));
+    }
+}
+
+fn open_archive(args: &SelfplayArgs, data_dir: &Path) -> io::Result<Option<(PathBuf, File)>> {
+    if args.no_archive {
+        return Ok(None);
+    }
+    let path = match &args.archive {
+        Some(path) => path.clone(),
+        None => {
+            let dir = data_dir.join(SELFPLAY_DIR);
+            fs::create_dir_all(&dir)?;
+            dir.join(format!("selfplay_{}.jsonl", now_unix()))
+        }
+    };
+    let file = File::create(&path)?;
+    Ok(Some((path, file)))
+}
+
+// Plays the games on worker threads and tallies them as they come in.
+// Finished games are rated like any other, under the bots' names.
+pub fn run(
+    args: &SelfplayArgs,
+    lexicon: &Lexicon,
+    options: &GameOptions,
+    rating_mode: RatingMode,
+    data_dir: &Path,
+) -> io::Result<()> {
+    if !(2..=4).contains(&args.bots.len()) {
+        eprintln!("{}", t!("selfplay-players"));
+        return Ok(());
+    }
+    let entrants = Arc::new(selfplay::entrants(&args.bots));
+    let threads = args
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, args.games.max(1) as usize);
+    let archive = open_archive(args, data_dir)?;
+    let mut profiles = if args.no_ratings {
+        None
+    } else {
+        Some(ProfileStore::load(data_dir)?)
+    };
+    let mut writer = archive.as_ref().map(|(_, file)| BufWriter::new(file));
+
+    println!(
+        "{}",
+        t!(
+            "selfplay-running",
+            games = args.games,
+            players = entrants
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            threads = threads,
+            seed = args.seed
+        )
+    );
+    let started = Instant::now();
+    let trie = Arc::new(Trie::new(lexicon));
+    let options = Arc::new(options.clone());
+    let next = Arc::new(AtomicU64::new(0));
+    let (sender, results) = mpsc::channel::<Result<(GameRecord, Game), Aborted>>();
+
+    let workers: Vec<_> = (0..threads)
+        .map(|_| {
+            let (entrants, trie, options, next) = (
+                entrants.clone(),
+                trie.clone(),
+                options.clone(),
+                next.clone(),
+            );
+            let sender = sender.clone();
+            let (games, seed) = (args.games, args.seed);
+            thread::spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= games {
+                        break;
+                    }
+                    let seats = selfplay::seating(index, entrants.len());
+                    let seed = seed.wrapping_add(index);
+                    let result = selfplay::play(seed, &seats, &entrants, &options, &trie)
+                        .map(|game| (GameRecord::new(seed, &seats, &game), game));
+                    if sender.send(result).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut stats = vec![EntrantStats::default(); entrants.len()];
+    let mut done = 0;
+    let mut aborted = 0;
+    let step = (args.games / PROGRESS_STEPS).max(1);
+    for result in results {
+        done += 1;
+        let (record, game) = match result {
+            Ok(played) => played,
+            // Left out of the stats, the ratings and the archive.
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    t!(
+                        "selfplay-aborted",
+                        seed = e.seed,
+                        turn = e.turn,
+                        reason = e.error.to_string()
+                    )
+                );
+                aborted += 1;
+                continue;
+            }
+        };
+        for (seat, &entrant) in record.seats.iter().enumerate() {
+            stats[entrant].add(&record, seat);
+        }
+        if let Some(profiles) = &mut profiles
+            && game.game_over
+        {
+            profiles.record_bot_game(&game, rating_mode, now_unix());
+        }
+        if let Some(writer) = &mut writer {
+            serde_json::to_writer(&mut *writer, &record).map_err(io::Error::other)?;
+            writer.write_all(b"\n")?;
+        }
+        if done % step == 0 && done < args.games {
+            eprintln!(
+                "{}",
+                t!("selfplay-progress", done = done, games = args.games)
+            );
+        }
+    }
+    for worker in workers {
+        let _ = worker.join();
+    }
+    if let Some(writer) = &mut writer {
+        writer.flush()?;
+    }
+    if let Some(profiles) = &profiles {
+        profiles.save(data_dir)?;
+    }
+
+    println!(
+        "{}",
+        t!(
+            "selfplay-done",
+            games = done,
+            secs = format!("{:.1}", started.elapsed().as_secs_f64())
+        )
+    );
+    if aborted > 0 {
+        println!("{}", t!("selfplay-aborted-count", count = aborted));
+    }
+    println!();
+    show_stats(&entrants, &stats);
+    println!();
+    if let Some((path, _)) = &archive {
+        println!(
+            "{}",
+            t!("selfplay-archived", path = path.display().to_string())
+        );
+    }
+    if profiles.is_some() {
+        println!("{}", t!("selfplay-rated"));
+    }
+    Ok(())
+}
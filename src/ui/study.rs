@@ -0,0 +1,218 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use clap::Args;
+
+use crate::game::bag::TileSet;
+use crate::game::lexicon::Lexicon;
+use crate::save::{format_unix, now_unix};
+use crate::study::cards::{self, AnagramIndex, CardStore, QuizSource};
+use crate::t;
+use crate::ui::keywords;
+use crate::ui::theme::appearance;
+
+// Days covered by the progress report.
+const PROGRESS_DAYS: i64 = 30;
+
+// "1-200": probability ranks, both ends included.
+fn parse_ranks(input: &str) -> Result<(usize, usize), String> {
+    let invalid = || t!("study-invalid-ranks", value = input);
+    let (from, to) = input.split_once('-').ok_or_else(invalid)?;
+    let from: usize = from.trim().parse().map_err(|_| invalid())?;
+    let to: usize = to.trim().parse().map_err(|_| invalid())?;
+    if from == 0 || to < from {
+        return Err(invalid());
+    }
+    Ok((from, to))
+}
+
+// `krabbels study`: a quiz of alphagrams, the cards due first.
+#[derive(Debug, Args)]
+pub struct StudyArgs {
+    /// Word length of the quiz
+    #[arg(long, short, default_value_t = 7)]
+    pub length: usize,
+    /// Probability ranks to draw new cards from, e.g. 1-200 (most likely first)
+    #[arg(long, short, value_parser = parse_ranks, conflicts_with = "list")]
+    pub ranks: Option<(usize, usize)>,
+    /// File of words or alphagrams to study, one per line
+    #[arg(long)]
+    pub list: Option<PathBuf>,
+    /// Cards in the session
+    #[arg(long, default_value_t = 20)]
+    pub size: usize,
+    /// New cards at most in the session
+    #[arg(long, default_value_t = 10)]
+    pub new: usize,
+    /// Show the progress report instead of quizzing
+    #[arg(long)]
+    pub progress: bool,
+}
+
+impl StudyArgs {
+    fn source(&self) -> io::Result<QuizSource> {
+        if let Some(path) = &self.list {
+            let text = fs::read_to_string(path)?;
+            let entries = text
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .filter_map(|line| line.split_whitespace().next())
+                .map(str::to_string)
+                .collect();
+            return Ok(QuizSource::List(entries));
+        }
+        Ok(match self.ranks {
+            Some((from, to)) => QuizSource::Probability {
+                length: self.length,
+                from,
+                to,
+            },
+            None => QuizSource::Length(self.length),
+        })
+    }
+}
+
+fn read_answer() -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_uppercase()))
+}
+
+fn show_summary(store: &CardStore, now: i64) {
+    let summary = store.summary(now);
+    println!(
+        "{}",
+        t!(
+            "study-summary",
+            cards = summary.cards,
+            due = summary.due,
+            mastered = summary.mastered
+        )
+    );
+    if summary.reviews > 0 {
+        println!(
+            "{}",
+            t!(
+                "study-accuracy",
+                reviews = summary.reviews,
+                percent = summary.correct * 100 / summary.reviews
+            )
+        );
+    }
+}
+
+pub fn show_progress(store: &CardStore, now: i64) {
+    show_summary(store, now);
+    let days = store.progress(PROGRESS_DAYS, now);
+    if days.is_empty() {
+        println!("{}", t!("study-no-reviews", days = PROGRESS_DAYS));
+        return;
+    }
+    println!(
+        "{:<10}  {:>8}  {:>6}  {:>8}",
+        t!("study-day"),
+        t!("study-answers"),
+        t!("study-right"),
+        t!("study-time")
+    );
+    for day in days {
+        let date = format_unix(day.day * 86_400);
+        println!(
+            "{:<10}  {:>8}  {:>5}%  {:>7.1}s",
+            &date[..10],
+            day.reviews,
+            day.correct * 100 / day.reviews,
+            day.total_ms as f64 / day.reviews as f64 / 1000.0
+        );
+    }
+}
+
+// One card: the alphagram, the answer typed and how it went. None when the
+// player left.
+fn ask(alphagram: &str, solutions: &[String]) -> io::Result<Option<(bool, bool, u64)>> {
+    print!(
+        "{} ",
+        t!(
+            "study-question",
+            letters = alphagram,
+            count = solutions.len()
+        )
+    );
+    io::stdout().flush()?;
+    let started = Instant::now();
+    let Some(answer) = read_answer()? else {
+        return Ok(None);
+    };
+    // Quitting words can be answers too.
+    if keywords::is(keywords::QUIT, &answer) && !solutions.contains(&answer) {
+        return Ok(None);
+    }
+    let response_ms = started.elapsed().as_millis() as u64;
+
+    let given: BTreeSet<&str> = answer.split_whitespace().collect();
+    let expected: BTreeSet<&str> = solutions.iter().map(String::as_str).collect();
+    let found = given.intersection(&expected).count();
+    let correct = given == expected;
+    let theme = appearance().theme;
+    if correct {
+        println!("  {}", theme.success().apply(&t!("study-correct")));
+    } else {
+        let wrong: Vec<&str> = given.difference(&expected).copied().collect();
+        println!(
+            "  {}",
+            theme
+                .alert()
+                .apply(&t!("study-wrong", words = solutions.join(", ")))
+        );
+        if !wrong.is_empty() {
+            println!("  {}", t!("study-not-words", words = wrong.join(", ")));
+        }
+    }
+    Ok(Some((correct, found > 0, response_ms)))
+}
+
+pub fn run(
+    args: &StudyArgs,
+    lexicon: &Lexicon,
+    tile_set: TileSet,
+    data_dir: &Path,
+) -> io::Result<()> {
+    let mut store = CardStore::load(data_dir)?;
+    if args.progress {
+        show_progress(&store, now_unix());
+        return Ok(());
+    }
+
+    let index = AnagramIndex::new(lexicon);
+    let candidates = index.select(&args.source()?, tile_set);
+    let session = store.session(&candidates, args.size, args.new, now_unix());
+    if session.is_empty() {
+        println!("{}", t!("study-nothing-due"));
+        show_summary(&store, now_unix());
+        return Ok(());
+    }
+
+    println!("{}", t!("study-help", count = session.len()));
+    let (mut asked, mut right) = (0, 0);
+    for alphagram in &session {
+        let Some((correct, partly, response_ms)) = ask(alphagram, index.solutions(alphagram))?
+        else {
+            break;
+        };
+        let quality = cards::quality(correct, partly, response_ms);
+        store.review(alphagram, quality, response_ms, now_unix());
+        // Saved after every card: leaving halfway loses nothing.
+        store.save(data_dir)?;
+        asked += 1;
+        right += correct as usize;
+    }
+
+    println!("{}", t!("study-done", asked = asked, right = right));
+    show_summary(&store, now_unix());
+    Ok(())
+}
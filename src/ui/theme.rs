@@ -0,0 +1,222 @@
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+
+use crate::game::board::CellKind;
+
+static APPEARANCE: OnceLock<Appearance> = OnceLock::new();
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Classic,
+    // Dark text on bright backgrounds, bold where it matters.
+    HighContrast,
+    // Okabe-Ito colors, told apart with any kind of color blindness.
+    Colorblind,
+    // No colors at all; the square labels carry everything.
+    Mono,
+}
+
+// How a piece of text is drawn. Both front ends translate it: `colored` in
+// line mode, ratatui styles in the full-screen one.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Paint {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub underline: bool,
+}
+
+impl Paint {
+    const fn fg(color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            bg: None,
+            bold: false,
+            underline: false,
+        }
+    }
+
+    const fn on(fg: Color, bg: Color) -> Self {
+        Self {
+            fg: Some(fg),
+            bg: Some(bg),
+            bold: true,
+            underline: false,
+        }
+    }
+
+    const fn bold() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            bold: true,
+            underline: false,
+        }
+    }
+
+    pub fn apply(self, text: &str) -> ColoredString {
+        let mut out = text.normal();
+        if let Some(fg) = self.fg {
+            out = out.color(fg);
+        }
+        if let Some(bg) = self.bg {
+            out = out.on_color(bg);
+        }
+        if self.bold {
+            out = out.bold();
+        }
+        if self.underline {
+            out = out.underline();
+        }
+        out
+    }
+}
+
+const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::TrueColor { r, g, b }
+}
+
+impl Theme {
+    pub fn premium(self, kind: CellKind) -> Paint {
+        match (self, kind) {
+            (_, CellKind::Normal) | (Theme::Mono, _) => Paint::default(),
+            (Theme::Classic, CellKind::DoubleLetter) => Paint::fg(Color::Blue),
+            (Theme::Classic, CellKind::TripleLetter) => Paint::fg(Color::Cyan),
+            (Theme::Classic, CellKind::DoubleWord) => Paint::fg(Color::Red),
+            (Theme::Classic, CellKind::TripleWord) => Paint::fg(Color::Magenta),
+            (Theme::HighContrast, CellKind::DoubleLetter) => {
+                Paint::on(Color::Black, Color::BrightCyan)
+            }
+            (Theme::HighContrast, CellKind::TripleLetter) => {
+                Paint::on(Color::BrightWhite, Color::Blue)
+            }
+            (Theme::HighContrast, CellKind::DoubleWord) => {
+                Paint::on(Color::Black, Color::BrightMagenta)
+            }
+            (Theme::HighContrast, CellKind::TripleWord) => {
+                Paint::on(Color::BrightWhite, Color::Red)
+            }
+            (Theme::Colorblind, CellKind::DoubleLetter) => Paint::fg(rgb(86, 180, 233)),
+            (Theme::Colorblind, CellKind::TripleLetter) => Paint::fg(rgb(0, 114, 178)),
+            (Theme::Colorblind, CellKind::DoubleWord) => Paint::fg(rgb(230, 159, 0)),
+            (Theme::Colorblind, CellKind::TripleWord) => Paint::fg(rgb(213, 94, 0)),
+        }
+    }
+
+    // Letters already on the board.
+    pub fn letter(self) -> Paint {
+        match self {
+            Theme::HighContrast => Paint {
+                fg: Some(Color::BrightWhite),
+                ..Paint::bold()
+            },
+            _ => Paint::bold(),
+        }
+    }
+
+    // Tiles laid this turn but not played yet.
+    pub fn pending(self) -> Paint {
+        match self {
+            Theme::Classic => Paint::on(Color::Black, Color::Yellow),
+            Theme::HighContrast => Paint::on(Color::Black, Color::BrightYellow),
+            Theme::Colorblind => Paint::on(Color::Black, rgb(240, 228, 66)),
+            Theme::Mono => Paint {
+                underline: true,
+                ..Paint::bold()
+            },
+        }
+    }
+
+    // Flagged clocks and errors.
+    pub fn alert(self) -> Paint {
+        match self {
+            Theme::Classic => Paint::fg(Color::Red),
+            Theme::HighContrast => Paint {
+                fg: Some(Color::BrightRed),
+                ..Paint::bold()
+            },
+            Theme::Colorblind => Paint::fg(rgb(213, 94, 0)),
+            Theme::Mono => Paint::bold(),
+        }
+    }
+
+    // Confirmations and the live score.
+    pub fn success(self) -> Paint {
+        match self {
+            Theme::Classic => Paint::fg(Color::Green),
+            Theme::HighContrast => Paint {
+                fg: Some(Color::BrightGreen),
+                ..Paint::bold()
+            },
+            Theme::Colorblind => Paint::fg(rgb(0, 158, 115)),
+            Theme::Mono => Paint::default(),
+        }
+    }
+}
+
+// Line drawing for the board and the rack.
+pub struct Frame {
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    // Corners and joints, left to right: top, middle and bottom rows.
+    pub top: [&'static str; 3],
+    pub middle: [&'static str; 3],
+    pub bottom: [&'static str; 3],
+    pub centre: &'static str,
+    pub marker: &'static str,
+}
+
+const UNICODE_FRAME: Frame = Frame {
+    horizontal: "─",
+    vertical: "│",
+    top: ["┌", "┬", "┐"],
+    middle: ["├", "┼", "┤"],
+    bottom: ["└", "┴", "┘"],
+    centre: "★",
+    marker: "▶",
+};
+
+const ASCII_FRAME: Frame = Frame {
+    horizontal: "-",
+    vertical: "|",
+    top: ["+", "+", "+"],
+    middle: ["+", "+", "+"],
+    bottom: ["+", "+", "+"],
+    centre: "*",
+    marker: ">",
+};
+
+impl Frame {
+    pub fn get(ascii: bool) -> &'static Frame {
+        if ascii { &ASCII_FRAME } else { &UNICODE_FRAME }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Appearance {
+    pub theme: Theme,
+    // Plain ASCII instead of box drawing, stars and superscripts.
+    pub ascii: bool,
+    // Screen-reader output: the board and the moves told in sentences.
+    pub linear: bool,
+}
+
+impl Appearance {
+    pub fn frame(&self) -> &'static Frame {
+        Frame::get(self.ascii)
+    }
+}
+
+// Set once at startup, like the language.
+pub fn set_appearance(appearance: Appearance) {
+    let _ = APPEARANCE.set(appearance);
+}
+
+pub fn appearance() -> Appearance {
+    APPEARANCE.get().copied().unwrap_or_default()
+}
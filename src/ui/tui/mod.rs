@@ -0,0 +1,445 @@
+mod placement;
+mod widgets;
+
+use std::io;
+use std::time::Duration;
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Gauge, Paragraph, Wrap},
+};
+
+use self::placement::Placement;
+use self::widgets::{BoardWidget, COMPACT_SIZE, FULL_SIZE, block, rack_lines, style};
+use crate::error::MoveError;
+use crate::game::Game;
+use crate::game::action::Action;
+use crate::game::board::{BOARD_SIZE, Direction, Position};
+use crate::t;
+use crate::ui::chat::{self, PANE_LINES};
+use crate::ui::display::{format_clock, format_unseen_letters, format_unseen_ratio};
+use crate::ui::menu::{self, TurnInput};
+use crate::ui::theme::appearance;
+
+// Redraw at least this often so the clocks keep ticking.
+const TICK: Duration = Duration::from_millis(250);
+const SIDE_MIN_W: u16 = 34;
+
+pub enum Status {
+    Info(String),
+    Error(String),
+}
+
+// Full-screen front end for hot-seat games. It only reads the game: moves
+// come back to the caller as `TurnInput`, like from `menu::prompt_turn`.
+pub struct Tui {
+    terminal: DefaultTerminal,
+    cursor: Position,
+    dir: Direction,
+    input: String,
+    placement: Placement,
+    status: Option<Status>,
+}
+
+impl Tui {
+    pub fn enter() -> io::Result<Self> {
+        Ok(Self {
+            terminal: ratatui::try_init()?,
+            cursor: Position {
+                row: BOARD_SIZE / 2,
+                col: BOARD_SIZE / 2,
+            },
+            dir: Direction::Across,
+            input: String::new(),
+            placement: Placement::default(),
+            status: None,
+        })
+    }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.status = Some(status);
+    }
+
+    // None when the player asked to leave.
+    pub fn prompt_turn(&mut self, game: &Game) -> io::Result<Option<TurnInput>> {
+        // Tiles of a rejected move stay put so the player can fix them.
+        self.placement.sync(game);
+        loop {
+            self.draw(game)?;
+            if !event::poll(TICK)? {
+                continue;
+            }
+            // Resizes need nothing more than the redraw at the top.
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.on_key(game, key) {
+                KeyOutcome::Continue => {}
+                KeyOutcome::Quit => return Ok(None),
+                KeyOutcome::Submit(input) => return Ok(Some(input)),
+            }
+        }
+    }
+
+    fn on_key(&mut self, game: &Game, key: KeyEvent) -> KeyOutcome {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return KeyOutcome::Quit;
+        }
+        // '/' (chat) and ':' (typed move) open the command line; otherwise
+        // letters go straight onto the board.
+        let typing = !self.input.is_empty();
+
+        let last = BOARD_SIZE - 1;
+        match key.code {
+            KeyCode::Left => self.cursor.col = self.cursor.col.saturating_sub(1),
+            KeyCode::Right => self.cursor.col = (self.cursor.col + 1).min(last),
+            KeyCode::Up => self.cursor.row = self.cursor.row.saturating_sub(1),
+            KeyCode::Down => self.cursor.row = (self.cursor.row + 1).min(last),
+            KeyCode::Tab => {
+                self.dir = match self.dir {
+                    Direction::Across => Direction::Down,
+                    Direction::Down => Direction::Across,
+                }
+            }
+            KeyCode::Esc if typing => self.input.clear(),
+            KeyCode::Esc if !self.placement.is_empty() => {
+                if let Some(first) = self.placement.tiles().first() {
+                    self.cursor = first.pos;
+                }
+                self.placement.clear();
+            }
+            KeyCode::Esc => return KeyOutcome::Quit,
+            KeyCode::Backspace if typing => {
+                self.input.pop();
+            }
+            KeyCode::Backspace => {
+                if let Some(pos) = self.placement.recall() {
+                    self.cursor = pos;
+                }
+            }
+            KeyCode::Char(ch) if typing || ch == '/' || ch == ':' => self.input.push(ch),
+            KeyCode::Char(ch) if ch.is_alphabetic() => {
+                let letter = ch.to_uppercase().next().unwrap_or(ch);
+                match self.placement.lay(game, &mut self.cursor, self.dir, letter) {
+                    Ok(()) => self.status = None,
+                    Err(e) => self.status = Some(Status::Error(e.to_string())),
+                }
+            }
+            KeyCode::Enter => match self.submit(game) {
+                Ok(input) => {
+                    self.input.clear();
+                    self.status = None;
+                    return KeyOutcome::Submit(input);
+                }
+                Err(message) => self.status = Some(Status::Error(message)),
+            },
+            _ => {}
+        }
+        KeyOutcome::Continue
+    }
+
+    fn submit(&self, game: &Game) -> Result<TurnInput, String> {
+        let line = self.input.trim();
+        if let Some(command) = chat::parse_command(line) {
+            return command.map(TurnInput::Chat).map_err(|e| e.to_string());
+        }
+        if let Some(typed) = line.strip_prefix(':') {
+            if let Some(lookup) = menu::parse_lookup(typed) {
+                return lookup.map_err(|e| e.to_string());
+            }
+            return menu::parse_move(typed)
+                .map(TurnInput::Play)
+                .map_err(|e| e.to_string());
+        }
+
+        let (pos, dir, word) = self
+            .placement
+            .to_move(&game.board, self.dir)
+            .map_err(|e| e.to_string())?;
+        Ok(TurnInput::Play(Action::PlaceWord { pos, dir, word }))
+    }
+
+    fn draw(&mut self, game: &Game) -> io::Result<()> {
+        let Self {
+            terminal,
+            cursor,
+            dir,
+            input,
+            placement,
+            status,
+        } = self;
+        let preview = (!placement.is_empty()).then(|| {
+            placement
+                .to_move(&game.board, *dir)
+                .and_then(|(pos, dir, word)| game.preview_move(&pos, &dir, &word))
+        });
+        let screen = Screen {
+            game,
+            cursor: *cursor,
+            dir: *dir,
+            input,
+            placement,
+            preview,
+            status: status.as_ref(),
+        };
+        terminal.draw(|frame| screen.render(frame))?;
+        Ok(())
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+enum KeyOutcome {
+    Continue,
+    Quit,
+    Submit(TurnInput),
+}
+
+struct Screen<'a> {
+    game: &'a Game,
+    cursor: Position,
+    dir: Direction,
+    input: &'a str,
+    placement: &'a Placement,
+    // Score of the tiles laid so far, or why they can't be played.
+    preview: Option<Result<u32, MoveError>>,
+    status: Option<&'a Status>,
+}
+
+impl Screen<'_> {
+    fn render(&self, frame: &mut Frame) {
+        let [main, prompt] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(4)]).areas(frame.area());
+
+        let board_size = if main.width >= FULL_SIZE.0 + SIDE_MIN_W && main.height >= FULL_SIZE.1 {
+            FULL_SIZE
+        } else {
+            COMPACT_SIZE
+        };
+
+        // Side panes go to the right of the board when they fit, under it
+        // otherwise.
+        let (board_area, side) = if main.width >= board_size.0 + SIDE_MIN_W {
+            let [board, side] = Layout::horizontal([
+                Constraint::Length(board_size.0 + 1),
+                Constraint::Min(SIDE_MIN_W),
+            ])
+            .areas(main);
+            (board, side)
+        } else {
+            let [board, side] =
+                Layout::vertical([Constraint::Length(board_size.1), Constraint::Min(0)])
+                    .areas(main);
+            (board, side)
+        };
+
+        if board_area.width >= COMPACT_SIZE.0 && board_area.height >= COMPACT_SIZE.1 {
+            frame.render_widget(
+                BoardWidget {
+                    board: &self.game.board,
+                    cursor: Some(self.cursor),
+                    pending: self.placement.tiles(),
+                },
+                board_area,
+            );
+        } else {
+            frame.render_widget(
+                Paragraph::new(t!("tui-too-small")).wrap(Wrap { trim: true }),
+                board_area,
+            );
+        }
+
+        self.render_side(frame, side);
+        self.render_prompt(frame, prompt);
+    }
+
+    fn render_side(&self, frame: &mut Frame, area: Rect) {
+        let players = self.game.players.len() as u16;
+        let unseen = self.game.unseen_tiles(self.game.current_player_index);
+        let unseen_letters = format_unseen_letters(&unseen);
+        // Entries are up to 4 wide plus a space; one more line for the ratio.
+        let per_line = (area.width.saturating_sub(2) as usize + 1) / 5;
+        let unseen_lines = unseen.counts.len().div_ceil(per_line.max(1)) as u16 + 1;
+        let [scores, rack, bag, unseen_area, log, chat] = Layout::vertical([
+            Constraint::Length(players + 2),
+            Constraint::Length(5),
+            Constraint::Length(3),
+            Constraint::Length(unseen_lines + 2),
+            Constraint::Min(3),
+            Constraint::Length(PANE_LINES as u16 + 2),
+        ])
+        .areas(area);
+
+        frame.render_widget(self.scores(), scores);
+
+        let rack_text: Vec<Line> = rack_lines(&self.game.current_player().rack)
+            .into_iter()
+            .map(Line::from)
+            .collect();
+        frame.render_widget(
+            Paragraph::new(rack_text)
+                .block(block(format!(" {} ", self.game.current_player().name))),
+            rack,
+        );
+
+        let left = self.game.bag.tiles.len();
+        let bag_size = self.game.tile_set.tile_count();
+        frame.render_widget(
+            Gauge::default()
+                .block(block(format!(" {} ", t!("tui-bag"))))
+                .gauge_style(style(appearance().theme.success()))
+                .ratio(left as f64 / bag_size.max(1) as f64)
+                .label(t!("tui-bag-count", left = left, total = bag_size)),
+            bag,
+        );
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(unseen_letters),
+                Line::styled(
+                    format_unseen_ratio(&unseen),
+                    Style::default().add_modifier(Modifier::DIM),
+                ),
+            ])
+            .wrap(Wrap { trim: false })
+            .block(block(format!(
+                " {} ",
+                t!("tui-unseen", count = unseen.total())
+            ))),
+            unseen_area,
+        );
+
+        let visible = log.height.saturating_sub(2) as usize;
+        let start = self.game.history.len().saturating_sub(visible);
+        let lines: Vec<Line> = self.game.history[start..]
+            .iter()
+            .map(|record| {
+                let name = self
+                    .game
+                    .players
+                    .iter()
+                    .find(|p| p.id == record.player_id)
+                    .map(|p| p.name.as_str())
+                    .unwrap_or("?");
+                let word = record.words.first().map(|(w, _)| w.as_str()).unwrap_or("-");
+                Line::from(format!(
+                    "{:>3}. {:<10} {:<12} {:>4}{}",
+                    record.turn,
+                    name,
+                    word,
+                    record.score,
+                    if record.bingo { " *" } else { "" }
+                ))
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(block(format!(" {} ", t!("tui-moves")))),
+            log,
+        );
+
+        let reader = &self.game.current_player().name;
+        let lines: Vec<Line> = self
+            .game
+            .chat
+            .recent(Some(reader), PANE_LINES)
+            .iter()
+            .map(|message| Line::from(chat::format_message(message)))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(block(format!(" {} ", t!("chat-title")))),
+            chat,
+        );
+    }
+
+    fn scores(&self) -> Paragraph<'_> {
+        let game = self.game;
+        let look = appearance();
+        let lines: Vec<Line> = game
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| {
+                let current = i == game.current_player_index;
+                let marker = if current { look.frame().marker } else { " " };
+                let mut spans = vec![Span::raw(format!(
+                    "{} {:<12} {:>4}",
+                    marker, player.name, player.score
+                ))];
+                if let Some(clock) = &game.clock {
+                    let time = format!("  {}", format_clock(clock.remaining(i)));
+                    let time_style = if clock.is_flagged(i) {
+                        style(look.theme.alert())
+                    } else {
+                        Style::default()
+                    };
+                    spans.push(Span::styled(time, time_style));
+                }
+                let line = Line::from(spans);
+                if current {
+                    line.style(Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines).block(block(format!(" {} ", t!("tui-turn", turn = game.turn))))
+    }
+
+    fn render_prompt(&self, frame: &mut Frame, area: Rect) {
+        let look = appearance();
+        let col = (b'a' + self.cursor.col as u8) as char;
+        let arrow = match (self.dir, look.ascii) {
+            (Direction::Across, false) => "→",
+            (Direction::Down, false) => "↓",
+            (Direction::Across, true) => "->",
+            (Direction::Down, true) => "v",
+        };
+        // The separator dots of the preview and the key help.
+        let dot = if look.ascii { "-" } else { "·" };
+        let mut title = vec![Span::raw(format!(
+            " {}{} {} ",
+            col,
+            self.cursor.row + 1,
+            arrow
+        ))];
+        match &self.preview {
+            Some(Ok(score)) => title.push(Span::styled(
+                format!("{} ", t!("tui-preview", score = *score).replace('·', dot)),
+                style(look.theme.success()),
+            )),
+            Some(Err(e)) => title.push(Span::styled(
+                format!("{} {} ", dot, e),
+                Style::default().add_modifier(Modifier::DIM),
+            )),
+            None => {}
+        }
+
+        let status = match self.status {
+            Some(Status::Info(text)) => Line::styled(text.as_str(), style(look.theme.success())),
+            Some(Status::Error(text)) => Line::styled(text.as_str(), style(look.theme.alert())),
+            None => Line::styled(
+                t!(if look.ascii {
+                    "tui-keys-ascii"
+                } else {
+                    "tui-keys"
+                }),
+                Style::default().add_modifier(Modifier::DIM),
+            ),
+        };
+        let lines = vec![Line::from(format!("> {}", self.input)), status];
+        frame.render_widget(Paragraph::new(lines).block(block(Line::from(title))), area);
+
+        frame.set_cursor_position((area.x + 3 + self.input.chars().count() as u16, area.y + 1));
+    }
+}
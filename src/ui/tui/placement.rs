@@ -0,0 +1,361 @@
+use crate::error::MoveError;
+use crate::game::Game;
+use crate::game::bag::Tile;
+use crate::game::board::{BOARD_SIZE, Board, Direction, Position, Word};
+
+// A tile laid on the board but not played yet.
+#[derive(Clone, Copy)]
+pub struct Pending {
+    pub pos: Position,
+    pub letter: char,
+    pub blank: bool,
+}
+
+// The move being built with the board cursor.
+#[derive(Default)]
+pub struct Placement {
+    tiles: Vec<Pending>,
+    // (turn, seat) the tiles were laid in; they go away once it's over.
+    owner: Option<(u32, usize)>,
+}
+
+fn step(pos: Position, dir: Direction) -> Option<Position> {
+    let Position { row, col } = pos;
+    let (row, col) = match dir {
+        Direction::Across => (row, col + 1),
+        Direction::Down => (row + 1, col),
+    };
+    (row < BOARD_SIZE && col < BOARD_SIZE).then_some(Position { row, col })
+}
+
+fn step_back(pos: Position, dir: Direction) -> Option<Position> {
+    let Position { row, col } = pos;
+    match dir {
+        Direction::Across => col.checked_sub(1).map(|col| Position { row, col }),
+        Direction::Down => row.checked_sub(1).map(|row| Position { row, col }),
+    }
+}
+
+fn other(dir: Direction) -> Direction {
+    match dir {
+        Direction::Across => Direction::Down,
+        Direction::Down => Direction::Across,
+    }
+}
+
+impl Placement {
+    pub fn tiles(&self) -> &[Pending] {
+        &self.tiles
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tiles.clear();
+    }
+
+    // Drops the tiles if the turn they belong to has ended.
+    pub fn sync(&mut self, game: &Game) {
+        let now = (game.turn, game.current_player_index);
+        if self.owner != Some(now) {
+            self.tiles.clear();
+            self.owner = Some(now);
+        }
+    }
+
+    fn pending_at(&self, pos: Position) -> Option<&Pending> {
+        self.tiles.iter().find(|p| p.pos == pos)
+    }
+
+    fn taken(&self, board: &Board, pos: Position) -> bool {
+        board.cells[pos.row][pos.col].letter.is_some() || self.pending_at(pos).is_some()
+    }
+
+    fn letter_at(&self, board: &Board, pos: Position) -> Option<char> {
+        board.cells[pos.row][pos.col]
+            .letter
+            .or_else(|| self.pending_at(pos).map(|p| p.letter))
+    }
+
+    // Lays a rack tile at the cursor, or at the next free cell after it,
+    // then moves the cursor on to the following free cell.
+    pub fn lay(
+        &mut self,
+        game: &Game,
+        cursor: &mut Position,
+        dir: Direction,
+        letter: char,
+    ) -> Result<(), MoveError> {
+        let board = &game.board;
+        let mut pos = *cursor;
+        while self.taken(board, pos) {
+            pos = step(pos, dir).ok_or(MoveError::OutOfBounds {
+                row: pos.row,
+                col: pos.col,
+            })?;
+        }
+
+        // Same choice as `Board::plan_move`: the letter itself if it's
+        // still on the rack, a blank otherwise.
+        let rack = &game.current_player().rack.tiles;
+        let count = |pred: &dyn Fn(&Tile) -> bool| rack.iter().filter(|t| pred(t)).count();
+        let used = |blank: bool| {
+            self.tiles
+                .iter()
+                .filter(|p| p.blank == blank && (blank || p.letter == letter))
+                .count()
+        };
+        let blank = if count(&|t| !t.is_blank && t.letter == letter) > used(false) {
+            false
+        } else if count(&|t| t.is_blank) > used(true) {
+            true
+        } else {
+            return Err(MoveError::MissingLetter { letter });
+        };
+
+        self.tiles.push(Pending { pos, letter, blank });
+
+        *cursor = pos;
+        while let Some(next) = step(*cursor, dir) {
+            *cursor = next;
+            if !self.taken(board, next) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // Takes back the last tile laid and returns where it was.
+    pub fn recall(&mut self) -> Option<Position> {
+        self.tiles.pop().map(|p| p.pos)
+    }
+
+    // The move spelled out in full, board letters included, the way
+    // `Action::PlaceWord` wants it. One tile alone goes along `dir`
+    // unless only the other direction makes a word.
+    pub fn to_move(
+        &self,
+        board: &Board,
+        dir: Direction,
+    ) -> Result<(Position, Direction, Word), MoveError> {
+        let mut tiles = self.tiles.clone();
+        tiles.sort_by_key(|p| (p.pos.row, p.pos.col));
+        let (first, last) = match (tiles.first(), tiles.last()) {
+            (Some(first), Some(last)) => (first.pos, last.pos),
+            _ => return Err(MoveError::NothingPlaced),
+        };
+
+        let dir = if tiles.len() > 1 {
+            if tiles.iter().all(|p| p.pos.row == first.row) {
+                Direction::Across
+            } else if tiles.iter().all(|p| p.pos.col == first.col) {
+                Direction::Down
+            } else {
+                return Err(MoveError::NotInLine);
+            }
+        } else {
+            let has_neighbour = |dir: Direction| {
+                step(first, dir).is_some_and(|p| self.taken(board, p))
+                    || step_back(first, dir).is_some_and(|p| self.taken(board, p))
+            };
+            if !has_neighbour(dir) && has_neighbour(other(dir)) {
+                other(dir)
+            } else {
+                dir
+            }
+        };
+
+        let mut start = first;
+        while let Some(prev) = step_back(start, dir).filter(|&p| self.taken(board, p)) {
+            start = prev;
+        }
+
+        // Tiles laid keep the blank they were laid as, so the move uses
+        // the same rack tiles the player saw on the board.
+        let mut word = Word { tiles: Vec::new() };
+        let mut pos = Some(start);
+        let mut reached_last = false;
+        while let Some(p) = pos {
+            let Some(letter) = self.letter_at(board, p) else {
+                break;
+            };
+            word.tiles.push(Tile {
+                letter,
+                value: 0,
+                is_blank: self.pending_at(p).is_some_and(|pending| pending.blank),
+            });
+            reached_last |= p == last;
+            pos = step(p, dir);
+        }
+        if !reached_last {
+            return Err(MoveError::NotInLine);
+        }
+        Ok((start, dir, word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameOptions;
+    use crate::game::action::Action;
+
+    // Two players, the first to play with `rack`, taken from the bag so
+    // the tile counts still add up. Without a word list any letters make
+    // a word.
+    fn game_with_rack(rack: &str) -> Game {
+        let mut game = Game::with_options(&GameOptions::default());
+        game.add_player("Ann".to_string());
+        game.add_player("Bob".to_string());
+        let seat = game.current_player_index;
+        let old = std::mem::take(&mut game.players[seat].rack.tiles);
+        game.bag.tiles.extend(old);
+        for letter in rack.chars() {
+            let i = game
+                .bag
+                .tiles
+                .iter()
+                .position(|t| t.letter == letter)
+                .unwrap();
+            let tile = game.bag.tiles.remove(i);
+            game.players[seat].rack.tiles.push(tile);
+        }
+        game
+    }
+
+    fn at(row: usize, col: usize) -> Position {
+        Position { row, col }
+    }
+
+    fn spelled(word: &Word) -> Vec<(char, bool)> {
+        word.tiles.iter().map(|t| (t.letter, t.is_blank)).collect()
+    }
+
+    #[test]
+    fn lay_uses_the_letter_then_a_blank() {
+        let game = game_with_rack("AA_T");
+        let mut placement = Placement::default();
+        let mut cursor = at(7, 7);
+        for letter in ['A', 'A', 'A'] {
+            placement
+                .lay(&game, &mut cursor, Direction::Across, letter)
+                .unwrap();
+        }
+        let blanks: Vec<bool> = placement.tiles().iter().map(|p| p.blank).collect();
+        assert_eq!(blanks, [false, false, true]);
+        assert_eq!(cursor, at(7, 10));
+        assert!(matches!(
+            placement.lay(&game, &mut cursor, Direction::Across, 'A'),
+            Err(MoveError::MissingLetter { letter: 'A' })
+        ));
+    }
+
+    #[test]
+    fn lay_skips_occupied_squares() {
+        let mut game = game_with_rack("CAT");
+        game.board.cells[7][8].letter = Some('O');
+        let mut placement = Placement::default();
+        let mut cursor = at(7, 7);
+        placement
+            .lay(&game, &mut cursor, Direction::Across, 'C')
+            .unwrap();
+        assert_eq!(cursor, at(7, 9));
+        placement
+            .lay(&game, &mut cursor, Direction::Across, 'T')
+            .unwrap();
+        let laid: Vec<Position> = placement.tiles().iter().map(|p| p.pos).collect();
+        assert_eq!(laid, [at(7, 7), at(7, 9)]);
+
+        let (start, dir, word) = placement.to_move(&game.board, Direction::Across).unwrap();
+        assert_eq!((start, dir), (at(7, 7), Direction::Across));
+        assert_eq!(spelled(&word), [('C', false), ('O', false), ('T', false)]);
+    }
+
+    #[test]
+    fn recall_takes_back_the_last_tile() {
+        let game = game_with_rack("CAT");
+        let mut placement = Placement::default();
+        let mut cursor = at(7, 7);
+        for letter in ['C', 'A'] {
+            placement
+                .lay(&game, &mut cursor, Direction::Down, letter)
+                .unwrap();
+        }
+        assert_eq!(placement.recall(), Some(at(8, 7)));
+        assert_eq!(placement.tiles().len(), 1);
+        assert_eq!(placement.recall(), Some(at(7, 7)));
+        assert_eq!(placement.recall(), None);
+        assert!(matches!(
+            placement.to_move(&game.board, Direction::Down),
+            Err(MoveError::NothingPlaced)
+        ));
+    }
+
+    #[test]
+    fn to_move_rejects_tiles_out_of_line() {
+        let game = game_with_rack("CAT");
+        let mut placement = Placement::default();
+        placement
+            .lay(&game, &mut at(7, 7), Direction::Across, 'C')
+            .unwrap();
+        placement
+            .lay(&game, &mut at(8, 8), Direction::Across, 'A')
+            .unwrap();
+        assert!(matches!(
+            placement.to_move(&game.board, Direction::Across),
+            Err(MoveError::NotInLine)
+        ));
+
+        // In a row but with a gap.
+        placement.recall();
+        placement
+            .lay(&game, &mut at(7, 9), Direction::Across, 'A')
+            .unwrap();
+        assert!(matches!(
+            placement.to_move(&game.board, Direction::Across),
+            Err(MoveError::NotInLine)
+        ));
+    }
+
+    #[test]
+    fn one_tile_goes_the_way_it_makes_a_word() {
+        let mut game = game_with_rack("S");
+        game.board.cells[7][7].letter = Some('C');
+        let mut placement = Placement::default();
+        placement
+            .lay(&game, &mut at(8, 7), Direction::Across, 'S')
+            .unwrap();
+        let (start, dir, word) = placement.to_move(&game.board, Direction::Across).unwrap();
+        assert_eq!((start, dir), (at(7, 7), Direction::Down));
+        assert_eq!(spelled(&word), [('C', false), ('S', false)]);
+    }
+
+    #[test]
+    fn a_blank_laid_out_of_order_stays_a_blank() {
+        let mut game = game_with_rack("A_T");
+        let mut placement = Placement::default();
+        // The last A first, with the A from the rack; the first A then
+        // has only the blank left.
+        placement
+            .lay(&game, &mut at(7, 9), Direction::Across, 'A')
+            .unwrap();
+        let mut cursor = at(7, 7);
+        for letter in ['A', 'T'] {
+            placement
+                .lay(&game, &mut cursor, Direction::Across, letter)
+                .unwrap();
+        }
+
+        let (pos, dir, word) = placement.to_move(&game.board, Direction::Across).unwrap();
+        assert_eq!(spelled(&word), [('A', true), ('T', false), ('A', false)]);
+
+        game.apply_move(Action::PlaceWord { pos, dir, word })
+            .unwrap();
+        let blanks: Vec<bool> = (7..10)
+            .map(|col| game.board.cells[7][col].is_blank)
+            .collect();
+        assert_eq!(blanks, [true, false, false]);
+    }
+}
@@ -0,0 +1,238 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Widget},
+};
+
+use super::placement::Pending;
+use crate::game::board::{BOARD_SIZE, Board, CellKind, Position};
+use crate::game::player::Rack;
+use crate::t;
+use crate::ui::display::{premium_label, superscript_num};
+use crate::ui::theme::{Frame, Paint, appearance};
+
+// Same grid as `display`'s board: 4 columns per cell plus the separators.
+const CELL_W: u16 = 4;
+const LABEL_W: u16 = 4;
+pub const FULL_SIZE: (u16, u16) = (
+    LABEL_W + 1 + BOARD_SIZE as u16 * (CELL_W + 1),
+    2 + BOARD_SIZE as u16 * 2,
+);
+// On small terminals: 2 columns per cell and no row separators. The board
+// is not drawn at all below that size.
+const COMPACT_CELL_W: u16 = 2;
+pub const COMPACT_SIZE: (u16, u16) = (
+    LABEL_W + 1 + BOARD_SIZE as u16 * (COMPACT_CELL_W + 1),
+    3 + BOARD_SIZE as u16,
+);
+
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+fn color(color: colored::Color) -> Color {
+    use colored::Color as C;
+    match color {
+        C::Black => Color::Black,
+        C::Red => Color::Red,
+        C::Green => Color::Green,
+        C::Yellow => Color::Yellow,
+        C::Blue => Color::Blue,
+        C::Magenta => Color::Magenta,
+        C::Cyan => Color::Cyan,
+        C::White => Color::Gray,
+        C::BrightBlack => Color::DarkGray,
+        C::BrightRed => Color::LightRed,
+        C::BrightGreen => Color::LightGreen,
+        C::BrightYellow => Color::LightYellow,
+        C::BrightBlue => Color::LightBlue,
+        C::BrightMagenta => Color::LightMagenta,
+        C::BrightCyan => Color::LightCyan,
+        C::BrightWhite => Color::White,
+        C::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
+
+// The theme's paint as a ratatui style.
+pub fn style(paint: Paint) -> Style {
+    let mut style = Style::default();
+    if let Some(fg) = paint.fg {
+        style = style.fg(color(fg));
+    }
+    if let Some(bg) = paint.bg {
+        style = style.bg(color(bg));
+    }
+    if paint.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if paint.underline {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    style
+}
+
+// A titled pane, in ASCII when asked to.
+pub fn block<'a>(title: impl Into<Line<'a>>) -> Block<'a> {
+    let block = Block::bordered().title(title);
+    if appearance().ascii {
+        block.border_set(ASCII_BORDER)
+    } else {
+        block
+    }
+}
+
+pub struct BoardWidget<'a> {
+    pub board: &'a Board,
+    pub cursor: Option<Position>,
+    pub pending: &'a [Pending],
+}
+
+fn cell_label(board: &Board, row: usize, col: usize) -> Span<'static> {
+    let cell = &board.cells[row][col];
+    let look = appearance();
+    let letter = style(look.theme.letter());
+    match cell.letter {
+        // Blanks show in lowercase, as on a real board.
+        Some(ch) if cell.is_blank => Span::styled(ch.to_ascii_lowercase().to_string(), letter),
+        Some(ch) => Span::styled(ch.to_ascii_uppercase().to_string(), letter),
+        None if row == BOARD_SIZE / 2 && col == BOARD_SIZE / 2 => Span::raw(look.frame().centre),
+        None => match cell.kind {
+            CellKind::Normal => Span::raw(" "),
+            kind => Span::styled(premium_label(kind), style(look.theme.premium(kind))),
+        },
+    }
+}
+
+fn separator(frame: &Frame, joints: [&str; 3], cell_w: u16) -> String {
+    let mut line = String::from(joints[0]);
+    for x in 0..BOARD_SIZE {
+        line.push_str(&frame.horizontal.repeat(cell_w as usize));
+        line.push_str(if x + 1 == BOARD_SIZE {
+            joints[2]
+        } else {
+            joints[1]
+        });
+    }
+    line
+}
+
+impl Widget for BoardWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let look = appearance();
+        let frame = look.frame();
+        let full = area.width >= FULL_SIZE.0 && area.height >= FULL_SIZE.1;
+        let cell_w = if full { CELL_W } else { COMPACT_CELL_W };
+        let step = cell_w + 1;
+        let grid_x = area.x + LABEL_W;
+
+        for col in 0..BOARD_SIZE {
+            let x = grid_x + 1 + col as u16 * step + (cell_w - 1) / 2;
+            let label = ((b'A' + col as u8) as char).to_string();
+            buf.set_string(x, area.y, label, Style::default());
+        }
+
+        let mut y = area.y + 1;
+        buf.set_string(
+            grid_x,
+            y,
+            separator(frame, frame.top, cell_w),
+            Style::default(),
+        );
+        y += 1;
+
+        for row in 0..BOARD_SIZE {
+            buf.set_string(area.x, y, format!("{:>3}", row + 1), Style::default());
+            buf.set_string(grid_x, y, frame.vertical, Style::default());
+
+            for col in 0..BOARD_SIZE {
+                let x = grid_x + 1 + col as u16 * step;
+                let pos = Position { row, col };
+                let label = match self.pending.iter().find(|p| p.pos == pos) {
+                    Some(p) => Span::styled(
+                        if p.blank {
+                            p.letter.to_ascii_lowercase().to_string()
+                        } else {
+                            p.letter.to_string()
+                        },
+                        style(look.theme.pending()),
+                    ),
+                    None => cell_label(self.board, row, col),
+                };
+                let mut style = label.style;
+                if self.cursor == Some(pos) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                let text = format!("{:^1$}", label.content, cell_w as usize);
+                buf.set_string(x, y, text, style);
+                buf.set_string(x + cell_w, y, frame.vertical, Style::default());
+            }
+            y += 1;
+
+            if full && row + 1 < BOARD_SIZE {
+                buf.set_string(
+                    grid_x,
+                    y,
+                    separator(frame, frame.middle, cell_w),
+                    Style::default(),
+                );
+                y += 1;
+            }
+        }
+        buf.set_string(
+            grid_x,
+            y,
+            separator(frame, frame.bottom, cell_w),
+            Style::default(),
+        );
+    }
+}
+
+// The rack as `display` draws it, with narrower tiles to fit a side pane.
+pub fn rack_lines(rack: &Rack) -> Vec<String> {
+    const TILE_W: usize = 4;
+    if rack.tiles.is_empty() {
+        return vec![t!("rack-empty")];
+    }
+    let ascii = appearance().ascii;
+    let frame = Frame::get(ascii);
+
+    let border = |joints: [&str; 3]| {
+        let mut line = String::from(joints[0]);
+        for i in 0..rack.tiles.len() {
+            line.push_str(&frame.horizontal.repeat(TILE_W));
+            line.push_str(if i + 1 == rack.tiles.len() {
+                joints[2]
+            } else {
+                joints[1]
+            });
+        }
+        line
+    };
+
+    let mut tiles = String::from(frame.vertical);
+    for tile in &rack.tiles {
+        let label = if tile.value == 0 {
+            "_".to_string()
+        } else {
+            let value = if ascii {
+                tile.value.to_string()
+            } else {
+                superscript_num(tile.value)
+            };
+            format!("{}{}", tile.letter.to_ascii_uppercase(), value)
+        };
+        tiles.push_str(&format!("{:^1$}{2}", label, TILE_W, frame.vertical));
+    }
+
+    vec![border(frame.top), tiles, border(frame.bottom)]
+}
@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+
+use clap::Args;
+
+use crate::error::QueryError;
+use crate::game::bag::TileSet;
+use crate::game::board::{Board, Position};
+use crate::game::lexicon::Lexicon;
+use crate::game::unseen::BLANK;
+use crate::study::hooks::{self, Hooks};
+use crate::study::search::{self, Found, Pattern, SortOrder, WordQuery};
+use crate::t;
+use crate::ui::display::HookedBoard;
+use crate::ui::theme::appearance;
+
+// Longer words listed by default under a word's hooks.
+pub const HOOKS_SHOWN: usize = 20;
+
+fn parse_pattern(input: &str) -> Result<Pattern, String> {
+    Pattern::parse(input).map_err(|e| e.to_string())
+}
+
+// Upper case, blanks as '_'.
+fn parse_letters(input: &str) -> Result<String, String> {
+    search::parse_letters(input)
+        .map(|letters| letters.into_iter().collect())
+        .map_err(|e| e.to_string())
+}
+
+fn parse_plain_letters(input: &str) -> Result<String, String> {
+    let letters = parse_letters(input)?;
+    if letters.contains(BLANK) {
+        return Err(QueryError::InvalidLetters(input.to_string()).to_string());
+    }
+    Ok(letters)
+}
+
+// `krabbels words`: every option narrows the list down further.
+#[derive(Debug, Args)]
+pub struct WordsArgs {
+    /// Words using every tile of the rack, ? for a blank
+    #[arg(long, short, value_parser = parse_letters, conflicts_with = "build")]
+    pub anagram: Option<String>,
+    /// Words using some of the tiles of the rack, ? for a blank
+    #[arg(long, short, value_parser = parse_letters)]
+    pub build: Option<String>,
+    /// ? for any letter, * for any run of letters, [AEI] for one of them, [^AEI] for none
+    #[arg(long, short, value_parser = parse_pattern)]
+    pub pattern: Option<Pattern>,
+    /// Letters found anywhere in the word
+    #[arg(long, short, value_parser = parse_plain_letters)]
+    pub contains: Option<String>,
+    /// Letters the word starts with
+    #[arg(long, short, value_parser = parse_plain_letters)]
+    pub starts: Option<String>,
+    /// Letters the word ends with
+    #[arg(long, short, value_parser = parse_plain_letters)]
+    pub ends: Option<String>,
+    #[arg(long, default_value_t = 2)]
+    pub min_length: usize,
+    #[arg(long)]
+    pub max_length: Option<usize>,
+    #[arg(long, value_enum, default_value_t)]
+    pub sort: SortOrder,
+    /// Show at most this many words
+    #[arg(long)]
+    pub limit: Option<usize>,
+}
+
+impl WordsArgs {
+    pub fn query(&self) -> WordQuery {
+        WordQuery {
+            rack: self
+                .anagram
+                .as_ref()
+                .or(self.build.as_ref())
+                .map(|letters| letters.chars().collect()),
+            whole_rack: self.anagram.is_some(),
+            pattern: self.pattern.clone(),
+            contains: self.contains.iter().flat_map(|s| s.chars()).collect(),
+            starts: self.starts.clone().unwrap_or_default(),
+            ends: self.ends.clone().unwrap_or_default(),
+            min_length: self.min_length,
+            max_length: self.max_length,
+        }
+    }
+}
+
+// "1 in 3 240" reads better than a probability with six zeros.
+fn odds(probability: f64) -> String {
+    if probability <= 0.0 {
+        return t!("words-odds-none");
+    }
+    t!(
+        "words-odds",
+        odds = (1.0 / probability).round().max(1.0) as u64
+    )
+}
+
+fn show_found(found: &Found, width: usize) {
+    println!(
+        "  {:<width$}  {:>3} {}  {}",
+        found.display_word(),
+        found.score,
+        t!("words-points"),
+        odds(found.probability),
+        width = width
+    );
+}
+
+pub fn run(args: &WordsArgs, lexicon: &Lexicon, tile_set: TileSet) {
+    let mut found = args.query().search(lexicon, tile_set);
+    if found.is_empty() {
+        println!("{}", t!("words-none"));
+        return;
+    }
+    search::sort_words(&mut found, args.sort);
+    println!("{}", t!("words-found", count = found.len()));
+    let shown = &found[..args.limit.unwrap_or(found.len()).min(found.len())];
+    let width = shown
+        .iter()
+        .map(|f| f.word.chars().count())
+        .max()
+        .unwrap_or(0);
+    for word in shown {
+        show_found(word, width);
+    }
+    if shown.len() < found.len() {
+        println!("{}", t!("words-more", count = found.len() - shown.len()));
+    }
+}
+
+fn join_or_none<T: ToString>(items: &[T], separator: &str) -> String {
+    if items.is_empty() {
+        return t!("hooks-none");
+    }
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+pub fn show_hooks(hooks: &Hooks, limit: usize) {
+    println!("{}", t!("hooks-title", word = &hooks.word));
+    println!(
+        "  {}",
+        t!("hooks-front", letters = join_or_none(&hooks.front, " "))
+    );
+    println!(
+        "  {}",
+        t!("hooks-back", letters = join_or_none(&hooks.back, " "))
+    );
+    println!(
+        "  {}",
+        t!("hooks-inner", words = join_or_none(&hooks.inner, ", "))
+    );
+    let shown = &hooks.longer[..limit.min(hooks.longer.len())];
+    println!(
+        "  {}",
+        t!(
+            "hooks-longer",
+            count = hooks.longer.len(),
+            words = join_or_none(shown, ", ")
+        )
+    );
+    if shown.len() < hooks.longer.len() {
+        println!(
+            "  {}",
+            t!("words-more", count = hooks.longer.len() - shown.len())
+        );
+    }
+}
+
+// One line for the full-screen status bar.
+pub fn hooks_summary(hooks: &Hooks) -> String {
+    format!(
+        "{} {}. {}. {}.",
+        t!("hooks-title", word = &hooks.word),
+        t!("hooks-front", letters = join_or_none(&hooks.front, " ")),
+        t!("hooks-back", letters = join_or_none(&hooks.back, " ")),
+        t!("hooks-inner", words = join_or_none(&hooks.inner, ", "))
+    )
+}
+
+pub fn board_hooks_summary(hooks: &BTreeMap<Position, Vec<char>>) -> String {
+    if hooks.is_empty() {
+        return t!("hooks-board-none");
+    }
+    let squares: Vec<String> = hooks
+        .iter()
+        .map(|(pos, letters)| {
+            t!(
+                "hooks-square",
+                pos = pos.to_string(),
+                letters = join_or_none(letters, " ")
+            )
+        })
+        .collect();
+    format!("{} {}", t!("hooks-board-title"), squares.join(", "))
+}
+
+pub fn run_hooks(word: &str, limit: usize, lexicon: &Lexicon, tile_set: TileSet) {
+    show_hooks(&hooks::find_hooks(word, lexicon, tile_set), limit);
+}
+
+// The board with its hook squares, then every square spelled out.
+pub fn show_board_hooks(board: &Board, hooks: &BTreeMap<Position, Vec<char>>) {
+    if !appearance().linear {
+        println!();
+        println!("{}", HookedBoard { board, hooks });
+    }
+    if hooks.is_empty() {
+        println!("{}", t!("hooks-board-none"));
+        return;
+    }
+    println!("{}", t!("hooks-board-title"));
+    for (pos, letters) in hooks {
+        println!(
+            "  {}",
+            t!(
+                "hooks-square",
+                pos = pos.to_string(),
+                letters = join_or_none(letters, " ")
+            )
+        );
+    }
+}
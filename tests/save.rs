@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::Path;
+
+use krabbels::error::SaveError;
+use krabbels::game::Game;
+use krabbels::save::SaveFile;
+use krabbels::save::file::{load_from_path, read_checked, save_error, save_to_path, write_checked};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn error_of<T>(result: std::io::Result<T>) -> SaveError {
+    let err = result.err().expect("the file should be refused");
+    save_error(&err).cloned().expect("a save error")
+}
+
+fn fixture(name: &str) -> SaveFile {
+    load_from_path(&Path::new(FIXTURES).join(name)).expect("fixture loads")
+}
+
+#[test]
+fn checked_file_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.ron");
+    let body = "(anything: \"at all\")\n";
+
+    write_checked(&path, 7, body).unwrap();
+    assert_eq!(read_checked(&path).unwrap(), (7, body.to_string()));
+    // Nothing left over from the atomic write.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn changed_body_fails_the_checksum() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.ron");
+    write_checked(&path, 3, "(score: 10)\n").unwrap();
+
+    let text = fs::read_to_string(&path).unwrap().replace("10", "99");
+    fs::write(&path, text).unwrap();
+    assert!(matches!(
+        error_of(read_checked(&path)),
+        SaveError::ChecksumMismatch { .. }
+    ));
+}
+
+#[test]
+fn malformed_headers_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.ron");
+    for text in [
+        "// krabbels-save version=3 checksum=0000000000000000",
+        "// krabbels-save version=3\n()\n",
+        "// krabbels-save checksum=0000000000000000\n()\n",
+        "// krabbels-save version=x checksum=0000000000000000\n()\n",
+        "// krabbels-save version=3 checksum=0000000000000000 extra\n()\n",
+    ] {
+        fs::write(&path, text).unwrap();
+        assert_eq!(error_of(read_checked(&path)), SaveError::MalformedHeader);
+    }
+}
+
+#[test]
+fn newer_versions_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.ron");
+    write_checked(&path, 99, "()\n").unwrap();
+    assert_eq!(
+        error_of(load_from_path(&path)),
+        SaveError::UnsupportedVersion { version: 99 }
+    );
+}
+
+fn duel() -> SaveFile {
+    let mut game = Game::new();
+    game.add_player("Alice".to_string());
+    game.add_player("Bob".to_string());
+    SaveFile {
+        title: "duel".to_string(),
+        created_at_unix: 1,
+        updated_at_unix: 2,
+        game,
+    }
+}
+
+#[test]
+fn save_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.ron");
+    let save = duel();
+
+    save_to_path(&path, &save).unwrap();
+    let loaded = load_from_path(&path).unwrap();
+    assert_eq!(loaded.title, "duel");
+    assert_eq!(loaded.updated_at_unix, 2);
+    assert_eq!(loaded.game.bag.tiles, save.game.bag.tiles);
+    assert_eq!(
+        loaded.game.players[1].rack.tiles,
+        save.game.players[1].rack.tiles
+    );
+}
+
+#[test]
+fn inconsistent_games_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.ron");
+
+    let mut lost_tile = duel();
+    lost_tile.game.bag.tiles.pop();
+    let mut floating = duel();
+    let tile = floating.game.bag.tiles.pop().unwrap();
+    floating.game.board.cells[0][0].letter = Some(tile.letter);
+    floating.game.board.cells[0][0].is_blank = tile.is_blank;
+    let mut inflated = duel();
+    inflated.game.players[1].score = 12;
+
+    for save in [lost_tile, floating, inflated] {
+        // The checksum is right; the game itself isn't.
+        save_to_path(&path, &save).unwrap();
+        assert!(matches!(
+            error_of(load_from_path(&path)),
+            SaveError::Integrity(_)
+        ));
+    }
+}
+
+#[test]
+fn headerless_v1_save_is_migrated() {
+    let save = fixture("save_v1.ron");
+    assert_eq!(save.created_at_unix, 1_600_000_000);
+    assert_eq!(save.title, "game_1600000000");
+    let names: Vec<_> = save.game.players.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["Alice"]);
+    assert_eq!(save.game.players[0].rack.tiles.len(), 7);
+}
+
+#[test]
+fn v2_save_is_migrated() {
+    let save = fixture("save_v2.ron");
+    assert_eq!(save.created_at_unix, 1_650_000_000);
+    assert_eq!(save.updated_at_unix, 1_650_000_000);
+    let names: Vec<_> = save.game.players.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["Alice", "Bob"]);
+    assert!(save.game.history.is_empty());
+}
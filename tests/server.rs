@@ -0,0 +1,365 @@
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use krabbels::error::{MoveError, ServerError};
+use krabbels::game::board::Direction;
+use krabbels::game::clock::TimeControl;
+use krabbels::game::lexicon::Lexicon;
+use krabbels::game::rules::{RulePreset, RuleSet};
+use krabbels::game::view::GameView;
+use krabbels::game::{GameEvent, GameOptions};
+use krabbels::net::lobby::GameSummary;
+use krabbels::net::protocol::{
+    ClientMessage, PROTOCOL_VERSION, Play, ServerMessage, read_message, write_message,
+};
+use krabbels::net::server::{self, ServerConfig};
+use tempfile::TempDir;
+
+const PATIENCE: Duration = Duration::from_secs(5);
+
+// A server on a free localhost port, with its data directory.
+struct TestServer {
+    addr: String,
+    _data_dir: TempDir,
+}
+
+fn start_server() -> TestServer {
+    start_server_with(ServerConfig::default())
+}
+
+fn start_server_with(config: ServerConfig) -> TestServer {
+    let data_dir = tempfile::tempdir().unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let config = ServerConfig {
+        data_dir: PathBuf::from(data_dir.path()),
+        ..config
+    };
+    thread::spawn(move || server::serve(listener, config));
+    TestServer {
+        addr,
+        _data_dir: data_dir,
+    }
+}
+
+struct Client {
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Client {
+    fn connect(server: &TestServer) -> Self {
+        let stream = TcpStream::connect(&server.addr).unwrap();
+        stream.set_read_timeout(Some(PATIENCE)).unwrap();
+        Self {
+            writer: stream.try_clone().unwrap(),
+            reader: BufReader::new(stream),
+        }
+    }
+
+    fn send(&mut self, msg: &ClientMessage) {
+        write_message(&mut self.writer, msg).unwrap();
+    }
+
+    fn recv(&mut self) -> ServerMessage {
+        read_message(&mut self.reader)
+            .expect("a message before the timeout")
+            .expect("the connection still open")
+    }
+
+    // Skips whatever comes first until a message `pick` accepts.
+    fn wait_for<T>(&mut self, mut pick: impl FnMut(ServerMessage) -> Option<T>) -> T {
+        let deadline = Instant::now() + PATIENCE;
+        while Instant::now() < deadline {
+            if let Some(found) = pick(self.recv()) {
+                return found;
+            }
+        }
+        panic!("expected message never came");
+    }
+
+    fn hello(&mut self, name: &str, token: Option<String>) -> ServerMessage {
+        self.send(&ClientMessage::Hello {
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+            token,
+        });
+        self.recv()
+    }
+
+    fn login(server: &TestServer, name: &str) -> (Self, String) {
+        let mut client = Self::connect(server);
+        match client.hello(name, None) {
+            ServerMessage::Welcome { token, .. } => (client, token),
+            other => panic!("no welcome: {other:?}"),
+        }
+    }
+
+    fn state(&mut self) -> (u32, GameView) {
+        self.wait_for(|msg| match msg {
+            ServerMessage::State { game_id, view } => Some((game_id, *view)),
+            _ => None,
+        })
+    }
+}
+
+// Two players at a new two-seat game, both past the first board.
+fn duel(
+    server: &TestServer,
+    time_control: Option<TimeControl>,
+    rules: Option<RulePreset>,
+) -> (Client, Client, u32, GameView) {
+    let (mut ann, _) = Client::login(server, "Ann");
+    let (mut bob, _) = Client::login(server, "Bob");
+
+    ann.send(&ClientMessage::CreateGame {
+        seats: 2,
+        time_control,
+        rules,
+    });
+    let game_id = ann.wait_for(|msg| match msg {
+        ServerMessage::Joined { game_id, seat: 0 } => Some(game_id),
+        _ => None,
+    });
+    bob.send(&ClientMessage::JoinGame { game_id });
+    bob.wait_for(|msg| matches!(msg, ServerMessage::Joined { seat: 1, .. }).then_some(()));
+
+    let (_, view) = ann.state();
+    bob.state();
+    (ann, bob, game_id, view)
+}
+
+#[test]
+fn handshake_checks_the_protocol_version() {
+    let server = start_server();
+
+    let mut client = Client::connect(&server);
+    client.send(&ClientMessage::Hello {
+        version: PROTOCOL_VERSION + 1,
+        name: "Ann".to_string(),
+        token: None,
+    });
+    assert!(matches!(
+        client.recv(),
+        ServerMessage::Error {
+            error: ServerError::UnsupportedVersion {
+                server: PROTOCOL_VERSION,
+                ..
+            }
+        }
+    ));
+
+    // Nothing else is answered before a hello.
+    client.send(&ClientMessage::ListGames);
+    assert!(matches!(
+        client.recv(),
+        ServerMessage::Error {
+            error: ServerError::HelloFirst
+        }
+    ));
+
+    match client.hello("Ann", None) {
+        ServerMessage::Welcome { version, token } => {
+            assert_eq!(version, PROTOCOL_VERSION);
+            assert!(!token.is_empty());
+        }
+        other => panic!("no welcome: {other:?}"),
+    }
+    client.send(&ClientMessage::ListGames);
+    assert!(matches!(
+        client.recv(),
+        ServerMessage::GameList { games } if games.is_empty()
+    ));
+}
+
+#[test]
+fn a_name_takes_its_token() {
+    let server = start_server();
+    let (_ann, token) = Client::login(&server, "Ann");
+
+    let mut impostor = Client::connect(&server);
+    assert!(matches!(
+        impostor.hello("Ann", None),
+        ServerMessage::Error {
+            error: ServerError::NameTaken
+        }
+    ));
+    assert!(matches!(
+        impostor.hello("Ann", Some("0000000000000000".to_string())),
+        ServerMessage::Error { .. }
+    ));
+
+    let mut again = Client::connect(&server);
+    match again.hello("Ann", Some(token.clone())) {
+        ServerMessage::Welcome { token: same, .. } => assert_eq!(same, token),
+        other => panic!("no welcome: {other:?}"),
+    }
+}
+
+#[test]
+fn moves_reach_the_opponent() {
+    let server = start_server();
+    let (mut ann, mut bob, game_id, view) = duel(&server, None, None);
+    assert_eq!(view.current_player_index, 0);
+
+    // Without a word list any two letters make a word.
+    let word: String = view.rack.iter().filter(|&&c| c != '_').take(2).collect();
+    ann.send(&ClientMessage::Play {
+        game_id,
+        play: Play::PlaceWord {
+            row: 7,
+            col: 7,
+            dir: Direction::Across,
+            word: word.clone(),
+        },
+    });
+
+    bob.wait_for(|msg| matches!(msg, ServerMessage::Event { .. }).then_some(()));
+    let (seen_in, view) = bob.state();
+    assert_eq!(seen_in, game_id);
+    assert_eq!(&view.board[7][7..9], word);
+    assert_eq!(view.current_player_index, 1);
+    assert!(view.players[0].score > 0);
+    assert_eq!(view.rack.len(), 7);
+}
+
+#[test]
+fn out_of_turn_moves_are_rejected() {
+    let server = start_server();
+    let (mut ann, mut bob, game_id, _) = duel(&server, None, None);
+
+    bob.send(&ClientMessage::Play {
+        game_id,
+        play: Play::Pass,
+    });
+    match bob.recv() {
+        ServerMessage::MoveRejected {
+            game_id: id,
+            reason,
+        } => {
+            assert_eq!(id, game_id);
+            assert_eq!(reason, ServerError::NotYourTurn);
+        }
+        other => panic!("move not rejected: {other:?}"),
+    }
+
+    // Ann still has the move.
+    ann.send(&ClientMessage::Play {
+        game_id,
+        play: Play::Pass,
+    });
+    let (_, view) = bob.state();
+    assert_eq!(view.current_player_index, 1);
+}
+
+#[test]
+fn a_flagged_player_loses_without_moving() {
+    let server = start_server();
+    let no_time = TimeControl {
+        initial_secs: 0,
+        increment_secs: 0,
+        forfeit_on_timeout: true,
+    };
+    let (_ann, mut bob, _, _) = duel(&server, Some(no_time), None);
+
+    let view = bob.wait_for(|msg| match msg {
+        ServerMessage::State { view, .. } if view.game_over => Some(view),
+        _ => None,
+    });
+    assert_eq!(view.revealed_racks.len(), 2);
+}
+
+#[test]
+fn hosted_games_follow_the_server_settings() {
+    let server = start_server_with(ServerConfig {
+        game: GameOptions {
+            rules: RuleSet {
+                rack_size: 9,
+                ..RuleSet::default()
+            },
+            ..GameOptions::default()
+        },
+        time_control: TimeControl::parse("25+5"),
+        ..ServerConfig::default()
+    });
+    let (_ann, _bob, _, view) = duel(&server, None, None);
+
+    assert_eq!(view.rack.len(), 9);
+    assert!(view.players.iter().all(|p| p.time_left_ms.is_some()));
+}
+
+#[test]
+fn presets_are_played_and_phonies_challenged() {
+    let server = start_server_with(ServerConfig {
+        game: GameOptions {
+            lexicon: Some(Arc::new(Lexicon::from_words(["CHAT"]))),
+            ..GameOptions::default()
+        },
+        ..ServerConfig::default()
+    });
+    let (mut ann, mut bob, game_id, view) = duel(&server, None, Some(RulePreset::Tournament));
+
+    ann.send(&ClientMessage::ListGames);
+    let games: Vec<GameSummary> = ann.wait_for(|msg| match msg {
+        ServerMessage::GameList { games } => Some(games),
+        _ => None,
+    });
+    assert_eq!(games[0].rules, Some(RulePreset::Tournament));
+
+    // Under double challenge the phony goes down, and comes back off.
+    let word: String = view.rack.iter().filter(|&&c| c != '_').take(2).collect();
+    ann.send(&ClientMessage::Play {
+        game_id,
+        play: Play::PlaceWord {
+            row: 7,
+            col: 7,
+            dir: Direction::Across,
+            word,
+        },
+    });
+    bob.state();
+    bob.send(&ClientMessage::Play {
+        game_id,
+        play: Play::Challenge,
+    });
+    let upheld = bob.wait_for(|msg| match msg {
+        ServerMessage::Event {
+            event: GameEvent::ChallengeResolved { upheld, .. },
+            ..
+        } => Some(upheld),
+        ServerMessage::MoveRejected { reason, .. } => panic!("challenge refused: {reason:?}"),
+        _ => None,
+    });
+    assert!(upheld);
+    let (_, view) = bob.state();
+    assert_eq!(view.board[7], ".".repeat(15));
+    assert_eq!(view.players[0].score, 0);
+}
+
+#[test]
+fn errors_go_out_as_codes() {
+    let json = |msg: &ServerMessage| serde_json::to_string(msg).unwrap();
+    assert_eq!(
+        json(&ServerMessage::Error {
+            error: ServerError::NoGame { game_id: 9 }
+        }),
+        r#"{"type":"error","error":{"code":"no_game","detail":{"game_id":9}}}"#
+    );
+    assert_eq!(
+        json(&ServerMessage::Error {
+            error: ServerError::HelloFirst
+        }),
+        r#"{"type":"error","error":{"code":"hello_first"}}"#
+    );
+    assert_eq!(
+        json(&ServerMessage::MoveRejected {
+            game_id: 1,
+            reason: ServerError::Move(MoveError::MissingLetter { letter: 'Z' }),
+        }),
+        r#"{"type":"move_rejected","game_id":1,"reason":{"code":"move","detail":{"missing_letter":{"letter":"Z"}}}}"#
+    );
+}